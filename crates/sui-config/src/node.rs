@@ -13,6 +13,7 @@ use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use std::collections::BTreeMap;
 use std::net::SocketAddr;
+use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::usize;
@@ -98,6 +99,9 @@ pub struct NodeConfig {
 
     #[serde(default)]
     pub indirect_objects_threshold: usize,
+
+    #[serde(default)]
+    pub subscription_config: SubscriptionConfig,
 }
 
 fn default_authority_store_pruning_config() -> AuthorityStorePruningConfig {
@@ -324,6 +328,48 @@ impl AuthorityStorePruningConfig {
     }
 }
 
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SubscriptionConfig {
+    /// Maximum number of items queued for each subscriber of a JSON-RPC subscription.
+    ///
+    /// If unspecified, this will default to `1000`.
+    #[serde(default = "default_subscriber_queue_capacity")]
+    pub subscriber_queue_capacity: NonZeroUsize,
+
+    /// What to do when a subscriber's queue is full.
+    ///
+    /// If unspecified, this will default to `drop-oldest`.
+    #[serde(default)]
+    pub overflow_policy: SubscriberOverflowPolicy,
+}
+
+fn default_subscriber_queue_capacity() -> NonZeroUsize {
+    NonZeroUsize::new(1000).unwrap()
+}
+
+impl Default for SubscriptionConfig {
+    fn default() -> Self {
+        Self {
+            subscriber_queue_capacity: default_subscriber_queue_capacity(),
+            overflow_policy: SubscriberOverflowPolicy::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SubscriberOverflowPolicy {
+    /// Drop the oldest queued items and notify the subscriber of how many items it missed.
+    #[default]
+    DropOldest,
+    /// Notify the subscriber that it lagged behind, then close the subscription.
+    Disconnect,
+    /// Wait for the subscriber to catch up. This holds up dispatching to all other subscribers,
+    /// and once the dispatch buffer is full, the node's event processing.
+    Block,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct MetricsConfig {
@@ -13,6 +13,7 @@ use chrono::prelude::*;
 use fastcrypto::encoding::Base58;
 use fastcrypto::encoding::Encoding;
 use fastcrypto::traits::KeyPair;
+use futures::{Stream, StreamExt};
 use itertools::Itertools;
 use move_binary_format::compatibility::Compatibility;
 use move_binary_format::CompiledModule;
@@ -28,9 +29,10 @@ use prometheus::{
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use tap::TapFallible;
-use tokio::sync::mpsc::unbounded_channel;
+use tokio::sync::mpsc::{self, unbounded_channel};
 use tokio::sync::oneshot;
 use tokio_retry::strategy::{jitter, ExponentialBackoff};
+use tokio_stream::wrappers::ReceiverStream;
 use tracing::{debug, error, error_span, info, instrument, trace, warn, Instrument};
 
 pub use authority_notify_read::EffectsNotifyRead;
@@ -44,14 +46,14 @@ use shared_crypto::intent::{Intent, IntentScope};
 use sui_adapter::execution_engine;
 use sui_adapter::{adapter, execution_mode};
 use sui_config::genesis::Genesis;
-use sui_config::node::{AuthorityStorePruningConfig, DBCheckpointConfig};
+use sui_config::node::{AuthorityStorePruningConfig, DBCheckpointConfig, SubscriptionConfig};
 use sui_json_rpc_types::{
     DevInspectResults, DryRunTransactionResponse, SuiEvent, SuiEventEnvelope, SuiMoveValue,
     SuiTransactionEvents,
 };
 use sui_macros::{fail_point, nondeterministic};
 use sui_protocol_config::{ProtocolConfig, SupportedProtocolVersions};
-use sui_storage::indexes::{ObjectIndexChanges, MAX_GET_OWNED_OBJECT_SIZE};
+use sui_storage::indexes::{EventId, ObjectIndexChanges, MAX_GET_OWNED_OBJECT_SIZE};
 use sui_storage::write_ahead_log::WriteAheadLog;
 use sui_storage::{
     write_ahead_log::{DBTxGuard, TxGuard},
@@ -63,7 +65,8 @@ use sui_types::crypto::{user_hash, AuthorityKeyPair, NetworkKeyPair, Signer};
 use sui_types::digests::TransactionEventsDigest;
 use sui_types::dynamic_field::{DynamicFieldInfo, DynamicFieldName, DynamicFieldType, Field};
 use sui_types::error::UserInputError;
-use sui_types::event::{Event, EventID};
+use sui_types::event::{Event, EventEnvelope, EventID};
+use sui_types::filter::{EventFilter, Filter};
 use sui_types::gas::{GasCostSummary, GasPrice, SuiCostTable, SuiGasStatus};
 use sui_types::message_envelope::Message;
 use sui_types::messages_checkpoint::{
@@ -107,8 +110,14 @@ use crate::epoch::epoch_metrics::EpochMetrics;
 use crate::execution_driver::execution_process;
 use crate::module_cache_metrics::ResolverMetrics;
 use crate::stake_aggregator::StakeAggregator;
+use crate::streamer::StreamItem;
 use crate::{
-    event_handler::EventHandler, transaction_input_checker, transaction_manager::TransactionManager,
+    event_handler::{
+        EventHandler, EVENT_DISPATCH_BUFFER_SIZE, EVENT_REPLAY_BATCH_SIZE,
+        EVENT_REPLAY_COMMIT_WAIT_INTERVAL, EVENT_REPLAY_COMMIT_WAIT_RETRIES,
+    },
+    transaction_input_checker,
+    transaction_manager::TransactionManager,
 };
 
 #[cfg(test)]
@@ -580,11 +589,16 @@ impl AuthorityState {
         }
 
         // Checks to see if the transaction has expired
-        if match &transaction.inner().data().transaction_data().expiration() {
-            TransactionExpiration::None => false,
-            TransactionExpiration::Epoch(epoch) => *epoch < epoch_store.epoch(),
-        } {
-            return Err(SuiError::TransactionExpired);
+        if let TransactionExpiration::Epoch(expiration_epoch) =
+            transaction.inner().data().transaction_data().expiration()
+        {
+            if *expiration_epoch < epoch_store.epoch() {
+                return Err(UserInputError::TransactionExpired {
+                    expiration_epoch: *expiration_epoch,
+                    current_epoch: epoch_store.epoch(),
+                }
+                .into());
+            }
         }
 
         let signed = self.handle_transaction_impl(transaction, epoch_store).await;
@@ -1445,6 +1459,16 @@ impl AuthorityState {
                 self.metrics
                     .post_processing_total_events_emitted
                     .inc_by(events.data.len() as u64);
+
+                self.event_handler
+                    .process_tx(certificate.data(), effects, events, timestamp_ms)
+                    .await
+                    .tap_err(|e| {
+                        warn!(
+                            ?tx_digest,
+                            "Post processing - Couldn't stream transaction: {}", e
+                        )
+                    })?;
             }
         };
 
@@ -1587,6 +1611,7 @@ impl AuthorityState {
         pruning_config: AuthorityStorePruningConfig,
         genesis_objects: &[Object],
         db_checkpoint_config: &DBCheckpointConfig,
+        subscription_config: SubscriptionConfig,
     ) -> Arc<Self> {
         Self::check_protocol_version(supported_protocol_versions, epoch_store.protocol_version());
 
@@ -1615,7 +1640,7 @@ impl AuthorityState {
             epoch_store: ArcSwap::new(epoch_store.clone()),
             database: store.clone(),
             indexes,
-            event_handler: Arc::new(EventHandler::default()),
+            event_handler: Arc::new(EventHandler::new(subscription_config)),
             checkpoint_store,
             committee_store,
             transaction_manager,
@@ -1716,6 +1741,7 @@ impl AuthorityState {
             AuthorityStorePruningConfig::default(),
             genesis.objects(),
             &DBCheckpointConfig::default(),
+            SubscriptionConfig::default(),
         )
         .await;
 
@@ -2406,6 +2432,64 @@ impl AuthorityState {
             .ok_or_else(|| anyhow!("Checkpoint contents not found for digest: {:?}", digest))
     }
 
+    /// Returns up to `limit` executed checkpoints following the exclusive `cursor`, ordered by
+    /// sequence number.
+    pub fn get_checkpoints(
+        &self,
+        cursor: Option<CheckpointSequenceNumber>,
+        limit: u64,
+        descending_order: bool,
+    ) -> Result<Vec<VerifiedCheckpoint>, anyhow::Error> {
+        let checkpoint_store = self.get_checkpoint_store();
+        let highest = match checkpoint_store.get_highest_executed_checkpoint_seq_number()? {
+            Some(highest) => highest,
+            None => return Ok(vec![]),
+        };
+        let sequence_numbers: Vec<_> = if descending_order {
+            let start = match cursor {
+                Some(0) => return Ok(vec![]),
+                Some(cursor) => (cursor - 1).min(highest),
+                None => highest,
+            };
+            (0..=start).rev().take(limit as usize).collect()
+        } else {
+            let start = cursor.map_or(0, |cursor| cursor.saturating_add(1));
+            (start..=highest).take(limit as usize).collect()
+        };
+        let checkpoints =
+            checkpoint_store.multi_get_checkpoint_by_sequence_number(&sequence_numbers)?;
+        sequence_numbers
+            .into_iter()
+            .zip(checkpoints)
+            .map(|(sequence_number, checkpoint)| {
+                checkpoint.ok_or_else(|| {
+                    anyhow!(
+                        "Verified checkpoint not found for sequence number {}",
+                        sequence_number
+                    )
+                })
+            })
+            .collect()
+    }
+
+    pub fn multi_get_checkpoint_contents(
+        &self,
+        digests: &[CheckpointContentsDigest],
+    ) -> Result<Vec<CheckpointContents>, anyhow::Error> {
+        let contents = self
+            .get_checkpoint_store()
+            .multi_get_checkpoint_contents(digests)?;
+        digests
+            .iter()
+            .zip(contents)
+            .map(|(digest, contents)| {
+                contents.ok_or_else(|| {
+                    anyhow!("Checkpoint contents not found for digest: {:?}", digest)
+                })
+            })
+            .collect()
+    }
+
     pub fn get_checkpoint_contents_by_sequence_number(
         &self,
         sequence_number: CheckpointSequenceNumber,
@@ -2496,6 +2580,9 @@ impl AuthorityState {
             EventQuery::EventType(event_type) => {
                 index_store.events_by_type(&event_type, tx_num, event_num, limit, descending)?
             }
+            EventQuery::And(_) | EventQuery::Or(_) | EventQuery::Not(_) => {
+                index_store.events_by_query(&query, tx_num, event_num, limit, descending)?
+            }
         };
 
         // skip one event if exclusive cursor is provided,
@@ -2546,6 +2633,143 @@ impl AuthorityState {
         Ok(events)
     }
 
+    /// Reads up to `limit` indexed events positioned strictly after `position` and returns the
+    /// ones matching `filter`, together with the position of the last event read, if any.
+    pub fn get_event_envelopes_after(
+        &self,
+        position: EventId,
+        filter: &EventFilter,
+        limit: usize,
+    ) -> SuiResult<(Vec<EventEnvelope>, Option<EventId>)> {
+        let index_store = self.get_indexes()?;
+        let event_keys = index_store.events_after(position.0, position.1, limit)?;
+        let last_read = event_keys.last().map(|(id, _)| *id);
+
+        let stored_events = self.database.perpetual_tables.events.multi_get(
+            event_keys
+                .iter()
+                .map(|((_, event_seq), (digest, _, _))| (*digest, *event_seq)),
+        )?;
+
+        let module_cache = self.epoch_store.load().module_cache().clone();
+        let mut envelopes = vec![];
+        for (event, ((tx_seq, event_seq), (digest, tx_digest, timestamp))) in
+            stored_events.into_iter().zip(event_keys)
+        {
+            let event = event.ok_or(SuiError::TransactionEventsNotFound { digest })?;
+            let envelope = EventHandler::create_envelope(
+                &event,
+                tx_digest,
+                event_seq as u64,
+                tx_seq,
+                timestamp,
+                &module_cache,
+            )?;
+            if filter.matches(&envelope) {
+                envelopes.push(envelope);
+            }
+        }
+        Ok((envelopes, last_read))
+    }
+
+    /// Subscribe to events matching `filter` that were indexed after the exclusive `cursor`.
+    /// Stored events are replayed first, then the subscription switches over to live events
+    /// from the event handler, without gaps or duplicates in between. The replay waits for
+    /// transactions sequenced before the subscription to be committed to the store.
+    pub fn subscribe_events_from(
+        self: &Arc<Self>,
+        filter: EventFilter,
+        cursor: EventID,
+    ) -> SuiResult<impl Stream<Item = StreamItem<EventEnvelope>>> {
+        let index_store = self.get_indexes()?;
+        let tx_seq = index_store.get_transaction_seq(&cursor.tx_digest)?.ok_or(
+            SuiError::TransactionNotFound {
+                digest: cursor.tx_digest,
+            },
+        )?;
+        let cursor: EventId = (tx_seq, cursor.event_seq as usize);
+
+        // Register the live subscription before recording the sequence number it starts at.
+        // Transactions sequenced from there on are dispatched to the live stream, everything
+        // before is replayed from the store.
+        let live = self.event_handler.subscribe(filter.clone());
+        // `next_sequence_number` is one past the next sequence number to be allocated.
+        let subscribed_at = index_store.next_sequence_number() - 1;
+
+        let (tx, rx) = mpsc::channel(EVENT_DISPATCH_BUFFER_SIZE);
+        let state = self.clone();
+        spawn_monitored_task!(async move {
+            // Transactions sequenced before the subscription may still be being written to the
+            // store, and their live events are not forwarded, wait for them to be replayed.
+            let mut from = cursor.0 + 1;
+            let mut retries = 0;
+            loop {
+                match index_store.first_uncommitted_sequence_number(from, subscribed_at) {
+                    Ok(None) => break,
+                    Ok(Some(seq)) if retries < EVENT_REPLAY_COMMIT_WAIT_RETRIES => {
+                        from = seq;
+                        retries += 1;
+                        tokio::time::sleep(EVENT_REPLAY_COMMIT_WAIT_INTERVAL).await;
+                    }
+                    Ok(Some(seq)) => {
+                        // Indexing the transaction failed, it has no events to replay.
+                        warn!(seq, "Transaction was not committed to the index store.");
+                        break;
+                    }
+                    Err(e) => {
+                        warn!(error = ?e, "Failed to replay events, closing subscription.");
+                        return;
+                    }
+                }
+            }
+
+            let mut position = cursor;
+            loop {
+                let (envelopes, last_read) = match state.get_event_envelopes_after(
+                    position,
+                    &filter,
+                    EVENT_REPLAY_BATCH_SIZE,
+                ) {
+                    Ok(result) => result,
+                    Err(e) => {
+                        warn!(error = ?e, "Failed to replay events, closing subscription.");
+                        return;
+                    }
+                };
+                for envelope in envelopes {
+                    if envelope.seq_num >= subscribed_at {
+                        break;
+                    }
+                    if tx.send(StreamItem::Data(envelope)).await.is_err() {
+                        return;
+                    }
+                }
+                match last_read {
+                    Some(last_read) if last_read.0 < subscribed_at => position = last_read,
+                    _ => break,
+                }
+            }
+            debug!(
+                replayed_to = ?position,
+                "Finished replaying events, switching to live events."
+            );
+
+            let mut live = Box::pin(live);
+            while let Some(item) = live.next().await {
+                if let StreamItem::Data(envelope) = &item {
+                    let key = (envelope.seq_num, envelope.event_num as usize);
+                    if key <= cursor || envelope.seq_num < subscribed_at {
+                        continue;
+                    }
+                }
+                if tx.send(item).await.is_err() {
+                    return;
+                }
+            }
+        });
+        Ok(ReceiverStream::new(rx))
+    }
+
     pub async fn insert_genesis_object(&self, object: Object) {
         self.database
             .insert_genesis_object(object)
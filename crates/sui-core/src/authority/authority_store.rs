@@ -349,6 +349,11 @@ impl AuthorityStore {
         self.perpetual_tables.database_is_empty()
     }
 
+    /// Manually compacts the named perpetual table.
+    pub fn compact_perpetual_table(&self, table_name: &str) -> SuiResult {
+        self.perpetual_tables.compact_table(table_name)
+    }
+
     /// A function that acquires all locks associated with the objects (in order to avoid deadlocks).
     async fn acquire_locks(&self, input_objects: &[ObjectRef]) -> Vec<MutexGuard> {
         self.mutex_table
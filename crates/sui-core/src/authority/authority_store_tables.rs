@@ -12,7 +12,9 @@ use sui_types::digests::TransactionEventsDigest;
 use sui_types::storage::ObjectStore;
 use typed_store::metrics::SamplingInterval;
 use typed_store::rocks::util::{empty_compaction_filter, reference_count_merge_operator};
-use typed_store::rocks::{DBBatch, DBMap, DBOptions, MetricConf, ReadWriteOptions};
+use typed_store::rocks::{
+    DBBatch, DBMap, DBOptions, MetricConf, ReadWriteOptions, TypedStoreError,
+};
 use typed_store::traits::{Map, TableSummary, TypedStoreDebug};
 
 use crate::authority::authority_store_types::{
@@ -224,6 +226,21 @@ impl AuthorityPerpetualTables {
             .checkpoint_db(path)
             .map_err(SuiError::StorageError)
     }
+
+    /// Runs a manual compaction over the whole key range of the named table.
+    pub fn compact_table(&self, table_name: &str) -> SuiResult {
+        let unregistered =
+            || SuiError::StorageError(TypedStoreError::UnregisteredColumn(table_name.to_string()));
+        // Only the perpetual tables can be compacted, not any other column family of the db.
+        if !Self::describe_tables().contains_key(table_name) {
+            return Err(unregistered());
+        }
+        // All tables share the same db, so the handle of any of them can reach every column family.
+        let db = &self.objects.rocksdb;
+        let cf = db.cf_handle(table_name).ok_or_else(unregistered)?;
+        db.compact_range_cf(&cf, None::<Vec<u8>>, None::<Vec<u8>>);
+        Ok(())
+    }
 }
 
 impl ObjectStore for AuthorityPerpetualTables {
@@ -332,3 +349,17 @@ fn indirect_move_objects_table_default_config() -> DBOptions {
         .set_compaction_filter("empty filter", empty_compaction_filter);
     options
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compact_table_only_accepts_perpetual_tables() {
+        let dir = tempfile::tempdir().unwrap();
+        let tables = AuthorityPerpetualTables::open(dir.path(), None);
+        tables.compact_table("objects").unwrap();
+        assert!(tables.compact_table("default").is_err());
+        assert!(tables.compact_table("no_such_table").is_err());
+    }
+}
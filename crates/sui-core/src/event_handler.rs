@@ -1,14 +1,18 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::time::Duration;
+
 use move_bytecode_utils::module_cache::SyncModuleCache;
 use tokio_stream::Stream;
 use tracing::{debug, error, instrument, trace};
 
+use sui_config::node::SubscriptionConfig;
 use sui_json_rpc_types::SuiMoveStruct;
 use sui_types::base_types::TransactionDigest;
 use sui_types::filter::EventFilter;
-use sui_types::messages::TransactionEvents;
+use sui_types::messages::{SenderSignedData, TransactionEnvelope, TransactionEvents};
+use sui_types::query::{ObjectChangeFilter, TransactionFilter};
 use sui_types::{
     error::{SuiError, SuiResult},
     event::{Event, EventEnvelope},
@@ -16,28 +20,62 @@ use sui_types::{
 };
 
 use crate::authority::{AuthorityStore, ResolverWrapper};
-use crate::streamer::Streamer;
+use crate::streamer::{StreamItem, Streamer};
 
 #[cfg(test)]
 #[path = "unit_tests/event_handler_tests.rs"]
 mod event_handler_tests;
 
 pub const EVENT_DISPATCH_BUFFER_SIZE: usize = 1000;
+/// Number of stored events read per batch when replaying events to a resumed subscription.
+pub const EVENT_REPLAY_BATCH_SIZE: usize = 100;
+/// How often, and how many times, a resumed subscription checks whether the transactions
+/// sequenced before it are committed to the store before replaying their events.
+pub const EVENT_REPLAY_COMMIT_WAIT_INTERVAL: Duration = Duration::from_millis(50);
+pub const EVENT_REPLAY_COMMIT_WAIT_RETRIES: usize = 100;
 
 pub struct EventHandler {
     event_streamer: Streamer<EventEnvelope, EventFilter>,
+    transaction_streamer: Streamer<TransactionEnvelope, TransactionFilter>,
+    object_change_streamer: Streamer<TransactionEnvelope, ObjectChangeFilter>,
 }
 
 impl Default for EventHandler {
     fn default() -> Self {
-        let streamer = Streamer::spawn(EVENT_DISPATCH_BUFFER_SIZE);
-        Self {
-            event_streamer: streamer,
-        }
+        Self::new(SubscriptionConfig::default())
     }
 }
 
 impl EventHandler {
+    pub fn new(subscription_config: SubscriptionConfig) -> Self {
+        Self {
+            event_streamer: Streamer::spawn(EVENT_DISPATCH_BUFFER_SIZE, subscription_config),
+            transaction_streamer: Streamer::spawn(EVENT_DISPATCH_BUFFER_SIZE, subscription_config),
+            object_change_streamer: Streamer::spawn(
+                EVENT_DISPATCH_BUFFER_SIZE,
+                subscription_config,
+            ),
+        }
+    }
+
+    #[instrument(level = "debug", skip_all, fields(tx_digest=?effects.transaction_digest()), err)]
+    pub async fn process_tx(
+        &self,
+        transaction: &SenderSignedData,
+        effects: &TransactionEffects,
+        events: &TransactionEvents,
+        timestamp_ms: u64,
+    ) -> SuiResult {
+        let envelope = TransactionEnvelope {
+            timestamp_ms,
+            transaction: transaction.clone(),
+            effects: effects.clone(),
+            events: events.clone(),
+        };
+        self.object_change_streamer.send(envelope.clone()).await?;
+        self.transaction_streamer.send(envelope).await
+    }
+
     #[instrument(level = "debug", skip_all, fields(seq=?seq_num, tx_digest=?effects.transaction_digest()), err)]
     pub async fn process_events(
         &self,
@@ -52,7 +90,7 @@ impl EventHandler {
             .iter()
             .enumerate()
             .map(|(event_num, e)| {
-                self.create_envelope(
+                Self::create_envelope(
                     e,
                     *effects.transaction_digest(),
                     event_num.try_into().unwrap(),
@@ -80,8 +118,7 @@ impl EventHandler {
         Ok(())
     }
 
-    fn create_envelope(
-        &self,
+    pub(crate) fn create_envelope(
         event: &Event,
         digest: TransactionDigest,
         event_num: u64,
@@ -112,7 +149,23 @@ impl EventHandler {
         ))
     }
 
-    pub fn subscribe(&self, filter: EventFilter) -> impl Stream<Item = EventEnvelope> {
+    pub fn subscribe(&self, filter: EventFilter) -> impl Stream<Item = StreamItem<EventEnvelope>> {
         self.event_streamer.subscribe(filter)
     }
+
+    pub fn subscribe_transactions(
+        &self,
+        filter: TransactionFilter,
+    ) -> impl Stream<Item = StreamItem<TransactionEnvelope>> {
+        self.transaction_streamer.subscribe(filter)
+    }
+
+    /// Subscribe to the transactions which may change objects matching the filter, the object
+    /// changes are resolved by the subscriber.
+    pub fn subscribe_object_changes(
+        &self,
+        filter: ObjectChangeFilter,
+    ) -> impl Stream<Item = StreamItem<TransactionEnvelope>> {
+        self.object_change_streamer.subscribe(filter)
+    }
 }
@@ -1,28 +1,63 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::event_handler::EVENT_DISPATCH_BUFFER_SIZE;
-use futures::Stream;
+use futures::stream::BoxStream;
+use futures::StreamExt;
 use mysten_metrics::spawn_monitored_task;
 use std::collections::BTreeMap;
 use std::fmt::Debug;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use sui_config::node::{SubscriberOverflowPolicy, SubscriptionConfig};
 use sui_types::base_types::ObjectID;
 use sui_types::error::SuiError;
 use sui_types::filter::Filter;
 use tokio::runtime::Handle;
-use tokio::sync::mpsc::Sender;
-use tokio::sync::{mpsc, RwLock};
-use tokio_stream::wrappers::ReceiverStream;
+use tokio::sync::mpsc::error::{TryRecvError, TrySendError};
+use tokio::sync::mpsc::{Receiver, Sender};
+use tokio::sync::{broadcast, mpsc, RwLock};
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
+use tokio_stream::wrappers::{BroadcastStream, ReceiverStream};
 use tracing::{debug, warn};
 
-type Subscribers<T, F> = Arc<RwLock<BTreeMap<String, (Sender<T>, F)>>>;
+#[cfg(test)]
+#[path = "unit_tests/streamer_tests.rs"]
+mod streamer_tests;
 
-/// The Streamer splits a mpsc channel into multiple mpsc channels using the subscriber's `Filter<T>` object.
-/// Data will be sent to the subscribers in parallel and the subscription will be dropped if it received a send error.
+type Subscribers<T, F> = Arc<RwLock<BTreeMap<String, Subscriber<T, F>>>>;
+
+/// An item delivered to a subscriber of the [Streamer].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StreamItem<T> {
+    Data(T),
+    /// The subscriber fell behind and the given number of items were dropped from its queue.
+    Lagged(u64),
+}
+
+#[derive(Clone)]
+struct Subscriber<T, F> {
+    queue: SubscriberQueue<T>,
+    filter: F,
+}
+
+#[derive(Clone)]
+enum SubscriberQueue<T> {
+    /// A broadcast channel with a single receiver drops the oldest items when full, and reports
+    /// the number of dropped items to the receiver.
+    DropOldest(broadcast::Sender<T>),
+    /// Once the channel is full, items are only counted as dropped. The subscriber's stream
+    /// reports the count and closes after it has drained the channel.
+    Disconnect(Sender<T>, Arc<AtomicU64>),
+    /// The dispatcher waits for the subscriber to make room in its channel.
+    Block(Sender<T>),
+}
+
+/// The Streamer splits a mpsc channel into multiple bounded subscriber queues using the subscriber's `Filter<T>` object.
+/// Subscribers falling behind are handled according to the configured [SubscriberOverflowPolicy].
 pub struct Streamer<T, F: Filter<T>> {
     streamer_queue: Sender<T>,
     subscribers: Subscribers<T, F>,
+    config: SubscriptionConfig,
 }
 
 impl<T, F> Streamer<T, F>
@@ -30,51 +65,127 @@ where
     T: Clone + Debug + Send + Sync + 'static,
     F: Filter<T> + Clone + Send + Sync + 'static + Clone,
 {
-    pub fn spawn(buffer: usize) -> Self {
+    pub fn spawn(buffer: usize, config: SubscriptionConfig) -> Self {
         let (tx, rx) = mpsc::channel::<T>(buffer);
         let streamer = Self {
             streamer_queue: tx,
             subscribers: Default::default(),
+            config,
         };
         let mut rx = rx;
         let subscribers = streamer.subscribers.clone();
         spawn_monitored_task!(async move {
             while let Some(data) = rx.recv().await {
-                Self::send_to_all_subscribers(subscribers.clone(), data).await;
+                Self::send_to_all_subscribers(&subscribers, data).await;
             }
         });
         streamer
     }
 
-    async fn send_to_all_subscribers(subscribers: Subscribers<T, F>, data: T) {
-        for (id, (subscriber, filter)) in subscribers.read().await.clone() {
-            if !(filter.matches(&data)) {
+    async fn send_to_all_subscribers(subscribers: &Subscribers<T, F>, data: T) {
+        // Clone the subscribers out of the lock, a blocked subscriber must not hold up new subscriptions.
+        let current = subscribers.read().await.clone();
+        let mut disconnected = vec![];
+        for (id, subscriber) in current {
+            if !(subscriber.filter.matches(&data)) {
                 continue;
             }
             let data = data.clone();
-            let subscribers = subscribers.clone();
-            spawn_monitored_task!(async move {
-                match subscriber.send(data).await {
-                    Ok(_) => {
-                        debug!("Sending Move event to subscriber [{id}].")
-                    }
-                    Err(e) => {
-                        subscribers.write().await.remove(&id);
-                        warn!("Error sending event, removing subscriber [{id}] from subscriber list. Error: {e}");
+            let connected = match &subscriber.queue {
+                SubscriberQueue::DropOldest(tx) => tx.send(data).is_ok(),
+                SubscriberQueue::Disconnect(tx, dropped) => {
+                    if dropped.load(Ordering::SeqCst) > 0 {
+                        dropped.fetch_add(1, Ordering::SeqCst);
+                        !tx.is_closed()
+                    } else {
+                        match tx.try_send(data) {
+                            Ok(()) => true,
+                            Err(TrySendError::Full(_)) => {
+                                warn!("Subscriber [{id}] lagged behind, disconnecting.");
+                                dropped.fetch_add(1, Ordering::SeqCst);
+                                true
+                            }
+                            Err(TrySendError::Closed(_)) => false,
+                        }
                     }
                 }
-            });
+                SubscriberQueue::Block(tx) => tx.send(data).await.is_ok(),
+            };
+            if connected {
+                debug!("Sending data to subscriber [{id}].")
+            } else {
+                disconnected.push(id);
+            }
+        }
+        if !disconnected.is_empty() {
+            let mut subscribers = subscribers.write().await;
+            for id in disconnected {
+                subscribers.remove(&id);
+                debug!("Removed subscriber [{id}] from subscriber list.");
+            }
         }
     }
 
     /// Subscribe to the data stream filtered by the filter object.
-    pub fn subscribe(&self, filter: F) -> impl Stream<Item = T> {
+    pub fn subscribe(&self, filter: F) -> BoxStream<'static, StreamItem<T>> {
         let handle = Handle::current();
         let _ = handle.enter();
         let mut subscribers = futures::executor::block_on(async { self.subscribers.write().await });
-        let (tx, rx) = mpsc::channel::<T>(EVENT_DISPATCH_BUFFER_SIZE);
-        subscribers.insert(ObjectID::random().to_string(), (tx, filter));
-        ReceiverStream::new(rx)
+        let capacity = self.config.subscriber_queue_capacity.get();
+        let (queue, stream) = match self.config.overflow_policy {
+            SubscriberOverflowPolicy::DropOldest => {
+                let (tx, rx) = broadcast::channel::<T>(capacity);
+                let stream = BroadcastStream::new(rx).map(|item| match item {
+                    Ok(data) => StreamItem::Data(data),
+                    Err(BroadcastStreamRecvError::Lagged(n)) => StreamItem::Lagged(n),
+                });
+                (SubscriberQueue::DropOldest(tx), stream.boxed())
+            }
+            SubscriberOverflowPolicy::Disconnect => {
+                let (tx, rx) = mpsc::channel::<T>(capacity);
+                let dropped = Arc::new(AtomicU64::new(0));
+                (
+                    SubscriberQueue::Disconnect(tx, dropped.clone()),
+                    Self::disconnecting_stream(rx, dropped),
+                )
+            }
+            SubscriberOverflowPolicy::Block => {
+                let (tx, rx) = mpsc::channel::<T>(capacity);
+                (
+                    SubscriberQueue::Block(tx),
+                    ReceiverStream::new(rx).map(StreamItem::Data).boxed(),
+                )
+            }
+        };
+        subscribers.insert(ObjectID::random().to_string(), Subscriber { queue, filter });
+        stream
+    }
+
+    /// Streams the items queued in `rx`. Once the dispatcher started counting `dropped` items,
+    /// the stream reports how many were dropped after the queue is drained, then closes.
+    fn disconnecting_stream(
+        rx: Receiver<T>,
+        dropped: Arc<AtomicU64>,
+    ) -> BoxStream<'static, StreamItem<T>> {
+        futures::stream::unfold(Some(rx), move |rx| {
+            let dropped = dropped.clone();
+            async move {
+                let mut rx = rx?;
+                // Nothing is queued once items are dropped, so the count must be read before
+                // checking whether the queue is drained.
+                let lagged = dropped.load(Ordering::SeqCst);
+                let data = match rx.try_recv() {
+                    Ok(data) => data,
+                    Err(TryRecvError::Empty) if lagged > 0 => {
+                        return Some((StreamItem::Lagged(dropped.load(Ordering::SeqCst)), None));
+                    }
+                    Err(TryRecvError::Empty) => rx.recv().await?,
+                    Err(TryRecvError::Disconnected) => return None,
+                };
+                Some((StreamItem::Data(data), Some(rx)))
+            }
+        })
+        .boxed()
     }
 
     pub async fn send(&self, data: T) -> Result<(), SuiError> {
@@ -388,7 +388,7 @@ impl TransactionManager {
     }
 
     // Returns the number of certificates pending execution or being executed by the execution driver right now.
-    pub(crate) fn execution_queue_len(&self) -> usize {
+    pub fn execution_queue_len(&self) -> usize {
         let inner = self.inner.read();
         inner.pending_certificates.len() + inner.executing_certificates.len()
     }
@@ -1978,7 +1978,15 @@ async fn test_transaction_expiration() {
         .handle_transaction(&epoch_store, expired_transaction)
         .await;
 
-    assert!(matches!(result.unwrap_err(), SuiError::TransactionExpired));
+    assert_eq!(
+        result.unwrap_err(),
+        SuiError::UserInputError {
+            error: UserInputError::TransactionExpired {
+                expiration_epoch: 0,
+                current_epoch: 1,
+            }
+        }
+    );
 
     // Non expired transaction signed without issue
     *data.expiration_mut() = TransactionExpiration::Epoch(10);
@@ -2900,6 +2908,7 @@ async fn test_authority_persist() {
             AuthorityStorePruningConfig::default(),
             &[], // no genesis objects
             &DBCheckpointConfig::default(),
+            SubscriptionConfig::default(),
         )
         .await
     }
@@ -0,0 +1,100 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::num::NonZeroUsize;
+use std::time::Duration;
+
+use futures::StreamExt;
+use sui_config::node::{SubscriberOverflowPolicy, SubscriptionConfig};
+use sui_types::filter::Filter;
+
+use crate::streamer::{StreamItem, Streamer};
+
+#[derive(Clone)]
+struct AcceptAll;
+
+impl Filter<u64> for AcceptAll {
+    fn matches(&self, _item: &u64) -> bool {
+        true
+    }
+}
+
+fn spawn_streamer(
+    capacity: usize,
+    overflow_policy: SubscriberOverflowPolicy,
+) -> Streamer<u64, AcceptAll> {
+    Streamer::spawn(
+        100,
+        SubscriptionConfig {
+            subscriber_queue_capacity: NonZeroUsize::new(capacity).unwrap(),
+            overflow_policy,
+        },
+    )
+}
+
+async fn send_all(streamer: &Streamer<u64, AcceptAll>, items: u64) {
+    for i in 0..items {
+        streamer.send(i).await.unwrap();
+    }
+    // Give the dispatcher some time to fan out the items.
+    tokio::time::sleep(Duration::from_millis(100)).await;
+}
+
+#[tokio::test]
+async fn test_drop_oldest_notifies_lag() {
+    let streamer = spawn_streamer(2, SubscriberOverflowPolicy::DropOldest);
+    let mut stream = streamer.subscribe(AcceptAll);
+    send_all(&streamer, 5).await;
+
+    assert_eq!(stream.next().await, Some(StreamItem::Lagged(3)));
+    assert_eq!(stream.next().await, Some(StreamItem::Data(3)));
+    assert_eq!(stream.next().await, Some(StreamItem::Data(4)));
+}
+
+#[tokio::test]
+async fn test_disconnect_notifies_lag_and_closes() {
+    let streamer = spawn_streamer(2, SubscriberOverflowPolicy::Disconnect);
+    let mut stream = streamer.subscribe(AcceptAll);
+    send_all(&streamer, 5).await;
+
+    assert_eq!(stream.next().await, Some(StreamItem::Data(0)));
+    assert_eq!(stream.next().await, Some(StreamItem::Data(1)));
+    assert_eq!(stream.next().await, Some(StreamItem::Lagged(3)));
+    assert_eq!(stream.next().await, None);
+}
+
+#[tokio::test]
+async fn test_block_delivers_everything() {
+    let streamer = spawn_streamer(1, SubscriberOverflowPolicy::Block);
+    let mut stream = streamer.subscribe(AcceptAll);
+    send_all(&streamer, 5).await;
+
+    for i in 0..5 {
+        assert_eq!(stream.next().await, Some(StreamItem::Data(i)));
+    }
+}
+
+#[tokio::test]
+async fn test_block_holds_up_dispatch() {
+    let streamer = spawn_streamer(1, SubscriberOverflowPolicy::Block);
+    let mut stream = streamer.subscribe(AcceptAll);
+    // One item in the subscriber's queue, one held by the dispatcher and the rest of the dispatch
+    // buffer. Sending more must wait for the subscriber to read.
+    send_all(&streamer, 102).await;
+    assert!(
+        tokio::time::timeout(Duration::from_millis(100), streamer.send(102))
+            .await
+            .is_err()
+    );
+
+    for i in 0..102 {
+        assert_eq!(stream.next().await, Some(StreamItem::Data(i)));
+    }
+    assert!(
+        tokio::time::timeout(Duration::from_secs(10), streamer.send(102))
+            .await
+            .unwrap()
+            .is_ok()
+    );
+    assert_eq!(stream.next().await, Some(StreamItem::Data(102)));
+}
@@ -31,6 +31,9 @@ pub enum FaucetError {
     #[error("Coin Transfer Failed `{0}`")]
     Transfer(String),
 
+    #[error("Too many requests for {0}, retry in {1} seconds")]
+    TooManyRequests(String, u64),
+
     #[error("Internal error: {0}")]
     Internal(String),
 }
@@ -667,8 +667,8 @@ mod tests {
         // Now we transfer one gas out
         let res = SuiClientCommands::PayAllSui {
             input_coins: vec![*bad_gas.id()],
-            recipient: SuiAddress::random_for_testing_only(),
-            gas_budget: 50000,
+            recipient: SuiAddress::random_for_testing_only().into(),
+            gas_budget: Some(50000),
         }
         .execute(faucet.wallet_mut())
         .await
@@ -723,7 +723,7 @@ mod tests {
         let res = SuiClientCommands::SplitCoin {
             coin_id: *gases[0].id(),
             amounts: Some(vec![tiny_value + PAY_SUI_GAS]),
-            gas_budget: 50000,
+            gas_budget: Some(50000),
             gas: None,
             count: None,
         }
@@ -804,7 +804,7 @@ mod tests {
     async fn get_current_gases(address: SuiAddress, context: &mut WalletContext) -> Vec<GasCoin> {
         // Get the latest list of gas
         let results = SuiClientCommands::Gas {
-            address: Some(address),
+            address: Some(address.into()),
         }
         .execute(context)
         .await
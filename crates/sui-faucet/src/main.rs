@@ -3,7 +3,8 @@
 
 use axum::{
     error_handling::HandleErrorLayer,
-    http::StatusCode,
+    extract::ConnectInfo,
+    http::{header, HeaderMap, StatusCode},
     response::IntoResponse,
     routing::{get, post},
     BoxError, Extension, Json, Router,
@@ -20,7 +21,10 @@ use std::{
 use std::{env, path::PathBuf};
 use sui::client_commands::WalletContext;
 use sui_config::{sui_config_dir, SUI_CLIENT_CONFIG};
-use sui_faucet::{Faucet, FaucetRequest, FaucetResponse, RequestMetricsLayer, SimpleFaucet};
+use sui_faucet::{
+    client_ip, Faucet, FaucetError, FaucetRequest, FaucetResponse, RateLimiter,
+    RequestMetricsLayer, SimpleFaucet, TokenBucketConfig,
+};
 use tower::{limit::RateLimitLayer, ServiceBuilder};
 use tower_http::cors::{Any, CorsLayer};
 use tracing::{info, warn};
@@ -58,15 +62,41 @@ struct FaucetConfig {
 
     #[clap(long)]
     write_ahead_log: PathBuf,
+
+    /// Where rate limits are persisted, defaults to `rate_limits` next to the write ahead log.
+    #[clap(long)]
+    rate_limit_db: Option<PathBuf>,
+
+    /// Requests a recipient address can make in a burst, 0 for no limit.
+    #[clap(long, default_value_t = 0)]
+    recipient_rate_limit_capacity: u64,
+
+    /// Seconds for a recipient address to regain one request.
+    #[clap(long, default_value_t = 3600)]
+    recipient_rate_limit_refill_secs: u64,
+
+    /// Requests a client IP can make in a burst, 0 for no limit.
+    #[clap(long, default_value_t = 0)]
+    ip_rate_limit_capacity: u64,
+
+    /// Seconds for a client IP to regain one request.
+    #[clap(long, default_value_t = 3600)]
+    ip_rate_limit_refill_secs: u64,
+
+    /// Proxy allowed to set the client IP through `X-Forwarded-For`, can be repeated.
+    #[clap(long = "trusted-proxy")]
+    trusted_proxies: Vec<IpAddr>,
 }
 
 struct AppState<F = SimpleFaucet> {
     faucet: F,
+    rate_limiter: RateLimiter,
     config: FaucetConfig,
     // TODO: add counter
 }
 
 const PROM_PORT_ADDR: &str = "0.0.0.0:9184";
+const RATE_LIMIT_PRUNE_INTERVAL: Duration = Duration::from_secs(3600);
 
 #[tokio::main]
 async fn main() -> Result<(), anyhow::Error> {
@@ -89,6 +119,11 @@ async fn main() -> Result<(), anyhow::Error> {
         max_request_per_second,
         wallet_client_timeout_secs,
         ref write_ahead_log,
+        ref rate_limit_db,
+        recipient_rate_limit_capacity,
+        recipient_rate_limit_refill_secs,
+        ip_rate_limit_capacity,
+        ip_rate_limit_refill_secs,
         ..
     } = config;
 
@@ -99,13 +134,45 @@ async fn main() -> Result<(), anyhow::Error> {
     let registry_service = sui_node::metrics::start_prometheus_server(prom_binding);
     let prometheus_registry = registry_service.default_registry();
 
+    let rate_limit_db = rate_limit_db
+        .clone()
+        .unwrap_or_else(|| write_ahead_log.with_file_name("rate_limits"));
+    let rate_limiter = RateLimiter::open(
+        &rate_limit_db,
+        TokenBucketConfig {
+            capacity: recipient_rate_limit_capacity,
+            refill_interval: Duration::from_secs(recipient_rate_limit_refill_secs),
+        },
+        TokenBucketConfig {
+            capacity: ip_rate_limit_capacity,
+            refill_interval: Duration::from_secs(ip_rate_limit_refill_secs),
+        },
+    );
+
     let app_state = Arc::new(AppState {
         faucet: SimpleFaucet::new(context, &prometheus_registry, write_ahead_log)
             .await
             .unwrap(),
+        rate_limiter,
         config,
     });
 
+    let prune_state = app_state.clone();
+    spawn_monitored_task!(async move {
+        let mut interval = tokio::time::interval(RATE_LIMIT_PRUNE_INTERVAL);
+        loop {
+            interval.tick().await;
+            let state = prune_state.clone();
+            let pruned = tokio::task::spawn_blocking(move || state.rate_limiter.prune())
+                .await
+                .unwrap();
+            match pruned {
+                Ok(pruned) => info!("Pruned {pruned} refilled rate limit buckets"),
+                Err(e) => warn!("Failed to prune rate limit buckets: {:?}", e),
+            }
+        }
+    });
+
     // TODO: restrict access if needed
     let cors = CorsLayer::new()
         .allow_methods(vec![Method::GET, Method::POST])
@@ -134,7 +201,7 @@ async fn main() -> Result<(), anyhow::Error> {
     let addr = SocketAddr::new(IpAddr::V4(host_ip), port);
     info!("listening on {}", addr);
     axum::Server::bind(&addr)
-        .serve(app.into_make_service())
+        .serve(app.into_make_service_with_connect_info::<SocketAddr>())
         .await?;
     Ok(())
 }
@@ -147,6 +214,8 @@ async fn health() -> &'static str {
 /// handler for all the request_gas requests
 async fn request_gas(
     Extension(state): Extension<Arc<AppState>>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
     Json(payload): Json<FaucetRequest>,
 ) -> impl IntoResponse {
     // ID for traceability
@@ -154,17 +223,69 @@ async fn request_gas(
     info!(uuid = ?id, "Got new gas request.");
     let result = match payload {
         FaucetRequest::FixedAmountRequest(requests) => {
+            let forwarded_for = headers
+                .get_all("x-forwarded-for")
+                .iter()
+                .filter_map(|value| value.to_str().ok())
+                .collect::<Vec<_>>()
+                .join(",");
+            let ip = client_ip(
+                peer.ip(),
+                Some(forwarded_for.as_str()),
+                &state.config.trusted_proxies,
+            );
+            // The rate limiter blocks on its lock and database, keep it off the async workers.
+            let limiter_state = state.clone();
+            let recipient = requests.recipient;
+            let acquired = tokio::task::spawn_blocking(move || {
+                limiter_state.rate_limiter.acquire(recipient, ip)
+            })
+            .await
+            .unwrap();
+            match acquired {
+                Ok(()) => {}
+                Err(e @ FaucetError::TooManyRequests(_, retry_after_secs)) => {
+                    warn!(uuid = ?id, "Gas request is rate limited: {:?}", e);
+                    return (
+                        StatusCode::TOO_MANY_REQUESTS,
+                        [(header::RETRY_AFTER, retry_after_secs.to_string())],
+                        Json(FaucetResponse::from(e)),
+                    )
+                        .into_response();
+                }
+                Err(e) => {
+                    warn!(uuid = ?id, "Failed to check rate limits: {:?}", e);
+                    return (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        Json(FaucetResponse::from(e)),
+                    )
+                        .into_response();
+                }
+            }
             // We spawn a tokio task for this such that connection drop will not interrupt
             // it and impact the reclycing of coins
             spawn_monitored_task!(async move {
-                state
+                let result = state
                     .faucet
                     .send(
                         id,
                         requests.recipient,
                         &vec![state.config.amount; state.config.num_coins],
                     )
+                    .await;
+                // Only served requests count against the rate limits.
+                if result.is_err() {
+                    let limiter_state = state.clone();
+                    let released = tokio::task::spawn_blocking(move || {
+                        limiter_state.rate_limiter.release(recipient, ip)
+                    })
                     .await
+                    .unwrap();
+                    if let Err(e) = released {
+                        warn!(uuid = ?id, "Failed to release rate limit tokens: {:?}", e);
+                    }
+                }
+                result
             })
             .await
             .unwrap()
@@ -173,7 +294,7 @@ async fn request_gas(
     match result {
         Ok(v) => {
             info!(uuid =?id, "Request is successfully served");
-            (StatusCode::CREATED, Json(FaucetResponse::from(v)))
+            (StatusCode::CREATED, Json(FaucetResponse::from(v))).into_response()
         }
         Err(v) => {
             warn!(uuid =?id, "Failed to request gas: {:?}", v);
@@ -181,6 +302,7 @@ async fn request_gas(
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(FaucetResponse::from(v)),
             )
+                .into_response()
         }
     }
 }
@@ -0,0 +1,407 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fmt;
+use std::net::IpAddr;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use sui_types::base_types::SuiAddress;
+use typed_store::rocks::DBMap;
+use typed_store::traits::{TableSummary, TypedStoreDebug};
+use typed_store::Map;
+use typed_store_derive::DBMapUtils;
+
+use crate::FaucetError;
+
+/// Token bucket limit applied to every key of one kind: a key can make `capacity` requests in a
+/// burst, and regains one request every `refill_interval`. A `capacity` of 0 disables the limit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TokenBucketConfig {
+    pub capacity: u64,
+    pub refill_interval: Duration,
+}
+
+impl TokenBucketConfig {
+    pub fn unlimited() -> Self {
+        Self {
+            capacity: 0,
+            refill_interval: Duration::ZERO,
+        }
+    }
+
+    fn refill_interval_ms(&self) -> u64 {
+        (self.refill_interval.as_millis() as u64).max(1)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum RateLimitKey {
+    Recipient(SuiAddress),
+    Ip(IpAddr),
+}
+
+impl fmt::Display for RateLimitKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RateLimitKey::Recipient(address) => write!(f, "recipient {address}"),
+            RateLimitKey::Ip(ip) => write!(f, "IP {ip}"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+struct Bucket {
+    tokens: u64,
+    /// Time the last whole token was added, in milliseconds since the unix epoch.
+    last_refill_ms: u64,
+}
+
+impl Bucket {
+    /// The bucket as of `now_ms`. Keys without a bucket have a full one.
+    fn refill(bucket: Option<Bucket>, config: &TokenBucketConfig, now_ms: u64) -> Bucket {
+        let full = Bucket {
+            tokens: config.capacity,
+            last_refill_ms: now_ms,
+        };
+        let Some(bucket) = bucket else {
+            return full;
+        };
+        let interval = config.refill_interval_ms();
+        let refilled = now_ms.saturating_sub(bucket.last_refill_ms) / interval;
+        let tokens = bucket.tokens.saturating_add(refilled);
+        if tokens >= config.capacity {
+            full
+        } else {
+            Bucket {
+                tokens,
+                last_refill_ms: bucket.last_refill_ms + refilled * interval,
+            }
+        }
+    }
+
+    /// Time until the bucket, empty as of `now_ms`, gets a token back.
+    fn retry_after(&self, config: &TokenBucketConfig, now_ms: u64) -> Duration {
+        let next_refill_ms = self.last_refill_ms + config.refill_interval_ms();
+        Duration::from_millis(next_refill_ms.saturating_sub(now_ms))
+    }
+}
+
+/// Token buckets of the recipients and client IPs the faucet served, persisted so that limits
+/// survive restarts.
+#[derive(DBMapUtils)]
+pub struct RateLimitTables {
+    buckets: DBMap<RateLimitKey, Bucket>,
+}
+
+/// Limits how often the faucet serves each recipient address and each client IP.
+pub struct RateLimiter {
+    tables: RateLimitTables,
+    recipient_limit: TokenBucketConfig,
+    ip_limit: TokenBucketConfig,
+    // Buckets are read, updated and written back, requests must not interleave.
+    lock: Mutex<()>,
+}
+
+impl RateLimiter {
+    pub fn open(
+        path: &Path,
+        recipient_limit: TokenBucketConfig,
+        ip_limit: TokenBucketConfig,
+    ) -> Self {
+        let tables = RateLimitTables::open_tables_read_write(
+            path.to_path_buf(),
+            typed_store::rocks::MetricConf::default(),
+            None,
+            None,
+        );
+        Self {
+            tables,
+            recipient_limit,
+            ip_limit,
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Take a request token from the buckets of both `recipient` and `ip`. If either bucket is
+    /// empty no token is taken, and the error says how long to wait before retrying.
+    pub fn acquire(&self, recipient: SuiAddress, ip: IpAddr) -> Result<(), FaucetError> {
+        self.acquire_at(recipient, ip, now_ms())
+    }
+
+    fn acquire_at(
+        &self,
+        recipient: SuiAddress,
+        ip: IpAddr,
+        now_ms: u64,
+    ) -> Result<(), FaucetError> {
+        let _guard = self.lock.lock().unwrap();
+
+        let limits = [
+            (RateLimitKey::Recipient(recipient), &self.recipient_limit),
+            (RateLimitKey::Ip(ip), &self.ip_limit),
+        ];
+        let mut buckets = vec![];
+        let mut limited = vec![];
+        let mut retry_after = Duration::ZERO;
+        for (key, config) in limits {
+            if config.capacity == 0 {
+                continue;
+            }
+            let bucket = self
+                .tables
+                .buckets
+                .get(&key)
+                .map_err(FaucetError::internal)?;
+            let bucket = Bucket::refill(bucket, config, now_ms);
+            if bucket.tokens == 0 {
+                limited.push(key.to_string());
+                retry_after = retry_after.max(bucket.retry_after(config, now_ms));
+            }
+            buckets.push((key, bucket));
+        }
+
+        if !limited.is_empty() {
+            return Err(FaucetError::TooManyRequests(
+                limited.join(" and "),
+                // Round up, retrying after a truncated delay would be limited again.
+                (retry_after.as_millis() as u64 + 999) / 1000,
+            ));
+        }
+
+        let taken = buckets.into_iter().map(|(key, bucket)| {
+            (
+                key,
+                Bucket {
+                    tokens: bucket.tokens - 1,
+                    ..bucket
+                },
+            )
+        });
+        self.tables
+            .buckets
+            .batch()
+            .insert_batch(&self.tables.buckets, taken)
+            .and_then(|batch| batch.write())
+            .map_err(FaucetError::internal)
+    }
+
+    /// Give back the token `acquire` took from the buckets of `recipient` and `ip`, for requests
+    /// that failed to be served.
+    pub fn release(&self, recipient: SuiAddress, ip: IpAddr) -> Result<(), FaucetError> {
+        self.release_at(recipient, ip, now_ms())
+    }
+
+    fn release_at(
+        &self,
+        recipient: SuiAddress,
+        ip: IpAddr,
+        now_ms: u64,
+    ) -> Result<(), FaucetError> {
+        let _guard = self.lock.lock().unwrap();
+
+        let limits = [
+            (RateLimitKey::Recipient(recipient), &self.recipient_limit),
+            (RateLimitKey::Ip(ip), &self.ip_limit),
+        ];
+        let mut released = vec![];
+        for (key, config) in limits {
+            if config.capacity == 0 {
+                continue;
+            }
+            let bucket = self
+                .tables
+                .buckets
+                .get(&key)
+                .map_err(FaucetError::internal)?;
+            let bucket = Bucket::refill(bucket, config, now_ms);
+            released.push((
+                key,
+                Bucket {
+                    tokens: (bucket.tokens + 1).min(config.capacity),
+                    ..bucket
+                },
+            ));
+        }
+        self.tables
+            .buckets
+            .batch()
+            .insert_batch(&self.tables.buckets, released)
+            .and_then(|batch| batch.write())
+            .map_err(FaucetError::internal)
+    }
+
+    /// Remove the buckets that have refilled completely, which behave the same as no bucket.
+    /// Returns the number of buckets removed.
+    pub fn prune(&self) -> Result<usize, FaucetError> {
+        let _guard = self.lock.lock().unwrap();
+        let now_ms = now_ms();
+        let full: Vec<_> = self
+            .tables
+            .buckets
+            .iter()
+            .filter(|(key, bucket)| {
+                let config = match key {
+                    RateLimitKey::Recipient(_) => &self.recipient_limit,
+                    RateLimitKey::Ip(_) => &self.ip_limit,
+                };
+                Bucket::refill(Some(*bucket), config, now_ms).tokens >= config.capacity
+            })
+            .map(|(key, _)| key)
+            .collect();
+        self.tables
+            .buckets
+            .multi_remove(&full)
+            .map_err(FaucetError::internal)?;
+        Ok(full.len())
+    }
+}
+
+/// The IP of the client that sent a request received from `peer`. `X-Forwarded-For` is only
+/// honored if `peer` is a trusted proxy, in which case the client is the right-most address in
+/// `forwarded_for` that is not itself a trusted proxy.
+pub fn client_ip(peer: IpAddr, forwarded_for: Option<&str>, trusted_proxies: &[IpAddr]) -> IpAddr {
+    if !trusted_proxies.contains(&peer) {
+        return peer;
+    }
+    let mut client = peer;
+    for hop in forwarded_for.into_iter().flat_map(|f| f.rsplit(',')) {
+        // Entries left of a malformed one cannot be trusted.
+        let Ok(ip) = hop.trim().parse::<IpAddr>() else {
+            break;
+        };
+        client = ip;
+        if !trusted_proxies.contains(&ip) {
+            break;
+        }
+    }
+    client
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system time should be after the unix epoch")
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: Duration = Duration::from_secs(3600);
+
+    fn limit(capacity: u64) -> TokenBucketConfig {
+        TokenBucketConfig {
+            capacity,
+            refill_interval: HOUR,
+        }
+    }
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn recipient_bucket_empties_and_refills() {
+        let tmp = tempfile::tempdir().unwrap();
+        let limiter = RateLimiter::open(&tmp.path().join("limits"), limit(2), limit(0));
+        let recipient = SuiAddress::random_for_testing_only();
+        let t0 = 1_000_000;
+
+        limiter.acquire_at(recipient, ip("1.1.1.1"), t0).unwrap();
+        limiter.acquire_at(recipient, ip("2.2.2.2"), t0).unwrap();
+
+        // Third request within the hour is limited, whatever the IP.
+        let Err(FaucetError::TooManyRequests(limited, retry_after_secs)) =
+            limiter.acquire_at(recipient, ip("3.3.3.3"), t0 + 1000)
+        else {
+            panic!("Expected the recipient to be rate limited");
+        };
+        assert_eq!(limited, format!("recipient {recipient}"));
+        assert_eq!(retry_after_secs, HOUR.as_secs() - 1);
+
+        // Other recipients are not affected.
+        let other = SuiAddress::random_for_testing_only();
+        limiter.acquire_at(other, ip("3.3.3.3"), t0 + 1000).unwrap();
+
+        // One token is back after an hour, but only one.
+        let t1 = t0 + HOUR.as_millis() as u64;
+        limiter.acquire_at(recipient, ip("3.3.3.3"), t1).unwrap();
+        assert!(limiter.acquire_at(recipient, ip("3.3.3.3"), t1).is_err());
+    }
+
+    #[test]
+    fn limited_request_takes_no_token() {
+        let tmp = tempfile::tempdir().unwrap();
+        let limiter = RateLimiter::open(&tmp.path().join("limits"), limit(1), limit(1));
+        let recipient = SuiAddress::random_for_testing_only();
+        let other = SuiAddress::random_for_testing_only();
+        let t0 = 1_000_000;
+
+        limiter.acquire_at(recipient, ip("1.1.1.1"), t0).unwrap();
+        // The IP is limited, so the other recipient's token is not taken.
+        assert!(limiter.acquire_at(other, ip("1.1.1.1"), t0).is_err());
+        limiter.acquire_at(other, ip("2.2.2.2"), t0).unwrap();
+    }
+
+    #[test]
+    fn released_token_is_given_back() {
+        let tmp = tempfile::tempdir().unwrap();
+        let limiter = RateLimiter::open(&tmp.path().join("limits"), limit(1), limit(1));
+        let recipient = SuiAddress::random_for_testing_only();
+        let t0 = 1_000_000;
+
+        limiter.acquire_at(recipient, ip("1.1.1.1"), t0).unwrap();
+        assert!(limiter.acquire_at(recipient, ip("1.1.1.1"), t0).is_err());
+
+        // A failed request gets its token back, but never more than the capacity.
+        limiter.release_at(recipient, ip("1.1.1.1"), t0).unwrap();
+        limiter.release_at(recipient, ip("1.1.1.1"), t0).unwrap();
+        limiter.acquire_at(recipient, ip("1.1.1.1"), t0).unwrap();
+        assert!(limiter.acquire_at(recipient, ip("1.1.1.1"), t0).is_err());
+    }
+
+    #[test]
+    fn buckets_persist_across_restarts() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("limits");
+        let recipient = SuiAddress::random_for_testing_only();
+        let t0 = 1_000_000;
+
+        {
+            let limiter = RateLimiter::open(&path, limit(1), limit(0));
+            limiter.acquire_at(recipient, ip("1.1.1.1"), t0).unwrap();
+        }
+
+        let limiter = RateLimiter::open(&path, limit(1), limit(0));
+        assert!(matches!(
+            limiter.acquire_at(recipient, ip("1.1.1.1"), t0),
+            Err(FaucetError::TooManyRequests(..))
+        ));
+    }
+
+    #[test]
+    fn forwarded_for_only_from_trusted_proxies() {
+        let proxy = ip("10.0.0.1");
+        let trusted = [proxy, ip("10.0.0.2")];
+
+        // Untrusted peers cannot spoof their IP.
+        assert_eq!(
+            client_ip(ip("1.1.1.1"), Some("2.2.2.2"), &trusted),
+            ip("1.1.1.1")
+        );
+        // The right-most untrusted hop is the client, entries left of it may be spoofed.
+        assert_eq!(
+            client_ip(proxy, Some("2.2.2.2, 3.3.3.3, 10.0.0.2"), &trusted),
+            ip("3.3.3.3")
+        );
+        assert_eq!(client_ip(proxy, None, &trusted), proxy);
+        assert_eq!(
+            client_ip(proxy, Some("not-an-ip, 10.0.0.2"), &trusted),
+            ip("10.0.0.2")
+        );
+    }
+}
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS balance_changes_start;
+DROP TABLE IF EXISTS balance_changes;
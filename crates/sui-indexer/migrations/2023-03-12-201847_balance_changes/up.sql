@@ -0,0 +1,19 @@
+CREATE TABLE balance_changes (
+    id BIGSERIAL PRIMARY KEY,
+    transaction_digest VARCHAR(255) NOT NULL,
+    checkpoint_sequence_number BIGINT NOT NULL,
+    epoch BIGINT NOT NULL,
+    owner VARCHAR(255) NOT NULL,
+    coin_type TEXT NOT NULL,
+    -- signed amount in the smallest coin unit, in decimal as it may not fit in a BIGINT
+    amount TEXT NOT NULL
+);
+
+CREATE INDEX balance_changes_transaction_digest ON balance_changes (transaction_digest);
+CREATE INDEX balance_changes_owner_coin_type ON balance_changes (owner, coin_type, checkpoint_sequence_number);
+CREATE INDEX balance_changes_coin_type ON balance_changes (coin_type);
+
+-- at most one row, the checkpoint balance changes are indexed from up to the latest checkpoint
+CREATE TABLE balance_changes_start (
+    checkpoint_sequence_number BIGINT PRIMARY KEY
+);
@@ -1,17 +1,106 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::errors::IndexerError;
+use crate::models::balance_changes::BalanceChange;
+use crate::store::IndexerStore;
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::http_client::HttpClient;
 use jsonrpsee::RpcModule;
-use sui_json_rpc::api::CoinReadApiClient;
+use jsonrpsee_proc_macros::rpc;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use sui_json_rpc::api::CoinReadApiServer;
+use sui_json_rpc::api::{cap_page_limit, CoinReadApiClient};
 use sui_json_rpc::SuiRpcModule;
-use sui_json_rpc_types::{Balance, CoinPage, SuiCoinMetadata};
+use sui_json_rpc_types::{Balance, CoinPage, Page, SuiCoinMetadata};
 use sui_open_rpc::Module;
+use sui_open_rpc_macros::open_rpc;
 use sui_types::balance::Supply;
-use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
+use sui_types::committee::EpochId;
+use sui_types::gas_coin::GAS;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::parse_sui_struct_tag;
+
+pub type BalanceChangePage = Page<IndexedBalanceChange, u64>;
+
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckpointBalance {
+    pub coin_type: String,
+    pub checkpoint: CheckpointSequenceNumber,
+    /// Negative only if the indexer has not indexed the history of the owner from genesis.
+    pub total_balance: i128,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexedBalanceChange {
+    pub transaction_digest: TransactionDigest,
+    pub checkpoint: CheckpointSequenceNumber,
+    pub epoch: EpochId,
+    pub owner: SuiAddress,
+    pub coin_type: String,
+    /// Negative amounts are spent, positive amounts are received.
+    pub amount: i128,
+}
+
+impl TryFrom<BalanceChange> for IndexedBalanceChange {
+    type Error = IndexerError;
+
+    fn try_from(change: BalanceChange) -> Result<Self, Self::Error> {
+        let amount = change.amount.parse().map_err(|e| {
+            IndexerError::SerdeError(format!(
+                "Failed to parse balance change amount {} with error {:?}",
+                change.amount, e
+            ))
+        })?;
+        Ok(Self {
+            transaction_digest: change.transaction_digest.parse()?,
+            checkpoint: change.checkpoint_sequence_number as u64,
+            epoch: change.epoch as u64,
+            owner: change.owner.parse()?,
+            coin_type: change.coin_type,
+            amount,
+        })
+    }
+}
+
+/// Balance history queries, answered from the balance changes indexed from the fullnode.
+#[open_rpc(namespace = "sui", tag = "Coin Query API")]
+#[rpc(server, client, namespace = "sui")]
+pub trait BalanceHistoryApi {
+    /// Return the total coin balance for one coin type owned by the address owner, as of the end
+    /// of a checkpoint.
+    #[method(name = "getBalanceAtCheckpoint")]
+    async fn get_balance_at_checkpoint(
+        &self,
+        /// the owner's Sui address
+        owner: SuiAddress,
+        /// optional type name for the coin (e.g., 0x168da5bf1f48dafc111b0a488fa454aca95e0b5e::usdc::USDC), default to 0x2::sui::SUI if not specified.
+        coin_type: Option<String>,
+        /// the checkpoint sequence number
+        checkpoint: CheckpointSequenceNumber,
+    ) -> RpcResult<CheckpointBalance>;
+
+    /// Return the balance changes of one coin type, optionally only those of one owner.
+    #[method(name = "getBalanceChanges")]
+    async fn get_balance_changes(
+        &self,
+        /// optional type name for the coin (e.g., 0x168da5bf1f48dafc111b0a488fa454aca95e0b5e::usdc::USDC), default to 0x2::sui::SUI if not specified.
+        coin_type: Option<String>,
+        /// optional owner's Sui address, return changes of all owners if not specified
+        owner: Option<SuiAddress>,
+        /// optional paging cursor
+        cursor: Option<u64>,
+        /// maximum number of items per page
+        limit: Option<usize>,
+        /// query result ordering, default to false (ascending order), oldest record first.
+        descending_order: Option<bool>,
+    ) -> RpcResult<BalanceChangePage>;
+}
 
 pub(crate) struct CoinReadApi {
     fullnode: HttpClient,
@@ -78,3 +167,136 @@ impl SuiRpcModule for CoinReadApi {
         sui_json_rpc::api::CoinReadApiOpenRpc::module_doc()
     }
 }
+
+pub(crate) struct BalanceHistoryReadApi<S> {
+    state: S,
+}
+
+impl<S: IndexerStore> BalanceHistoryReadApi<S> {
+    pub fn new(state: S) -> Self {
+        Self { state }
+    }
+
+    fn get_balance_changes_internal(
+        &self,
+        coin_type: Option<String>,
+        owner: Option<SuiAddress>,
+        cursor: Option<u64>,
+        limit: Option<usize>,
+        descending_order: Option<bool>,
+    ) -> Result<BalanceChangePage, IndexerError> {
+        let limit = cap_page_limit(limit);
+        // the page is of size (limit + 1), the extra change tells whether there is a next page
+        let mut changes = self.state.get_balance_change_page(
+            owner.map(|owner| owner.to_string()),
+            normalize_coin_type(coin_type)?,
+            cursor.map(|cursor| cursor as i64),
+            limit + 1,
+            descending_order.unwrap_or_default(),
+        )?;
+        let has_next_page = changes.len() > limit;
+        changes.truncate(limit);
+        let next_cursor = changes
+            .last()
+            .and_then(|change| change.id)
+            .map(|id| id as u64)
+            .map_or(cursor, Some);
+        Ok(Page {
+            data: changes
+                .into_iter()
+                .map(IndexedBalanceChange::try_from)
+                .collect::<Result<_, _>>()?,
+            next_cursor,
+            has_next_page,
+        })
+    }
+
+    /// Balances are summed from the indexed balance changes, so they are only known at the
+    /// checkpoints that have balance changes indexed all the way from genesis.
+    fn check_balance_indexed(
+        &self,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> Result<(), IndexerError> {
+        let start = self.state.get_balance_changes_start_checkpoint()?;
+        let latest = self.state.get_latest_checkpoint_sequence_number()?;
+        match start {
+            Some(0) if checkpoint as i64 <= latest => Ok(()),
+            Some(0) => Err(IndexerError::InvalidArgumentError(format!(
+                "Checkpoint {} is not indexed yet, the latest indexed checkpoint is {}",
+                checkpoint, latest
+            ))),
+            Some(start) => Err(IndexerError::InvalidArgumentError(format!(
+                "Balance changes are only indexed from checkpoint {}, balances are not available",
+                start
+            ))),
+            None => Err(IndexerError::InvalidArgumentError(
+                "Balance changes are not indexed, balances are not available".to_string(),
+            )),
+        }
+    }
+}
+
+/// Coin types are indexed in their canonical form, parse the requested type to match it.
+fn normalize_coin_type(coin_type: Option<String>) -> Result<String, IndexerError> {
+    Ok(match coin_type {
+        Some(coin_type) => parse_sui_struct_tag(&coin_type)
+            .map_err(|e| {
+                IndexerError::InvalidArgumentError(format!(
+                    "Invalid coin type {}: {:?}",
+                    coin_type, e
+                ))
+            })?
+            .to_string(),
+        None => GAS::type_().to_string(),
+    })
+}
+
+#[async_trait]
+impl<S> BalanceHistoryApiServer for BalanceHistoryReadApi<S>
+where
+    S: IndexerStore + Sync + Send + 'static,
+{
+    async fn get_balance_at_checkpoint(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> RpcResult<CheckpointBalance> {
+        self.check_balance_indexed(checkpoint)?;
+        let coin_type = normalize_coin_type(coin_type)?;
+        let total_balance = self.state.get_balance_at_checkpoint(
+            owner.to_string(),
+            coin_type.clone(),
+            checkpoint as i64,
+        )?;
+        Ok(CheckpointBalance {
+            coin_type,
+            checkpoint,
+            total_balance,
+        })
+    }
+
+    async fn get_balance_changes(
+        &self,
+        coin_type: Option<String>,
+        owner: Option<SuiAddress>,
+        cursor: Option<u64>,
+        limit: Option<usize>,
+        descending_order: Option<bool>,
+    ) -> RpcResult<BalanceChangePage> {
+        Ok(self.get_balance_changes_internal(coin_type, owner, cursor, limit, descending_order)?)
+    }
+}
+
+impl<S> SuiRpcModule for BalanceHistoryReadApi<S>
+where
+    S: IndexerStore + Sync + Send + 'static,
+{
+    fn rpc(self) -> RpcModule<Self> {
+        self.into_rpc()
+    }
+
+    fn rpc_doc_module() -> Module {
+        BalanceHistoryApiOpenRpc::module_doc()
+    }
+}
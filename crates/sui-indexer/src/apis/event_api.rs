@@ -72,6 +72,7 @@ where
         &self,
         mut _sink: SubscriptionSink,
         _filter: SuiEventFilter,
+        _cursor: Option<EventID>,
     ) -> SubscriptionResult {
         // subscription not supported by subscription yet
         Err(SubscriptionEmptyError)
@@ -8,7 +8,10 @@ mod read_api;
 mod transaction_builder_api;
 mod write_api;
 
-pub(crate) use coin_api::CoinReadApi;
+pub use coin_api::{
+    BalanceChangePage, BalanceHistoryApiClient, CheckpointBalance, IndexedBalanceChange,
+};
+pub(crate) use coin_api::{BalanceHistoryReadApi, CoinReadApi};
 pub(crate) use event_api::EventReadApi;
 pub(crate) use governance_api::GovernanceReadApi;
 pub(crate) use read_api::ReadApi;
@@ -6,23 +6,24 @@ use crate::store::IndexerStore;
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::http_client::HttpClient;
-use jsonrpsee::RpcModule;
+use jsonrpsee::types::{SubscriptionEmptyError, SubscriptionResult};
+use jsonrpsee::{RpcModule, SubscriptionSink};
 use std::collections::BTreeMap;
 use sui_json_rpc::api::{cap_page_limit, ReadApiClient, ReadApiServer};
 use sui_json_rpc::SuiRpcModule;
 use sui_json_rpc_types::{
-    Checkpoint, CheckpointId, DynamicFieldPage, MoveFunctionArgType, Page, SuiGetPastObjectRequest,
-    SuiMoveNormalizedFunction, SuiMoveNormalizedModule, SuiMoveNormalizedStruct,
-    SuiObjectDataOptions, SuiObjectInfo, SuiObjectResponse, SuiPastObjectResponse,
-    SuiTransactionResponse, SuiTransactionResponseOptions, SuiTransactionResponseQuery,
-    TransactionsPage,
+    Checkpoint, CheckpointId, CheckpointPage, DynamicFieldPage, MoveFunctionArgType, Page,
+    SuiGetPastObjectRequest, SuiMoveNormalizedFunction, SuiMoveNormalizedModule,
+    SuiMoveNormalizedStruct, SuiObjectDataOptions, SuiObjectInfo, SuiObjectResponse,
+    SuiPastObjectResponse, SuiTransactionResponse, SuiTransactionResponseOptions,
+    SuiTransactionResponseQuery, TransactionsPage,
 };
 use sui_open_rpc::Module;
 use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress, TxSequenceNumber};
 use sui_types::digests::TransactionDigest;
 use sui_types::dynamic_field::DynamicFieldName;
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
-use sui_types::query::TransactionFilter;
+use sui_types::query::{ObjectChangeFilter, TransactionFilter};
 
 pub(crate) struct ReadApi<S> {
     fullnode: HttpClient,
@@ -434,6 +435,37 @@ where
         }
         Ok(self.get_checkpoint_internal(id)?)
     }
+
+    async fn get_checkpoints(
+        &self,
+        cursor: Option<CheckpointSequenceNumber>,
+        limit: Option<usize>,
+        descending_order: Option<bool>,
+        show_transactions: Option<bool>,
+    ) -> RpcResult<CheckpointPage> {
+        self.fullnode
+            .get_checkpoints(cursor, limit, descending_order, show_transactions)
+            .await
+    }
+
+    fn subscribe_transaction(
+        &self,
+        mut _sink: SubscriptionSink,
+        _filter: TransactionFilter,
+        _options: Option<SuiTransactionResponseOptions>,
+    ) -> SubscriptionResult {
+        // subscription not supported by subscription yet
+        Err(SubscriptionEmptyError)
+    }
+
+    fn subscribe_object_changes(
+        &self,
+        mut _sink: SubscriptionSink,
+        _filter: ObjectChangeFilter,
+    ) -> SubscriptionResult {
+        // subscription not supported by subscription yet
+        Err(SubscriptionEmptyError)
+    }
 }
 
 impl<S> SuiRpcModule for ReadApi<S>
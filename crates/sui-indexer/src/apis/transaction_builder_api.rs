@@ -14,6 +14,7 @@ use sui_json_rpc_types::{
 };
 use sui_open_rpc::Module;
 use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::committee::EpochId;
 
 pub(crate) struct TransactionBuilderApi {
     fullnode: HttpClient,
@@ -34,11 +35,19 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         signer: SuiAddress,
         object_id: ObjectID,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
         recipient: SuiAddress,
+        expiration_epoch: Option<EpochId>,
     ) -> RpcResult<TransactionBytes> {
         self.fullnode
-            .transfer_object(signer, object_id, gas, gas_budget, recipient)
+            .transfer_object(
+                signer,
+                object_id,
+                gas,
+                gas_budget,
+                recipient,
+                expiration_epoch,
+            )
             .await
     }
 
@@ -46,12 +55,20 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         &self,
         signer: SuiAddress,
         sui_object_id: ObjectID,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
         recipient: SuiAddress,
         amount: Option<u64>,
+        expiration_epoch: Option<EpochId>,
     ) -> RpcResult<TransactionBytes> {
         self.fullnode
-            .transfer_sui(signer, sui_object_id, gas_budget, recipient, amount)
+            .transfer_sui(
+                signer,
+                sui_object_id,
+                gas_budget,
+                recipient,
+                amount,
+                expiration_epoch,
+            )
             .await
     }
 
@@ -62,10 +79,19 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         recipients: Vec<SuiAddress>,
         amounts: Vec<BigInt>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
+        expiration_epoch: Option<EpochId>,
     ) -> RpcResult<TransactionBytes> {
         self.fullnode
-            .pay(signer, input_coins, recipients, amounts, gas, gas_budget)
+            .pay(
+                signer,
+                input_coins,
+                recipients,
+                amounts,
+                gas,
+                gas_budget,
+                expiration_epoch,
+            )
             .await
     }
 
@@ -75,10 +101,18 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         input_coins: Vec<ObjectID>,
         recipients: Vec<SuiAddress>,
         amounts: Vec<BigInt>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
+        expiration_epoch: Option<EpochId>,
     ) -> RpcResult<TransactionBytes> {
         self.fullnode
-            .pay_sui(signer, input_coins, recipients, amounts, gas_budget)
+            .pay_sui(
+                signer,
+                input_coins,
+                recipients,
+                amounts,
+                gas_budget,
+                expiration_epoch,
+            )
             .await
     }
 
@@ -87,10 +121,11 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         signer: SuiAddress,
         input_coins: Vec<ObjectID>,
         recipient: SuiAddress,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
+        expiration_epoch: Option<EpochId>,
     ) -> RpcResult<TransactionBytes> {
         self.fullnode
-            .pay_all_sui(signer, input_coins, recipient, gas_budget)
+            .pay_all_sui(signer, input_coins, recipient, gas_budget, expiration_epoch)
             .await
     }
 
@@ -99,10 +134,36 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         sender: SuiAddress,
         compiled_modules: Vec<Base64>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
+        expiration_epoch: Option<EpochId>,
     ) -> RpcResult<TransactionBytes> {
         self.fullnode
-            .publish(sender, compiled_modules, gas, gas_budget)
+            .publish(sender, compiled_modules, gas, gas_budget, expiration_epoch)
+            .await
+    }
+
+    async fn upgrade(
+        &self,
+        sender: SuiAddress,
+        compiled_modules: Vec<Base64>,
+        dep_ids: Vec<ObjectID>,
+        upgrade_capability: ObjectID,
+        upgrade_policy: u8,
+        gas: Option<ObjectID>,
+        gas_budget: Option<u64>,
+        expiration_epoch: Option<EpochId>,
+    ) -> RpcResult<TransactionBytes> {
+        self.fullnode
+            .upgrade(
+                sender,
+                compiled_modules,
+                dep_ids,
+                upgrade_capability,
+                upgrade_policy,
+                gas,
+                gas_budget,
+                expiration_epoch,
+            )
             .await
     }
 
@@ -112,10 +173,18 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         coin_object_id: ObjectID,
         split_amounts: Vec<u64>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
+        expiration_epoch: Option<EpochId>,
     ) -> RpcResult<TransactionBytes> {
         self.fullnode
-            .split_coin(signer, coin_object_id, split_amounts, gas, gas_budget)
+            .split_coin(
+                signer,
+                coin_object_id,
+                split_amounts,
+                gas,
+                gas_budget,
+                expiration_epoch,
+            )
             .await
     }
 
@@ -125,10 +194,18 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         coin_object_id: ObjectID,
         split_count: u64,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
+        expiration_epoch: Option<EpochId>,
     ) -> RpcResult<TransactionBytes> {
         self.fullnode
-            .split_coin_equal(signer, coin_object_id, split_count, gas, gas_budget)
+            .split_coin_equal(
+                signer,
+                coin_object_id,
+                split_count,
+                gas,
+                gas_budget,
+                expiration_epoch,
+            )
             .await
     }
 
@@ -138,10 +215,18 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         primary_coin: ObjectID,
         coin_to_merge: ObjectID,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
+        expiration_epoch: Option<EpochId>,
     ) -> RpcResult<TransactionBytes> {
         self.fullnode
-            .merge_coin(signer, primary_coin, coin_to_merge, gas, gas_budget)
+            .merge_coin(
+                signer,
+                primary_coin,
+                coin_to_merge,
+                gas,
+                gas_budget,
+                expiration_epoch,
+            )
             .await
     }
 
@@ -154,8 +239,9 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         type_arguments: Vec<SuiTypeTag>,
         rpc_arguments: Vec<SuiJsonValue>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
         txn_builder_mode: Option<SuiTransactionBuilderMode>,
+        expiration_epoch: Option<EpochId>,
     ) -> RpcResult<TransactionBytes> {
         self.fullnode
             .move_call(
@@ -168,6 +254,7 @@ impl TransactionBuilderServer for TransactionBuilderApi {
                 gas,
                 gas_budget,
                 txn_builder_mode,
+                expiration_epoch,
             )
             .await
     }
@@ -177,11 +264,19 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         signer: SuiAddress,
         params: Vec<RPCTransactionRequestParams>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
         txn_builder_mode: Option<SuiTransactionBuilderMode>,
+        expiration_epoch: Option<EpochId>,
     ) -> RpcResult<TransactionBytes> {
         self.fullnode
-            .batch_transaction(signer, params, gas, gas_budget, txn_builder_mode)
+            .batch_transaction(
+                signer,
+                params,
+                gas,
+                gas_budget,
+                txn_builder_mode,
+                expiration_epoch,
+            )
             .await
     }
 
@@ -192,10 +287,19 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         amount: Option<u64>,
         validator: SuiAddress,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
+        expiration_epoch: Option<EpochId>,
     ) -> RpcResult<TransactionBytes> {
         self.fullnode
-            .request_add_stake(signer, coins, amount, validator, gas, gas_budget)
+            .request_add_stake(
+                signer,
+                coins,
+                amount,
+                validator,
+                gas,
+                gas_budget,
+                expiration_epoch,
+            )
             .await
     }
 
@@ -205,10 +309,18 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         delegation: ObjectID,
         staked_sui: ObjectID,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
+        expiration_epoch: Option<EpochId>,
     ) -> RpcResult<TransactionBytes> {
         self.fullnode
-            .request_withdraw_stake(signer, delegation, staked_sui, gas, gas_budget)
+            .request_withdraw_stake(
+                signer,
+                delegation,
+                staked_sui,
+                gas,
+                gas_budget,
+                expiration_epoch,
+            )
             .await
     }
 }
@@ -6,6 +6,7 @@ use jsonrpsee::types::error::CallError;
 use sui_types::base_types::ObjectIDParseError;
 use sui_types::error::SuiError;
 use thiserror::Error;
+use typed_store::rocks::TypedStoreError;
 
 #[derive(Debug, Error)]
 pub enum IndexerError {
@@ -15,6 +16,9 @@ pub enum IndexerError {
     #[error("Indexer failed to deserialize event from events table with error: `{0}`")]
     EventDeserializationError(String),
 
+    #[error("Indexer found checkpoint data inconsistent with the fullnode: `{0}`")]
+    CheckpointMismatchError(String),
+
     #[error("Indexer failed to read fullnode with error: `{0}`")]
     FullNodeReadingError(String),
 
@@ -42,6 +46,15 @@ pub enum IndexerError {
     #[error(transparent)]
     PostgresError(#[from] diesel::result::Error),
 
+    #[error("Indexer failed to read RocksDB with error: `{0}`")]
+    RocksDbReadError(String),
+
+    #[error("Indexer failed to commit changes to RocksDB with error: `{0}`")]
+    RocksDbWriteError(String),
+
+    #[error(transparent)]
+    TypedStoreError(#[from] TypedStoreError),
+
     #[error("Indexer failed to initialize fullnode RPC client with error: `{0}`")]
     RpcClientInitError(String),
 
@@ -67,6 +80,7 @@ pub enum IndexerError {
 impl IndexerError {
     pub fn name(&self) -> String {
         match self {
+            IndexerError::CheckpointMismatchError(_) => "CheckpointMismatchError".into(),
             IndexerError::FullNodeReadingError(_) => "FullNodeReadingError".into(),
             IndexerError::PostgresReadError(_) => "PostgresReadError".into(),
             IndexerError::PostgresWriteError(_) => "PostgresWriteError".into(),
@@ -81,6 +95,9 @@ impl IndexerError {
             IndexerError::SerdeError(_) => "SerdeError".into(),
             IndexerError::NotImplementedError(_) => "NotImplementedError".into(),
             IndexerError::PostgresError(_) => "PostgresError".into(),
+            IndexerError::RocksDbReadError(_) => "RocksDbReadError".into(),
+            IndexerError::RocksDbWriteError(_) => "RocksDbWriteError".into(),
+            IndexerError::TypedStoreError(_) => "TypedStoreError".into(),
             IndexerError::UncategorizedError(_) => "UncategorizedError".into(),
             IndexerError::ObjectIdParseError(_) => "ObjectIdParseError".into(),
             IndexerError::SuiError(_) => "SuiError".into(),
@@ -3,8 +3,10 @@
 
 use crate::errors::IndexerError;
 use crate::metrics::IndexerCheckpointHandlerMetrics;
+use crate::models::addresses::Address;
+use crate::models::balance_changes::{compose_balance_change, BalanceChange};
 use crate::models::checkpoints::Checkpoint;
-use crate::models::events::compose_event;
+use crate::models::events::{compose_event, Event};
 use crate::models::move_calls::MoveCall;
 use crate::models::objects::{DeletedObject, Object, ObjectStatus};
 use crate::models::packages::Package;
@@ -15,40 +17,74 @@ use crate::store::{
     TransactionObjectChanges,
 };
 use futures::future::join_all;
-use futures::FutureExt;
+use futures::{stream, FutureExt, StreamExt};
 use mysten_metrics::spawn_monitored_task;
 use prometheus::Registry;
 use std::collections::BTreeMap;
 use sui_json_rpc_types::{
-    OwnedObjectRef, SuiCommand, SuiGetPastObjectRequest, SuiObjectData, SuiObjectDataOptions,
-    SuiRawData, SuiTransactionDataAPI, SuiTransactionEffectsAPI, SuiTransactionKind,
-    SuiTransactionResponse, SuiTransactionResponseOptions,
+    Checkpoint as RpcCheckpoint, OwnedObjectRef, SuiCommand, SuiGetPastObjectRequest,
+    SuiObjectData, SuiObjectDataOptions, SuiRawData, SuiTransactionDataAPI,
+    SuiTransactionEffectsAPI, SuiTransactionKind, SuiTransactionResponse,
+    SuiTransactionResponseOptions,
 };
 use sui_sdk::error::Error;
 use sui_sdk::SuiClient;
+use sui_types::base_types::{ObjectID, SequenceNumber, TransactionDigest};
+use sui_types::event::EventID;
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
-use sui_types::object::Owner;
+use sui_types::object::{ObjectRead, Owner};
 use tokio::task::JoinHandle;
 use tracing::{info, warn};
 
 const HANDLER_RETRY_INTERVAL_IN_SECS: u64 = 10;
 const MULTI_GET_CHUNK_SIZE: usize = 500;
+const BALANCE_CHANGES_FETCH_CONCURRENCY: usize = 50;
+
+/// Configuration of the checkpoint ingestion pipeline. Checkpoints are committed in order, so the
+/// concurrencies also bound how many checkpoints each stage keeps in memory.
+#[derive(Clone, Debug)]
+pub struct CheckpointHandlerConfig {
+    /// Number of checkpoints downloaded from the fullnode concurrently.
+    pub fetch_concurrency: usize,
+    /// Number of downloaded checkpoints converted into models concurrently.
+    pub index_concurrency: usize,
+    /// Whether to index balance changes. The fullnode only serves them per transaction, so this
+    /// costs one extra request per transaction on top of the batched checkpoint reads.
+    pub index_balance_changes: bool,
+}
+
+impl Default for CheckpointHandlerConfig {
+    fn default() -> Self {
+        Self {
+            fetch_concurrency: 1,
+            index_concurrency: 1,
+            index_balance_changes: true,
+        }
+    }
+}
 
 pub struct CheckpointHandler<S> {
     state: S,
     rpc_client: SuiClient,
     metrics: IndexerCheckpointHandlerMetrics,
+    config: CheckpointHandlerConfig,
 }
 
 impl<S> CheckpointHandler<S>
 where
     S: IndexerStore + Sync + Send + 'static,
 {
-    pub fn new(state: S, rpc_client: SuiClient, prometheus_registry: &Registry) -> Self {
+    pub fn new(
+        state: S,
+        rpc_client: SuiClient,
+        prometheus_registry: &Registry,
+        config: CheckpointHandlerConfig,
+    ) -> Self {
         Self {
             state,
             rpc_client,
             metrics: IndexerCheckpointHandlerMetrics::new(prometheus_registry),
+            config,
         }
     }
 
@@ -71,55 +107,173 @@ where
 
     async fn start(&self) -> Result<(), IndexerError> {
         info!("Indexer checkpoint handler started...");
-        let mut next_cursor_sequence_number = self.state.get_latest_checkpoint_sequence_number()?;
-        if next_cursor_sequence_number > 0 {
-            info!("Resuming from checkpoint {next_cursor_sequence_number}");
-        }
-        next_cursor_sequence_number += 1;
-
-        loop {
-            self.metrics.total_checkpoint_requested.inc();
-            let request_guard = self.metrics.full_node_read_request_latency.start_timer();
-
-            let mut checkpoint = self
-                .download_checkpoint_data(next_cursor_sequence_number as u64)
-                .await;
-            // this happens very often b/c checkpoint indexing is faster than checkpoint
-            // generation. Ideally we will want to differentiate between a real error and
-            // a checkpoint not generated yet.
-            while checkpoint.is_err() {
-                tokio::time::sleep(std::time::Duration::from_secs(1)).await;
-                checkpoint = self
-                    .download_checkpoint_data(next_cursor_sequence_number as u64)
-                    .await
-            }
-            // unwrap here is safe because we checked for error above
-            let checkpoint = checkpoint.unwrap();
-            request_guard.stop_and_record();
-            self.metrics.total_checkpoint_received.inc();
+        let latest_sequence_number = self.state.get_latest_checkpoint_sequence_number()?;
+        let mut previous_cp = if latest_sequence_number >= 0 {
+            info!("Resuming from checkpoint {latest_sequence_number}");
+            self.state
+                .get_checkpoint((latest_sequence_number as u64).into())?
+        } else {
+            Checkpoint::default()
+        };
+        let next_cursor_sequence_number = (latest_sequence_number + 1) as u64;
+
+        // Checkpoints are downloaded and converted concurrently, `buffered` yields them in
+        // sequence order and bounds the number of checkpoints held in memory by each stage.
+        let mut indexed_checkpoints = stream::iter(next_cursor_sequence_number..)
+            .map(|seq| self.fetch_checkpoint(seq))
+            .buffered(self.config.fetch_concurrency)
+            .map(|checkpoint| {
+                let index_latency = self.metrics.index_latency.clone();
+                let index_balance_changes = self.config.index_balance_changes;
+                // Time the conversion only, not the wait for a blocking thread.
+                tokio::task::spawn_blocking(move || {
+                    let _index_guard = index_latency.start_timer();
+                    Self::index_checkpoint_data(checkpoint, index_balance_changes)
+                })
+                .map(|indexed| indexed.map_err(anyhow::Error::from)?)
+            })
+            .buffered(self.config.index_concurrency);
 
-            // Index checkpoint data
-            // TODO: Metrics
-            let (indexed_checkpoint, indexed_epoch) = self.index_checkpoint(checkpoint)?;
+        while let Some(indexed_checkpoint) = indexed_checkpoints.next().await {
+            let (indexed_checkpoint, indexed_epoch) =
+                indexed_checkpoint?.with_previous_checkpoint(&previous_cp)?;
 
             // Write to DB
             let db_guard = self.metrics.db_write_request_latency.start_timer();
             let tx_count = indexed_checkpoint.transactions.len();
             let object_count = indexed_checkpoint.objects_changes.len();
 
-            self.state.persist_checkpoint(&indexed_checkpoint)?;
+            self.state
+                .persist_checkpoint(&indexed_checkpoint, indexed_epoch.as_ref())?;
             info!(
                 "Checkpoint {} committed with {tx_count} transactions and {object_count} objects.",
-                next_cursor_sequence_number
+                indexed_checkpoint.checkpoint.sequence_number
             );
             self.metrics.total_checkpoint_processed.inc();
             db_guard.stop_and_record();
 
-            if let Some(indexed_epoch) = indexed_epoch {
-                self.state.persist_epoch(&indexed_epoch)?;
+            previous_cp = indexed_checkpoint.checkpoint;
+        }
+        Ok(())
+    }
+
+    /// Download checkpoint `seq`, waiting for it to be produced if needed.
+    async fn fetch_checkpoint(&self, seq: CheckpointSequenceNumber) -> CheckpointData {
+        self.metrics.total_checkpoint_requested.inc();
+        let request_guard = self.metrics.full_node_read_request_latency.start_timer();
+
+        let mut checkpoint = self.download_checkpoint_data(seq).await;
+        // this happens very often b/c checkpoint indexing is faster than checkpoint
+        // generation. Ideally we will want to differentiate between a real error and
+        // a checkpoint not generated yet.
+        while checkpoint.is_err() {
+            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+            checkpoint = self.download_checkpoint_data(seq).await
+        }
+        request_guard.stop_and_record();
+        self.metrics.total_checkpoint_received.inc();
+        // unwrap here is safe because we checked for error above
+        checkpoint.unwrap()
+    }
+
+    /// Re-derives the indexed data of checkpoint `seq` from the fullnode and diffs it against the
+    /// data committed to the store, returning a description of every difference found.
+    pub async fn verify_checkpoint(
+        &self,
+        seq: CheckpointSequenceNumber,
+    ) -> Result<Vec<String>, IndexerError> {
+        let checkpoint = self
+            .download_checkpoint_data(seq)
+            .await
+            .map_err(|e| IndexerError::FullNodeReadingError(e.to_string()))?;
+        let (expected, _) = self.index_checkpoint(checkpoint)?;
+        let mut diffs = vec![];
+
+        match self.state.get_checkpoint(seq.into()) {
+            Ok(indexed) if indexed == expected.checkpoint => {}
+            Ok(indexed) => diffs.push(format!(
+                "checkpoint differs, indexed {:?}, expected {:?}",
+                indexed, expected.checkpoint
+            )),
+            Err(e) => diffs.push(format!("checkpoint is not indexed: {e}")),
+        }
+
+        // Row ids are assigned by the store, ignore them when comparing.
+        for transaction in &expected.transactions {
+            let digest = &transaction.transaction_digest;
+            match self.state.get_transaction_by_digest(digest) {
+                Ok(indexed)
+                    if indexed
+                        == Transaction {
+                            id: indexed.id,
+                            ..transaction.clone()
+                        } => {}
+                Ok(_) => diffs.push(format!("transaction {digest} differs")),
+                Err(e) => diffs.push(format!("transaction {digest} is not indexed: {e}")),
+            }
+        }
+
+        for event in &expected.events {
+            let tx_digest = event
+                .transaction_digest
+                .parse::<TransactionDigest>()
+                .map_err(|e| {
+                    IndexerError::SerdeError(format!("Failed to parse event tx digest: {:?}", e))
+                })?;
+            let id = EventID::from((tx_digest, event.event_sequence));
+            match self.state.get_event(id.clone()) {
+                Ok(indexed)
+                    if indexed
+                        == Event {
+                            id: indexed.id,
+                            ..event.clone()
+                        } => {}
+                Ok(_) => diffs.push(format!("event {id:?} differs")),
+                Err(e) => diffs.push(format!("event {id:?} is not indexed: {e}")),
+            }
+        }
+
+        let changed_objects = expected
+            .objects_changes
+            .iter()
+            .flat_map(|changes| &changes.mutated_objects);
+        for object in changed_objects {
+            let (object_id, version, digest) = object.get_object_ref()?;
+            match self.state.get_object(object_id, Some(version)) {
+                Ok(ObjectRead::Exists((_, _, indexed_digest), _, _))
+                    if indexed_digest == digest => {}
+                Ok(_) => diffs.push(format!("object {object_id} version {version} differs")),
+                Err(e) => diffs.push(format!(
+                    "object {object_id} version {version} is not indexed: {e}"
+                )),
             }
-            next_cursor_sequence_number += 1;
         }
+
+        // Wrapped objects keep their contents, deleted ones read as deleted at that version.
+        let deleted_objects = expected
+            .objects_changes
+            .iter()
+            .flat_map(|changes| &changes.deleted_objects);
+        for deleted in deleted_objects {
+            let object_id: ObjectID = deleted.object_id.parse()?;
+            let version = SequenceNumber::from(deleted.version as u64);
+            let is_deleted = matches!(
+                deleted.object_status,
+                ObjectStatus::Deleted | ObjectStatus::UnwrappedThenDeleted
+            );
+            match self.state.get_object(object_id, Some(version)) {
+                Ok(ObjectRead::Deleted(_)) if is_deleted => {}
+                Ok(ObjectRead::Exists(..)) if !is_deleted => {}
+                Ok(_) => diffs.push(format!(
+                    "deleted object {object_id} version {version} differs"
+                )),
+                Err(e) => diffs.push(format!(
+                    "deleted object {object_id} version {version} is not indexed: {e}"
+                )),
+            }
+        }
+
+        Ok(diffs)
     }
 
     /// Download all the data we need for one checkpoint.
@@ -133,7 +287,7 @@ where
             .get_checkpoint(seq.into())
             .await?;
 
-        let transactions = join_all(checkpoint.transactions.chunks(MULTI_GET_CHUNK_SIZE).map(
+        let mut transactions = join_all(checkpoint.transactions.chunks(MULTI_GET_CHUNK_SIZE).map(
             |digests| {
                 self.rpc_client
                     .read_api()
@@ -153,6 +307,29 @@ where
             Ok::<_, Error>(acc)
         })?;
 
+        // multiGetTransactions does not serve balance changes, fetch them per transaction.
+        if self.config.index_balance_changes {
+            let balance_changes = stream::iter(transactions.iter().map(|tx| {
+                self.rpc_client.read_api().get_transaction_with_options(
+                    tx.digest,
+                    SuiTransactionResponseOptions::new().with_balance_changes(),
+                )
+            }))
+            .buffered(BALANCE_CHANGES_FETCH_CONCURRENCY)
+            .collect::<Vec<_>>()
+            .await;
+            for (tx, response) in transactions.iter_mut().zip(balance_changes) {
+                let response = response.map_err(|e| {
+                    warn!(
+                        "Failed to fetch balance changes of transaction {} in checkpoint {} with error: {:?}",
+                        tx.digest, seq, e
+                    );
+                    e
+                })?;
+                tx.balance_changes = response.balance_changes;
+            }
+        }
+
         let object_changes = transactions
             .iter()
             .flat_map(|tx| {
@@ -218,19 +395,26 @@ where
         &self,
         data: CheckpointData,
     ) -> Result<(TemporaryCheckpointStore, Option<TemporaryEpochStore>), IndexerError> {
+        let previous_cp = if data.checkpoint.sequence_number == 0 {
+            Checkpoint::default()
+        } else {
+            self.state
+                .get_checkpoint((data.checkpoint.sequence_number - 1).into())?
+        };
+        Self::index_checkpoint_data(data, self.config.index_balance_changes)?
+            .with_previous_checkpoint(&previous_cp)
+    }
+
+    fn index_checkpoint_data(
+        data: CheckpointData,
+        index_balance_changes: bool,
+    ) -> Result<IndexedCheckpointData, IndexerError> {
         let CheckpointData {
             checkpoint,
             transactions,
             changed_objects,
         } = data;
 
-        let previous_cp = if checkpoint.sequence_number == 0 {
-            Checkpoint::default()
-        } else {
-            self.state
-                .get_checkpoint((checkpoint.sequence_number - 1).into())?
-        };
-
         // Index transaction
         let db_transactions = transactions
             .iter()
@@ -382,6 +566,25 @@ where
             })
             .collect();
 
+        let balance_changes = index_balance_changes.then(|| {
+            transactions
+                .iter()
+                .flat_map(|tx| {
+                    tx.balance_changes
+                        .iter()
+                        .flatten()
+                        .filter_map(|balance_change| {
+                            compose_balance_change(
+                                balance_change,
+                                &tx.digest,
+                                checkpoint.sequence_number,
+                                checkpoint.epoch,
+                            )
+                        })
+                })
+                .collect()
+        });
+
         // Index epoch
         // TODO: Aggregate all object owner changes into owner index at epoch change.
         let epoch_index =
@@ -392,19 +595,18 @@ where
                     owner_index: vec![],
                 });
 
-        Ok((
-            TemporaryCheckpointStore {
-                checkpoint: Checkpoint::from(&checkpoint, &previous_cp)?,
-                transactions: db_transactions,
-                events,
-                objects_changes,
-                addresses,
-                packages,
-                move_calls,
-                recipients,
-            },
-            epoch_index,
-        ))
+        Ok(IndexedCheckpointData {
+            checkpoint,
+            transactions: db_transactions,
+            events,
+            objects_changes,
+            addresses,
+            packages,
+            move_calls,
+            recipients,
+            balance_changes,
+            epoch: epoch_index,
+        })
     }
 
     fn index_packages(
@@ -451,3 +653,121 @@ where
             .collect()
     }
 }
+
+/// Checkpoint data converted into models, except for the checkpoint row itself, which is derived
+/// from the previous checkpoint and so can only be built in sequence order.
+struct IndexedCheckpointData {
+    checkpoint: RpcCheckpoint,
+    transactions: Vec<Transaction>,
+    events: Vec<Event>,
+    objects_changes: Vec<TransactionObjectChanges>,
+    addresses: Vec<Address>,
+    packages: Vec<Package>,
+    move_calls: Vec<MoveCall>,
+    recipients: Vec<Recipient>,
+    balance_changes: Option<Vec<BalanceChange>>,
+    epoch: Option<TemporaryEpochStore>,
+}
+
+impl IndexedCheckpointData {
+    fn with_previous_checkpoint(
+        self,
+        previous_cp: &Checkpoint,
+    ) -> Result<(TemporaryCheckpointStore, Option<TemporaryEpochStore>), IndexerError> {
+        let checkpoint = &self.checkpoint;
+        // Refuse to index on top of data from a different chain, e.g. after a network wipe.
+        let previous_digest = checkpoint.previous_digest.map(|d| d.base58_encode());
+        if checkpoint.sequence_number != 0
+            && previous_digest.as_ref() != Some(&previous_cp.checkpoint_digest)
+        {
+            return Err(IndexerError::CheckpointMismatchError(format!(
+                "Checkpoint {} has previous digest {:?} but indexed checkpoint {} has digest {}",
+                checkpoint.sequence_number,
+                previous_digest,
+                previous_cp.sequence_number,
+                previous_cp.checkpoint_digest
+            )));
+        }
+
+        Ok((
+            TemporaryCheckpointStore {
+                checkpoint: Checkpoint::from(checkpoint, previous_cp)?,
+                transactions: self.transactions,
+                events: self.events,
+                objects_changes: self.objects_changes,
+                addresses: self.addresses,
+                packages: self.packages,
+                move_calls: self.move_calls,
+                recipients: self.recipients,
+                balance_changes: self.balance_changes,
+            },
+            self.epoch,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_types::digests::CheckpointDigest;
+    use sui_types::gas::GasCostSummary;
+
+    fn indexed_checkpoint(
+        sequence_number: CheckpointSequenceNumber,
+        previous_digest: Option<CheckpointDigest>,
+    ) -> IndexedCheckpointData {
+        IndexedCheckpointData {
+            checkpoint: RpcCheckpoint {
+                epoch: 0,
+                sequence_number,
+                digest: CheckpointDigest::random(),
+                network_total_transactions: 0,
+                previous_digest,
+                epoch_rolling_gas_cost_summary: GasCostSummary::default(),
+                timestamp_ms: 1000 * sequence_number,
+                end_of_epoch_data: None,
+                transactions: vec![],
+                checkpoint_commitments: vec![],
+            },
+            transactions: vec![],
+            events: vec![],
+            objects_changes: vec![],
+            addresses: vec![],
+            packages: vec![],
+            move_calls: vec![],
+            recipients: vec![],
+            balance_changes: Some(vec![]),
+            epoch: None,
+        }
+    }
+
+    #[test]
+    fn test_with_previous_checkpoint_rejects_broken_digest_chain() {
+        let (genesis, _) = indexed_checkpoint(0, None)
+            .with_previous_checkpoint(&Checkpoint::default())
+            .unwrap();
+        let genesis = genesis.checkpoint;
+        let genesis_digest = genesis.checkpoint_digest.parse().unwrap();
+
+        let (next, _) = indexed_checkpoint(1, Some(genesis_digest))
+            .with_previous_checkpoint(&genesis)
+            .unwrap();
+        assert_eq!(
+            Some(genesis.checkpoint_digest.clone()),
+            next.checkpoint.previous_checkpoint_digest
+        );
+
+        let result = indexed_checkpoint(1, Some(CheckpointDigest::random()))
+            .with_previous_checkpoint(&genesis);
+        assert!(matches!(
+            result,
+            Err(IndexerError::CheckpointMismatchError(_))
+        ));
+
+        let result = indexed_checkpoint(1, None).with_previous_checkpoint(&genesis);
+        assert!(matches!(
+            result,
+            Err(IndexerError::CheckpointMismatchError(_))
+        ));
+    }
+}
@@ -9,10 +9,11 @@ use diesel::prelude::*;
 use diesel::r2d2::{ConnectionManager, Pool, PooledConnection};
 use jsonrpsee::http_client::{HeaderMap, HeaderValue, HttpClientBuilder};
 use prometheus::Registry;
-use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::net::SocketAddr;
+use std::ops::Range;
 use sui_json_rpc::{JsonRpcServerBuilder, ServerHandle, CLIENT_SDK_TYPE_HEADER};
 use sui_sdk::{SuiClient, SuiClientBuilder};
-use tracing::{info, warn};
+use tracing::{error, info, warn};
 
 pub mod apis;
 pub mod errors;
@@ -28,9 +29,11 @@ pub type PgConnectionPool = Pool<ConnectionManager<PgConnection>>;
 pub type PgPoolConnection = PooledConnection<ConnectionManager<PgConnection>>;
 
 use crate::apis::{
-    CoinReadApi, EventReadApi, GovernanceReadApi, ReadApi, TransactionBuilderApi, WriteApi,
+    BalanceHistoryReadApi, CoinReadApi, EventReadApi, GovernanceReadApi, ReadApi,
+    TransactionBuilderApi, WriteApi,
 };
 use crate::handlers::checkpoint_handler::CheckpointHandler;
+pub use crate::handlers::checkpoint_handler::CheckpointHandlerConfig;
 use crate::store::IndexerStore;
 use errors::IndexerError;
 use mysten_metrics::spawn_monitored_task;
@@ -43,12 +46,36 @@ pub struct Indexer;
 impl Indexer {
     pub async fn start<S: IndexerStore + Sync + Send + Clone + 'static>(
         fullnode_url: &str,
+        rpc_server_address: SocketAddr,
         registry: &Registry,
         store: S,
     ) -> Result<(), IndexerError> {
-        let handle = build_json_rpc_server(registry, store.clone(), fullnode_url)
-            .await
-            .expect("Json rpc server should not run into errors upon start.");
+        Self::start_with_config(
+            fullnode_url,
+            rpc_server_address,
+            registry,
+            store,
+            CheckpointHandlerConfig::default(),
+        )
+        .await
+    }
+
+    pub async fn start_with_config<S: IndexerStore + Sync + Send + Clone + 'static>(
+        fullnode_url: &str,
+        rpc_server_address: SocketAddr,
+        registry: &Registry,
+        store: S,
+        config: CheckpointHandlerConfig,
+    ) -> Result<(), IndexerError> {
+        let handle = build_json_rpc_server(
+            registry,
+            store.clone(),
+            fullnode_url,
+            rpc_server_address,
+            config.index_balance_changes,
+        )
+        .await
+        .expect("Json rpc server should not run into errors upon start.");
         // let JSON RPC server run forever.
         spawn_monitored_task!(handle.stopped());
         info!("Sui indexer started...");
@@ -57,7 +84,8 @@ impl Indexer {
             let rpc_client = new_rpc_client(fullnode_url).await?;
             // NOTE: Each handler is responsible for one type of data from nodes,like transactions and events;
             // Handler orchestrator runs these handlers in parallel and manage them upon errors etc.
-            let cp = CheckpointHandler::new(store.clone(), rpc_client.clone(), registry);
+            let cp =
+                CheckpointHandler::new(store.clone(), rpc_client.clone(), registry, config.clone());
             cp.spawn()
                 .await
                 .expect("Indexer main should not run into errors.");
@@ -65,6 +93,41 @@ impl Indexer {
         })
         .await
     }
+
+    /// Re-indexes the checkpoints in `range` from the fullnode and compares the result with what
+    /// `store` has committed, returning the number of checkpoints that do not match.
+    pub async fn verify_range<S: IndexerStore + Sync + Send + Clone + 'static>(
+        fullnode_url: &str,
+        registry: &Registry,
+        store: S,
+        range: Range<u64>,
+    ) -> Result<usize, IndexerError> {
+        let rpc_client = new_rpc_client(fullnode_url).await?;
+        let cp = CheckpointHandler::new(
+            store,
+            rpc_client,
+            registry,
+            CheckpointHandlerConfig::default(),
+        );
+        let mut mismatches = 0;
+        for seq in range {
+            match cp.verify_checkpoint(seq).await {
+                Ok(diffs) if diffs.is_empty() => {}
+                Ok(diffs) => {
+                    mismatches += 1;
+                    for diff in diffs {
+                        error!("Checkpoint {} mismatch: {}", seq, diff);
+                    }
+                }
+                Err(e) => {
+                    mismatches += 1;
+                    error!("Failed to verify checkpoint {} with error: {:?}", seq, e);
+                }
+            }
+        }
+        info!("Verified checkpoints, {} mismatched", mismatches);
+        Ok(mismatches)
+    }
 }
 
 pub async fn new_rpc_client(http_url: &str) -> Result<SuiClient, IndexerError> {
@@ -113,6 +176,8 @@ pub async fn build_json_rpc_server<S: IndexerStore + Sync + Send + 'static + Clo
     prometheus_registry: &Registry,
     state: S,
     fullnode_url: &str,
+    rpc_server_address: SocketAddr,
+    serve_balance_history: bool,
 ) -> Result<ServerHandle, IndexerError> {
     let mut builder = JsonRpcServerBuilder::new(FAKE_PKG_VERSION, prometheus_registry);
 
@@ -128,11 +193,12 @@ pub async fn build_json_rpc_server<S: IndexerStore + Sync + Send + 'static + Clo
 
     builder.register_module(ReadApi::new(state.clone(), http_client.clone()))?;
     builder.register_module(CoinReadApi::new(http_client.clone()))?;
+    if serve_balance_history {
+        builder.register_module(BalanceHistoryReadApi::new(state.clone()))?;
+    }
     builder.register_module(TransactionBuilderApi::new(http_client.clone()))?;
     builder.register_module(GovernanceReadApi::new(http_client.clone()))?;
     builder.register_module(EventReadApi::new(state, http_client.clone()))?;
     builder.register_module(WriteApi::new(http_client))?;
-    // TODO: placeholder, read from env or config file.
-    let default_socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 3030);
-    Ok(builder.start(default_socket_addr).await?)
+    Ok(builder.start(rpc_server_address).await?)
 }
@@ -1,13 +1,20 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use anyhow::{anyhow, bail};
+use prometheus::Registry;
+use std::net::{IpAddr, SocketAddr};
+use std::num::NonZeroUsize;
+use std::ops::Range;
+use std::path::PathBuf;
+
 use sui_indexer::errors::IndexerError;
-use sui_indexer::{new_pg_connection_pool, Indexer};
+use sui_indexer::{new_pg_connection_pool, CheckpointHandlerConfig, Indexer};
 use sui_node::metrics::start_prometheus_server;
 
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 
-use sui_indexer::store::PgIndexerStore;
+use sui_indexer::store::{IndexerStore, PgIndexerStore, RocksIndexerStore};
 
 #[tokio::main]
 async fn main() -> Result<(), IndexerError> {
@@ -28,10 +35,63 @@ async fn main() -> Result<(), IndexerError> {
     );
 
     let registry = registry_service.default_registry();
-    let pg_connection_pool = new_pg_connection_pool(&indexer_config.db_url).await?;
-    let store = PgIndexerStore::new(pg_connection_pool);
+    match indexer_config.store {
+        StoreBackend::Postgres => {
+            // NOTE: clap requires --db-url for the postgres backend, so unwrap() is safe here.
+            let pg_connection_pool =
+                new_pg_connection_pool(indexer_config.db_url.as_ref().unwrap()).await?;
+            let store = PgIndexerStore::new(pg_connection_pool);
+            run(&indexer_config, &registry, store).await
+        }
+        StoreBackend::Rocksdb => {
+            // NOTE: clap requires --db-path for the rocksdb backend, so unwrap() is safe here.
+            let store = RocksIndexerStore::open(indexer_config.db_path.as_ref().unwrap())?;
+            run(&indexer_config, &registry, store).await
+        }
+    }
+}
+
+async fn run<S: IndexerStore + Sync + Send + Clone + 'static>(
+    config: &IndexerConfig,
+    registry: &Registry,
+    store: S,
+) -> Result<(), IndexerError> {
+    if let Some(range) = config.verify_range.clone() {
+        let mismatches =
+            Indexer::verify_range(&config.rpc_client_url, registry, store, range.clone()).await?;
+        if mismatches > 0 {
+            return Err(IndexerError::CheckpointMismatchError(format!(
+                "{} of checkpoints {:?} do not match the fullnode",
+                mismatches, range
+            )));
+        }
+        return Ok(());
+    }
+    let handler_config = CheckpointHandlerConfig {
+        fetch_concurrency: config.checkpoint_fetch_concurrency.get(),
+        index_concurrency: config.checkpoint_index_concurrency.get(),
+        index_balance_changes: !config.skip_balance_changes,
+    };
+    let rpc_server_address = SocketAddr::new(config.rpc_server_host, config.rpc_server_port);
+    Indexer::start_with_config(
+        &config.rpc_client_url,
+        rpc_server_address,
+        registry,
+        store,
+        handler_config,
+    )
+    .await
+}
 
-    Indexer::start(&indexer_config.rpc_client_url, &registry, store).await
+fn parse_checkpoint_range(s: &str) -> Result<Range<u64>, anyhow::Error> {
+    let (start, end) = s
+        .split_once("..")
+        .ok_or_else(|| anyhow!("Expected a checkpoint range of the form START..END"))?;
+    let range = start.parse()?..end.parse()?;
+    if range.is_empty() {
+        bail!("Checkpoint range {:?} is empty", range);
+    }
+    Ok(range)
 }
 
 #[derive(Parser)]
@@ -41,12 +101,46 @@ async fn main() -> Result<(), IndexerError> {
     rename_all = "kebab-case"
 )]
 pub struct IndexerConfig {
-    #[clap(long)]
-    pub db_url: String,
+    /// The storage backend to index into.
+    #[clap(long, value_enum, default_value = "postgres")]
+    pub store: StoreBackend,
+    /// Postgres database URL, used by the postgres backend.
+    #[clap(long, required_if_eq("store", "postgres"))]
+    pub db_url: Option<String>,
+    /// Directory of the embedded database, used by the rocksdb backend.
+    #[clap(long, required_if_eq("store", "rocksdb"))]
+    pub db_path: Option<PathBuf>,
     #[clap(long)]
     pub rpc_client_url: String,
+    /// IP address the indexer JSON-RPC server listens on.
+    #[clap(long, default_value = "127.0.0.1")]
+    pub rpc_server_host: IpAddr,
+    /// Port the indexer JSON-RPC server listens on.
+    #[clap(long, default_value = "3030")]
+    pub rpc_server_port: u16,
+    /// Instead of indexing, re-index the checkpoints START..END (end exclusive) from the fullnode
+    /// and compare them with the committed data, exiting with an error on any mismatch.
+    #[clap(long, parse(try_from_str = parse_checkpoint_range))]
+    pub verify_range: Option<Range<u64>>,
+    /// Number of checkpoints downloaded from the fullnode concurrently, raise it to speed up
+    /// backfilling.
+    #[clap(long, default_value = "1")]
+    pub checkpoint_fetch_concurrency: NonZeroUsize,
+    /// Number of downloaded checkpoints converted concurrently before being committed in order.
+    #[clap(long, default_value = "1")]
+    pub checkpoint_index_concurrency: NonZeroUsize,
+    /// Do not index balance changes, saving one fullnode request per transaction. The balance
+    /// history API is not served.
+    #[clap(long)]
+    pub skip_balance_changes: bool,
     #[clap(long, default_value = "0.0.0.0", global = true)]
     pub client_metric_host: String,
     #[clap(long, default_value = "9184", global = true)]
     pub client_metric_port: u16,
 }
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum StoreBackend {
+    Postgres,
+    Rocksdb,
+}
@@ -254,6 +254,7 @@ pub struct IndexerCheckpointHandlerMetrics {
     pub total_checkpoint_handler_error: IntCounter,
     pub db_write_request_latency: Histogram,
     pub full_node_read_request_latency: Histogram,
+    pub index_latency: Histogram,
 }
 
 impl IndexerCheckpointHandlerMetrics {
@@ -297,6 +298,13 @@ impl IndexerCheckpointHandlerMetrics {
                 registry,
             )
             .unwrap(),
+            index_latency: register_histogram_with_registry!(
+                "checkpoint_index_latency",
+                "Time spent converting a downloaded checkpoint into indexer models",
+                LATENCY_SEC_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
         }
     }
 }
@@ -0,0 +1,46 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::schema::balance_changes;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use sui_json_rpc_types::BalanceChange as SuiBalanceChange;
+use sui_types::base_types::TransactionDigest;
+use sui_types::object::Owner;
+
+#[derive(Queryable, Insertable, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[diesel(table_name = balance_changes)]
+pub struct BalanceChange {
+    #[diesel(deserialize_as = i64)]
+    pub id: Option<i64>,
+    pub transaction_digest: String,
+    pub checkpoint_sequence_number: i64,
+    pub epoch: i64,
+    pub owner: String,
+    pub coin_type: String,
+    /// Signed change of the balance in the smallest coin unit, as a decimal i128.
+    pub amount: String,
+}
+
+/// Balance changes of shared and immutable objects are not attributed to any address and
+/// are not indexed.
+pub fn compose_balance_change(
+    balance_change: &SuiBalanceChange,
+    transaction_digest: &TransactionDigest,
+    checkpoint_sequence_number: u64,
+    epoch: u64,
+) -> Option<BalanceChange> {
+    let owner = match balance_change.owner {
+        Owner::AddressOwner(address) | Owner::ObjectOwner(address) => address,
+        Owner::Shared { .. } | Owner::Immutable => return None,
+    };
+    Some(BalanceChange {
+        id: None,
+        transaction_digest: transaction_digest.to_string(),
+        checkpoint_sequence_number: checkpoint_sequence_number as i64,
+        epoch: epoch as i64,
+        owner: owner.to_string(),
+        coin_type: balance_change.coin_type.to_string(),
+        amount: balance_change.amount.to_string(),
+    })
+}
@@ -4,6 +4,7 @@
 use crate::errors::IndexerError;
 use chrono::NaiveDateTime;
 use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
 use sui_json_rpc_types::Checkpoint as RpcCheckpoint;
 use sui_types::base_types::TransactionDigest;
 use sui_types::digests::CheckpointDigest;
@@ -13,7 +14,7 @@ use sui_types::messages_checkpoint::EndOfEpochData;
 use crate::schema::checkpoints;
 use crate::schema::checkpoints::end_of_epoch_data;
 
-#[derive(Queryable, Insertable, Debug, Clone, Default)]
+#[derive(Queryable, Insertable, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 #[diesel(table_name = checkpoints)]
 pub struct Checkpoint {
     pub sequence_number: i64,
@@ -33,9 +34,23 @@ pub struct Checkpoint {
     pub total_transactions_from_genesis: i64,
     pub timestamp_ms: i64,
     pub timestamp_ms_str: NaiveDateTime,
+    // BCS has no floating point types, store the raw bits instead.
+    #[serde(with = "f32_bits")]
     pub checkpoint_tps: f32,
 }
 
+mod f32_bits {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &f32, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u32(value.to_bits())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f32, D::Error> {
+        u32::deserialize(deserializer).map(f32::from_bits)
+    }
+}
+
 impl TryFrom<Checkpoint> for RpcCheckpoint {
     type Error = IndexerError;
     fn try_from(checkpoint: Checkpoint) -> Result<Self, Self::Error> {
@@ -7,9 +7,10 @@ use crate::PgPoolConnection;
 
 use chrono::{NaiveDateTime, Utc};
 use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
 
 // NOTE: this is for the errors table in PG
-#[derive(Queryable, Insertable, Debug)]
+#[derive(Queryable, Insertable, Debug, Serialize, Deserialize)]
 #[diesel(table_name = error_logs)]
 pub struct ErrorLog {
     pub id: Option<i64>,
@@ -4,10 +4,11 @@
 use crate::errors::IndexerError;
 use crate::schema::events;
 use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
 use sui_json_rpc_types::{SuiEvent, SuiEventEnvelope, SuiMoveStruct};
 use sui_types::base_types::TransactionDigest;
 
-#[derive(Queryable, Insertable, Debug, Clone)]
+#[derive(Queryable, Insertable, Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[diesel(table_name = events)]
 pub struct Event {
     #[diesel(deserialize_as = i64)]
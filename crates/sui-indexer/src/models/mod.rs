@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod addresses;
+pub mod balance_changes;
 pub mod checkpoints;
 pub mod error_logs;
 pub mod events;
@@ -3,8 +3,9 @@
 
 use crate::schema::move_calls;
 use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Queryable, Insertable, Debug, Clone, Default)]
+#[derive(Queryable, Insertable, Debug, Clone, Default, Serialize, Deserialize)]
 #[diesel(table_name = move_calls)]
 pub struct MoveCall {
     pub id: Option<i64>,
@@ -13,6 +13,7 @@ use diesel::sql_types::{Bytea, Nullable, Record, VarChar};
 use diesel::SqlType;
 use diesel_derive_enum::DbEnum;
 use move_bytecode_utils::module_cache::GetModule;
+use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 use sui_json_rpc_types::{SuiObjectData, SuiObjectRef, SuiRawData};
 use sui_types::base_types::{EpochId, ObjectID, ObjectRef, ObjectType, SequenceNumber, SuiAddress};
@@ -23,7 +24,7 @@ use sui_types::object::{Data, MoveObject, ObjectFormatOptions, ObjectRead, Owner
 
 const OBJECT: &str = "object";
 
-#[derive(Queryable, Insertable, Debug, Identifiable, Clone)]
+#[derive(Queryable, Insertable, Debug, Identifiable, Clone, Serialize, Deserialize)]
 #[diesel(table_name = objects, primary_key(object_id))]
 pub struct Object {
     // epoch id in which this object got update.
@@ -43,7 +44,7 @@ pub struct Object {
     pub storage_rebate: i64,
     pub bcs: Vec<NamedBcsBytes>,
 }
-#[derive(SqlType, Debug, Clone)]
+#[derive(SqlType, Debug, Clone, Serialize, Deserialize)]
 #[diesel(sql_type = crate::schema::sql_types::BcsBytes)]
 pub struct NamedBcsBytes(pub String, pub Vec<u8>);
 
@@ -77,7 +78,7 @@ pub struct DeletedObject {
     pub has_public_transfer: bool,
 }
 
-#[derive(DbEnum, Debug, Clone, Copy)]
+#[derive(DbEnum, Debug, Clone, Copy, Serialize, Deserialize)]
 #[ExistingTypePath = "crate::schema::sql_types::ObjectStatus"]
 pub enum ObjectStatus {
     Created,
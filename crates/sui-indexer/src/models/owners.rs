@@ -4,6 +4,7 @@
 use crate::models::objects::ObjectStatus;
 use diesel::prelude::*;
 use diesel_derive_enum::DbEnum;
+use serde::{Deserialize, Serialize};
 
 #[derive(Queryable, Debug, Clone)]
 #[diesel(table_name = owner)]
@@ -18,7 +19,7 @@ pub struct ObjectOwner {
     pub object_status: ObjectStatus,
 }
 
-#[derive(DbEnum, Debug, Clone)]
+#[derive(DbEnum, Debug, Clone, Serialize, Deserialize)]
 #[ExistingTypePath = "crate::schema::sql_types::OwnerType"]
 pub enum OwnerType {
     AddressOwner,
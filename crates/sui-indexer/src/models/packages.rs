@@ -5,12 +5,13 @@ use crate::errors::IndexerError;
 use crate::schema::packages;
 
 use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use crate::models::objects::NamedBcsBytes;
 use sui_json_rpc_types::SuiRawMovePackage;
 use sui_types::base_types::SuiAddress;
 
-#[derive(Queryable, Insertable, Debug, Identifiable)]
+#[derive(Queryable, Insertable, Debug, Identifiable, Serialize, Deserialize)]
 #[diesel(table_name = packages, primary_key(package_id, version))]
 pub struct Package {
     pub package_id: String,
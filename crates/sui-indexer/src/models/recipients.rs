@@ -3,8 +3,9 @@
 
 use crate::schema::recipients;
 use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Queryable, Insertable, Debug, Clone, Default)]
+#[derive(Queryable, Insertable, Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Recipient {
     pub id: Option<i64>,
     pub transaction_digest: String,
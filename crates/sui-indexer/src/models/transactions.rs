@@ -7,6 +7,7 @@ use crate::utils::log_errors_to_pg;
 use chrono::NaiveDateTime;
 use diesel::prelude::*;
 use diesel::result::Error;
+use serde::{Deserialize, Serialize};
 use sui_json_rpc_types::{
     OwnedObjectRef, SuiObjectRef, SuiTransaction, SuiTransactionDataAPI, SuiTransactionEffects,
     SuiTransactionEffectsAPI, SuiTransactionResponse,
@@ -16,7 +17,7 @@ use crate::errors::IndexerError;
 use crate::schema::transactions::transaction_digest;
 use crate::PgPoolConnection;
 
-#[derive(Clone, Debug, Queryable, Insertable)]
+#[derive(Clone, Debug, PartialEq, Queryable, Insertable, Serialize, Deserialize)]
 #[diesel(table_name = transactions)]
 pub struct Transaction {
     #[diesel(deserialize_as = i64)]
@@ -24,6 +24,24 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    balance_changes (id) {
+        id -> Int8,
+        transaction_digest -> Varchar,
+        checkpoint_sequence_number -> Int8,
+        epoch -> Int8,
+        owner -> Varchar,
+        coin_type -> Text,
+        amount -> Text,
+    }
+}
+
+diesel::table! {
+    balance_changes_start (checkpoint_sequence_number) {
+        checkpoint_sequence_number -> Int8,
+    }
+}
+
 diesel::table! {
     checkpoints (sequence_number) {
         sequence_number -> Int8,
@@ -222,6 +240,8 @@ diesel::table! {
 
 diesel::allow_tables_to_appear_in_same_query!(
     addresses,
+    balance_changes,
+    balance_changes_start,
     checkpoints,
     error_logs,
     events,
@@ -3,6 +3,7 @@
 
 use crate::errors::IndexerError;
 use crate::models::addresses::Address;
+use crate::models::balance_changes::BalanceChange;
 use crate::models::checkpoints::Checkpoint;
 use crate::models::events::Event;
 use crate::models::move_calls::MoveCall;
@@ -20,6 +21,8 @@ use sui_types::event::EventID;
 use sui_types::object::ObjectRead;
 use sui_types::query::EventQuery;
 
+pub const MAX_EVENT_PAGE_SIZE: usize = 1000;
+
 #[async_trait]
 pub trait IndexerStore {
     fn get_latest_checkpoint_sequence_number(&self) -> Result<i64, IndexerError>;
@@ -114,8 +117,36 @@ pub trait IndexerStore {
         limit: usize,
     ) -> Result<Vec<Transaction>, IndexerError>;
 
-    fn persist_checkpoint(&self, data: &TemporaryCheckpointStore) -> Result<usize, IndexerError>;
-    fn persist_epoch(&self, data: &TemporaryEpochStore) -> Result<usize, IndexerError>;
+    /// Balance of `coin_type` owned by `owner` at the end of checkpoint `checkpoint_sequence_number`,
+    /// summed from the indexed balance changes.
+    fn get_balance_at_checkpoint(
+        &self,
+        owner: String,
+        coin_type: String,
+        checkpoint_sequence_number: i64,
+    ) -> Result<i128, IndexerError>;
+
+    /// The checkpoint balance changes are indexed from, without a gap up to the latest committed
+    /// checkpoint. `None` if the latest committed checkpoint was indexed without balance changes.
+    fn get_balance_changes_start_checkpoint(&self) -> Result<Option<i64>, IndexerError>;
+
+    fn get_balance_change_page(
+        &self,
+        owner: Option<String>,
+        coin_type: String,
+        start_sequence: Option<i64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<BalanceChange>, IndexerError>;
+
+    /// Commits all indexed data of a checkpoint, and of the epoch it ends if any, atomically.
+    /// Persisting an already committed checkpoint is a no-op, so indexing can resume from the
+    /// last committed checkpoint after a crash.
+    fn persist_checkpoint(
+        &self,
+        data: &TemporaryCheckpointStore,
+        epoch: Option<&TemporaryEpochStore>,
+    ) -> Result<usize, IndexerError>;
 
     fn log_errors(&self, errors: Vec<IndexerError>) -> Result<(), IndexerError>;
 }
@@ -136,6 +167,8 @@ pub struct TemporaryCheckpointStore {
     pub packages: Vec<Package>,
     pub move_calls: Vec<MoveCall>,
     pub recipients: Vec<Recipient>,
+    /// `None` if balance changes are not indexed.
+    pub balance_changes: Option<Vec<BalanceChange>>,
 }
 
 pub struct TransactionObjectChanges {
@@ -3,7 +3,9 @@
 
 pub use indexer_store::*;
 pub use pg_indexer_store::PgIndexerStore;
+pub use rocks_indexer_store::RocksIndexerStore;
 
 mod indexer_store;
 mod module_resolver;
 mod pg_indexer_store;
+mod rocks_indexer_store;
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::errors::IndexerError;
+use crate::models::balance_changes::BalanceChange;
 use crate::models::checkpoints::Checkpoint;
 use crate::models::error_logs::commit_error_logs;
 use crate::models::events::Event;
@@ -9,21 +10,22 @@ use crate::models::objects::Object;
 use crate::models::transactions::Transaction;
 
 use crate::schema::{
-    addresses, checkpoints, checkpoints::dsl as checkpoints_dsl, events, move_calls,
-    move_calls::dsl as move_calls_dsl, objects, objects::dsl as objects_dsl, objects_history,
-    packages, recipients, recipients::dsl as recipients_dsl, transactions,
-    transactions::dsl as transactions_dsl,
+    addresses, balance_changes, balance_changes::dsl as balance_changes_dsl, balance_changes_start,
+    balance_changes_start::dsl as balance_changes_start_dsl, checkpoints,
+    checkpoints::dsl as checkpoints_dsl, events, move_calls, move_calls::dsl as move_calls_dsl,
+    objects, objects::dsl as objects_dsl, objects_history, packages, recipients,
+    recipients::dsl as recipients_dsl, transactions, transactions::dsl as transactions_dsl,
 };
-use crate::store::indexer_store::TemporaryCheckpointStore;
+use crate::store::indexer_store::{TemporaryCheckpointStore, MAX_EVENT_PAGE_SIZE};
 use crate::store::module_resolver::IndexerModuleResolver;
 use crate::store::{IndexerStore, TemporaryEpochStore};
 use crate::{get_pg_pool_connection, PgConnectionPool};
 use async_trait::async_trait;
 use diesel::dsl::{count, max};
-use diesel::sql_types::VarChar;
+use diesel::sql_types::{BigInt, Text, VarChar};
 use diesel::upsert::excluded;
 use diesel::{ExpressionMethods, PgArrayExpressionMethods};
-use diesel::{OptionalExtension, QueryableByName};
+use diesel::{OptionalExtension, PgConnection, QueryResult, QueryableByName};
 use diesel::{QueryDsl, RunQueryDsl};
 use move_bytecode_utils::module_cache::SyncModuleCache;
 use std::collections::BTreeMap;
@@ -37,8 +39,6 @@ use tracing::{error, info};
 use sui_types::event::EventID;
 use sui_types::query::EventQuery;
 
-const MAX_EVENT_PAGE_SIZE: usize = 1000;
-
 const GET_PARTITION_SQL: &str = r#"
 SELECT parent.relname                           AS table_name,
        MAX(SUBSTRING(child.relname FROM '\d$')) AS last_partition
@@ -168,6 +168,11 @@ impl IndexerStore for PgIndexerStore {
                     .filter(events::dsl::event_time_ms.lt(end_time as i64));
             }
             EventQuery::EventType(_) => {}
+            EventQuery::And(_) | EventQuery::Or(_) | EventQuery::Not(_) => {
+                return Err(IndexerError::NotImplementedError(
+                    "Querying events by composed queries is not supported yet.".to_string(),
+                ));
+            }
             _ => {
                 return Err(IndexerError::NotImplementedError(
                     "Querying events by Recipient and Object is deprecated.".to_string(),
@@ -435,10 +440,12 @@ impl IndexerStore for PgIndexerStore {
             .read_only()
             .run(|conn| {
                 let mut boxed_query = transactions_dsl::transactions.into_boxed();
-                if is_descending {
-                    boxed_query = boxed_query.order(transactions_dsl::id.desc());
-                } else {
-                    boxed_query = boxed_query.order(transactions_dsl::id.asc());
+                if let Some(start_sequence) = start_sequence {
+                    if is_descending {
+                        boxed_query = boxed_query.filter(transactions_dsl::id.lt(start_sequence));
+                    } else {
+                        boxed_query = boxed_query.filter(transactions_dsl::id.gt(start_sequence));
+                    }
                 }
 
                 if is_descending {
@@ -674,7 +681,116 @@ impl IndexerStore for PgIndexerStore {
             })
     }
 
-    fn persist_checkpoint(&self, data: &TemporaryCheckpointStore) -> Result<usize, IndexerError> {
+    fn get_balance_at_checkpoint(
+        &self,
+        owner: String,
+        coin_type: String,
+        checkpoint_sequence_number: i64,
+    ) -> Result<i128, IndexerError> {
+        #[derive(QueryableByName, Debug, Clone)]
+        struct TempBalanceTable {
+            #[diesel(sql_type = Text)]
+            balance: String,
+        }
+
+        let mut pg_pool_conn = get_pg_pool_connection(&self.cp)?;
+        let balance = pg_pool_conn
+            .build_transaction()
+            .read_only()
+            .run(|conn| {
+                // Amounts are stored in decimal text, sum them as NUMERIC to not overflow.
+                diesel::sql_query(
+                    "SELECT CAST(COALESCE(SUM(CAST(amount AS NUMERIC)), 0) AS TEXT) AS balance
+                    FROM balance_changes
+                    WHERE owner = $1 AND coin_type = $2 AND checkpoint_sequence_number <= $3",
+                )
+                .bind::<VarChar, _>(&owner)
+                .bind::<Text, _>(&coin_type)
+                .bind::<BigInt, _>(checkpoint_sequence_number)
+                .get_result::<TempBalanceTable>(conn)
+            })
+            .map_err(|e| {
+                IndexerError::PostgresReadError(format!(
+                    "Failed reading balance of {} owned by {} at checkpoint {} and err: {:?}",
+                    coin_type, owner, checkpoint_sequence_number, e
+                ))
+            })?
+            .balance;
+        balance.parse().map_err(|e| {
+            IndexerError::PostgresReadError(format!(
+                "Failed parsing balance {} of {} owned by {} with err: {:?}",
+                balance, coin_type, owner, e
+            ))
+        })
+    }
+
+    fn get_balance_changes_start_checkpoint(&self) -> Result<Option<i64>, IndexerError> {
+        let mut pg_pool_conn = get_pg_pool_connection(&self.cp)?;
+        pg_pool_conn
+            .build_transaction()
+            .read_only()
+            .run(|conn| {
+                balance_changes_start_dsl::balance_changes_start
+                    .select(balance_changes_start::checkpoint_sequence_number)
+                    .first::<i64>(conn)
+                    .optional()
+            })
+            .map_err(|e| {
+                IndexerError::PostgresReadError(format!(
+                    "Failed reading balance changes start checkpoint with err: {:?}",
+                    e
+                ))
+            })
+    }
+
+    fn get_balance_change_page(
+        &self,
+        owner: Option<String>,
+        coin_type: String,
+        start_sequence: Option<i64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<BalanceChange>, IndexerError> {
+        let mut pg_pool_conn = get_pg_pool_connection(&self.cp)?;
+        pg_pool_conn
+            .build_transaction()
+            .read_only()
+            .run(|conn| {
+                let mut boxed_query = balance_changes_dsl::balance_changes
+                    .filter(balance_changes_dsl::coin_type.eq(&coin_type))
+                    .into_boxed();
+                if let Some(owner) = &owner {
+                    boxed_query = boxed_query.filter(balance_changes_dsl::owner.eq(owner));
+                }
+                if let Some(start_sequence) = start_sequence {
+                    if is_descending {
+                        boxed_query = boxed_query.filter(balance_changes_dsl::id.lt(start_sequence));
+                    } else {
+                        boxed_query = boxed_query.filter(balance_changes_dsl::id.gt(start_sequence));
+                    }
+                }
+                if is_descending {
+                    boxed_query = boxed_query.order(balance_changes_dsl::id.desc());
+                } else {
+                    boxed_query = boxed_query.order(balance_changes_dsl::id.asc());
+                }
+                boxed_query
+                    .limit(limit as i64)
+                    .load::<BalanceChange>(conn)
+            })
+            .map_err(|e| {
+                IndexerError::PostgresReadError(format!(
+                    "Failed reading balance changes of {} owned by {:?} with start_sequence {:?} and limit {} and err: {:?}",
+                    coin_type, owner, start_sequence, limit, e
+                ))
+            })
+    }
+
+    fn persist_checkpoint(
+        &self,
+        data: &TemporaryCheckpointStore,
+        epoch: Option<&TemporaryEpochStore>,
+    ) -> Result<usize, IndexerError> {
         let TemporaryCheckpointStore {
             checkpoint,
             transactions,
@@ -684,6 +800,7 @@ impl IndexerStore for PgIndexerStore {
             packages,
             move_calls,
             recipients, // TODO: store raw object
+            balance_changes,
         } = data;
 
         let mut pg_pool_conn = get_pg_pool_connection(&self.cp)?;
@@ -694,9 +811,14 @@ impl IndexerStore for PgIndexerStore {
             .serializable()
             .read_write()
             .run(|conn| {
-                diesel::insert_into(checkpoints::table)
-                    .values(checkpoint)
-                    .execute(conn)?;
+                // Checkpoint already committed by a previous run
+                let committed = checkpoints_dsl::checkpoints
+                    .filter(checkpoints::sequence_number.eq(checkpoint.sequence_number))
+                    .count()
+                    .get_result::<i64>(conn)?;
+                if committed > 0 {
+                    return Ok(0);
+                }
 
                 diesel::insert_into(transactions::table)
                     .values(transactions)
@@ -758,6 +880,42 @@ impl IndexerStore for PgIndexerStore {
 
                 diesel::insert_into(recipients::table)
                     .values(recipients)
+                    .execute(conn)?;
+
+                // Balance changes are indexed from this checkpoint on, unless they were already
+                // indexed for the previous one.
+                match balance_changes {
+                    Some(balance_changes) => {
+                        diesel::insert_into(balance_changes::table)
+                            .values(balance_changes)
+                            .execute(conn)?;
+                        let started = balance_changes_start_dsl::balance_changes_start
+                            .count()
+                            .get_result::<i64>(conn)?;
+                        if started == 0 {
+                            diesel::insert_into(balance_changes_start::table)
+                                .values(
+                                    balance_changes_start::checkpoint_sequence_number
+                                        .eq(checkpoint.sequence_number),
+                                )
+                                .execute(conn)?;
+                        }
+                    }
+                    None => {
+                        diesel::delete(balance_changes_start::table).execute(conn)?;
+                    }
+                }
+
+                if epoch.is_some() {
+                    // TODO: index epoch data
+                    self.partition_manager
+                        .advance_epoch(conn, checkpoint.epoch as EpochId + 1)?;
+                }
+
+                // The checkpoint row is written last, the latest checkpoint row marks where
+                // indexing resumes from.
+                diesel::insert_into(checkpoints::table)
+                    .values(checkpoint)
                     .execute(conn)
             })
             .map_err(|e| {
@@ -768,11 +926,6 @@ impl IndexerStore for PgIndexerStore {
             })
     }
 
-    fn persist_epoch(&self, _data: &TemporaryEpochStore) -> Result<usize, IndexerError> {
-        // TODO: create new partition on epoch change
-        self.partition_manager.advance_epoch(1)
-    }
-
     fn log_errors(&self, errors: Vec<IndexerError>) -> Result<(), IndexerError> {
         if !errors.is_empty() {
             let mut pg_pool_conn = get_pg_pool_connection(&self.cp)?;
@@ -806,19 +959,12 @@ impl PartitionManager {
         }
         Ok(manager)
     }
-    fn advance_epoch(&self, next_epoch_id: EpochId) -> Result<usize, IndexerError> {
-        let mut pg_pool_conn = get_pg_pool_connection(&self.cp)?;
-        pg_pool_conn
-            .build_transaction()
-            .read_write().serializable()
-            .run(|conn| {
-                for table in &self.tables {
-                    let sql = format!("CREATE TABLE {table}_partition_{next_epoch_id} PARTITION OF {table} FOR VALUES FROM ({next_epoch_id}) TO ({});", next_epoch_id+1);
-                    diesel::sql_query(sql).execute(conn)?;
-                }
-                Ok::<_, diesel::result::Error>(self.tables.len())
-            })
-            .map_err(|e| IndexerError::PostgresReadError(e.to_string()))
+    fn advance_epoch(&self, conn: &mut PgConnection, next_epoch_id: EpochId) -> QueryResult<usize> {
+        for table in &self.tables {
+            let sql = format!("CREATE TABLE IF NOT EXISTS {table}_partition_{next_epoch_id} PARTITION OF {table} FOR VALUES FROM ({next_epoch_id}) TO ({});", next_epoch_id+1);
+            diesel::sql_query(sql).execute(conn)?;
+        }
+        Ok(self.tables.len())
     }
 
     fn get_table_partitions(&self) -> Result<BTreeMap<String, String>, IndexerError> {
@@ -0,0 +1,1040 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::errors::IndexerError;
+use crate::models::addresses::Address;
+use crate::models::balance_changes::BalanceChange;
+use crate::models::checkpoints::Checkpoint;
+use crate::models::error_logs::ErrorLog;
+use crate::models::events::Event;
+use crate::models::move_calls::MoveCall;
+use crate::models::objects::{DeletedObject, NamedBcsBytes, Object};
+use crate::models::packages::Package;
+use crate::models::recipients::Recipient;
+use crate::models::transactions::Transaction;
+use crate::store::indexer_store::{TemporaryCheckpointStore, MAX_EVENT_PAGE_SIZE};
+use crate::store::{IndexerStore, TemporaryEpochStore};
+use async_trait::async_trait;
+use move_bytecode_utils::module_cache::SyncModuleCache;
+use move_core_types::language_storage::ModuleId;
+use move_core_types::resolver::ModuleResolver;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use sui_json_rpc_types::{CheckpointId, EventPage, SuiEventEnvelope};
+use sui_types::base_types::{ObjectID, SequenceNumber};
+use sui_types::event::EventID;
+use sui_types::object::ObjectRead;
+use sui_types::query::EventQuery;
+use tracing::error;
+use typed_store::rocks::{open_cf, DBMap, MetricConf, ReadWriteOptions, TypedStoreError};
+use typed_store::Map;
+
+/// Opens the database at `$path` with one column family per listed table.
+macro_rules! open_tables {
+    ($path:expr; $($table:ident),* $(,)?) => {{
+        let db = open_cf($path, None, MetricConf::default(), &[$(stringify!($table)),*])?;
+        IndexerTables {
+            $($table: DBMap::reopen(&db, Some(stringify!($table)), &ReadWriteOptions::default())?,)*
+        }
+    }};
+}
+
+/// Tables of the embedded indexer database. Rows are the same models as the Postgres tables,
+/// keyed by their primary key, and every column queried by the store has an index table keyed
+/// by `(column, row id)`.
+pub struct IndexerTables {
+    checkpoints: DBMap<i64, Checkpoint>,
+    checkpoints_by_digest: DBMap<String, i64>,
+
+    transactions: DBMap<i64, Transaction>,
+    transactions_by_digest: DBMap<String, i64>,
+    transactions_by_sender: DBMap<(String, i64), String>,
+    transactions_by_mutated_object: DBMap<(String, i64), String>,
+
+    events: DBMap<i64, Event>,
+    events_by_event_id: DBMap<(String, i64), i64>,
+    events_by_transaction: DBMap<(String, i64), ()>,
+    events_by_module: DBMap<((String, String), i64), ()>,
+    events_by_type: DBMap<(String, i64), ()>,
+    events_by_sender: DBMap<(String, i64), ()>,
+
+    objects: DBMap<String, Object>,
+    objects_history: DBMap<(String, i64), Object>,
+
+    addresses: DBMap<String, Address>,
+    packages: DBMap<(String, i64), Package>,
+
+    move_calls: DBMap<i64, MoveCall>,
+    move_calls_by_package: DBMap<(String, i64), MoveCall>,
+    move_calls_by_transaction: DBMap<(String, i64), ()>,
+
+    recipients: DBMap<i64, Recipient>,
+    recipients_by_address: DBMap<(String, i64), String>,
+    recipients_by_transaction: DBMap<(String, i64), ()>,
+
+    balance_changes: DBMap<i64, BalanceChange>,
+    balance_changes_by_owner: DBMap<((String, String), i64), BalanceChange>,
+    balance_changes_by_coin_type: DBMap<(String, i64), BalanceChange>,
+    /// The checkpoint balance changes are indexed from up to the latest checkpoint, if any.
+    balance_changes_start: DBMap<(), i64>,
+
+    error_logs: DBMap<i64, ErrorLog>,
+}
+
+/// An `IndexerStore` backed by an embedded RocksDB database, for running the indexer without a
+/// Postgres server.
+#[derive(Clone)]
+pub struct RocksIndexerStore {
+    tables: Arc<IndexerTables>,
+    // Row ids are allocated from the last id of each table, writers must not interleave.
+    write_lock: Arc<Mutex<()>>,
+    pub module_cache: Arc<SyncModuleCache<RocksModuleResolver>>,
+}
+
+impl IndexerTables {
+    fn open(path: &Path) -> Result<Self, TypedStoreError> {
+        Ok(open_tables!(path;
+            checkpoints,
+            checkpoints_by_digest,
+            transactions,
+            transactions_by_digest,
+            transactions_by_sender,
+            transactions_by_mutated_object,
+            events,
+            events_by_event_id,
+            events_by_transaction,
+            events_by_module,
+            events_by_type,
+            events_by_sender,
+            objects,
+            objects_history,
+            addresses,
+            packages,
+            move_calls,
+            move_calls_by_package,
+            move_calls_by_transaction,
+            recipients,
+            recipients_by_address,
+            recipients_by_transaction,
+            balance_changes,
+            balance_changes_by_owner,
+            balance_changes_by_coin_type,
+            balance_changes_start,
+            error_logs,
+        ))
+    }
+}
+
+impl RocksIndexerStore {
+    pub fn open(path: &Path) -> Result<Self, IndexerError> {
+        let tables = Arc::new(IndexerTables::open(path).map_err(|e| {
+            IndexerError::RocksDbReadError(format!(
+                "Failed opening RocksDB at {} with error: {:?}",
+                path.display(),
+                e
+            ))
+        })?);
+        let module_cache = Arc::new(SyncModuleCache::new(RocksModuleResolver {
+            tables: tables.clone(),
+        }));
+        Ok(Self {
+            tables,
+            write_lock: Arc::new(Mutex::new(())),
+            module_cache,
+        })
+    }
+
+    /// Reads the events with the given ids, in the order of `ids`.
+    fn multi_get_events(&self, ids: impl Iterator<Item = i64>) -> Result<Vec<Event>, IndexerError> {
+        let ids: Vec<i64> = ids.collect();
+        Ok(self
+            .tables
+            .events
+            .multi_get(&ids)?
+            .into_iter()
+            .flatten()
+            .collect())
+    }
+}
+
+#[async_trait]
+impl IndexerStore for RocksIndexerStore {
+    fn get_latest_checkpoint_sequence_number(&self) -> Result<i64, IndexerError> {
+        // -1 to differentiate between no checkpoints and the first checkpoint
+        Ok(next_id(&self.tables.checkpoints) - 1)
+    }
+
+    fn get_checkpoint(&self, id: CheckpointId) -> Result<Checkpoint, IndexerError> {
+        let sequence_number = match &id {
+            CheckpointId::SequenceNumber(seq) => Some(*seq as i64),
+            CheckpointId::Digest(digest) => self
+                .tables
+                .checkpoints_by_digest
+                .get(&digest.base58_encode())?,
+        };
+        sequence_number
+            .map(|seq| self.tables.checkpoints.get(&seq))
+            .transpose()?
+            .flatten()
+            .ok_or_else(|| IndexerError::RocksDbReadError(format!("Checkpoint {:?} not found", id)))
+    }
+
+    fn get_event(&self, id: EventID) -> Result<Event, IndexerError> {
+        self.tables
+            .events_by_event_id
+            .get(&(id.tx_digest.base58_encode(), id.event_seq))?
+            .map(|event_id| self.tables.events.get(&event_id))
+            .transpose()?
+            .flatten()
+            .ok_or_else(|| IndexerError::RocksDbReadError(format!("Event {:?} not found", id)))
+    }
+
+    fn get_events(
+        &self,
+        query: EventQuery,
+        cursor: Option<EventID>,
+        limit: Option<usize>,
+        descending_order: bool,
+    ) -> Result<EventPage, IndexerError> {
+        let page_limit = limit.unwrap_or(MAX_EVENT_PAGE_SIZE);
+        if page_limit > MAX_EVENT_PAGE_SIZE {
+            Err(IndexerError::InvalidArgumentError(format!(
+                "Limit {} exceeds the maximum page size {}",
+                page_limit, MAX_EVENT_PAGE_SIZE
+            )))?;
+        }
+
+        let cursor = cursor
+            .map(|c| {
+                self.get_event(c)?
+                    .id
+                    .ok_or_else(|| IndexerError::RocksDbReadError("Event ID is None".to_string()))
+            })
+            .transpose()?;
+        // fetch one more item to tell if there is next page
+        let fetch_limit = page_limit + 1;
+        let events = match query {
+            EventQuery::All | EventQuery::EventType(_) => {
+                iter_from(&self.tables.events, cursor, descending_order)?
+                    .take(fetch_limit)
+                    .map(|(_, event)| event)
+                    .collect()
+            }
+            EventQuery::Transaction(digest) => self.multi_get_events(
+                read_index(
+                    &self.tables.events_by_transaction,
+                    &digest.base58_encode(),
+                    cursor,
+                    descending_order,
+                )?
+                .take(fetch_limit)
+                .map(|(id, _)| id),
+            )?,
+            EventQuery::MoveModule { package, module } => self.multi_get_events(
+                read_index(
+                    &self.tables.events_by_module,
+                    &(package.to_string(), module),
+                    cursor,
+                    descending_order,
+                )?
+                .take(fetch_limit)
+                .map(|(id, _)| id),
+            )?,
+            EventQuery::MoveEvent(struct_name) => self.multi_get_events(
+                read_index(
+                    &self.tables.events_by_type,
+                    &struct_name,
+                    cursor,
+                    descending_order,
+                )?
+                .take(fetch_limit)
+                .map(|(id, _)| id),
+            )?,
+            EventQuery::Sender(sender) => self.multi_get_events(
+                read_index(
+                    &self.tables.events_by_sender,
+                    &sender.to_string(),
+                    cursor,
+                    descending_order,
+                )?
+                .take(fetch_limit)
+                .map(|(id, _)| id),
+            )?,
+            EventQuery::TimeRange {
+                start_time,
+                end_time,
+            } => {
+                // Event ids follow checkpoint order, so event times do not decrease with ids and
+                // the scan stops at the first event past the range.
+                let (start_time, end_time) = (start_time as i64, end_time as i64);
+                let before_range = |time: i64| {
+                    if descending_order {
+                        time >= end_time
+                    } else {
+                        time < start_time
+                    }
+                };
+                iter_from(&self.tables.events, cursor, descending_order)?
+                    .map(|(_, event)| event)
+                    .filter(|event| event.event_time_ms.is_some())
+                    .skip_while(|event| before_range(event.event_time_ms.unwrap_or_default()))
+                    .take_while(|event| {
+                        let time = event.event_time_ms.unwrap_or_default();
+                        time >= start_time && time < end_time
+                    })
+                    .take(fetch_limit)
+                    .collect()
+            }
+            EventQuery::And(_) | EventQuery::Or(_) | EventQuery::Not(_) => {
+                return Err(IndexerError::NotImplementedError(
+                    "Querying events by composed queries is not supported yet.".to_string(),
+                ));
+            }
+            _ => {
+                return Err(IndexerError::NotImplementedError(
+                    "Querying events by Recipient and Object is deprecated.".to_string(),
+                ));
+            }
+        };
+        let mut event_envelope_vec = events
+            .into_iter()
+            .map(|event: Event| event.try_into())
+            .collect::<Result<Vec<SuiEventEnvelope>, _>>()?;
+
+        let has_next_page = event_envelope_vec.len() > page_limit;
+        event_envelope_vec.truncate(page_limit);
+        let next_cursor = event_envelope_vec.last().map(|e| e.id.clone());
+        Ok(EventPage {
+            data: event_envelope_vec,
+            next_cursor,
+            has_next_page,
+        })
+    }
+
+    fn get_object(
+        &self,
+        object_id: ObjectID,
+        version: Option<SequenceNumber>,
+    ) -> Result<ObjectRead, IndexerError> {
+        let object = if let Some(version) = version {
+            self.tables
+                .objects_history
+                .get(&(object_id.to_string(), version.value() as i64))?
+        } else {
+            self.tables.objects.get(&object_id.to_string())?
+        };
+
+        match object {
+            None => Ok(ObjectRead::NotExists(object_id)),
+            Some(o) => o.try_into_object_read(&self.module_cache),
+        }
+    }
+
+    fn get_total_transaction_number(&self) -> Result<i64, IndexerError> {
+        // Transaction ids are dense and start from 0.
+        Ok(next_id(&self.tables.transactions))
+    }
+
+    fn get_transaction_by_digest(&self, txn_digest: &str) -> Result<Transaction, IndexerError> {
+        self.tables
+            .transactions_by_digest
+            .get(&txn_digest.to_string())?
+            .map(|id| self.tables.transactions.get(&id))
+            .transpose()?
+            .flatten()
+            .ok_or_else(|| {
+                IndexerError::RocksDbReadError(format!(
+                    "Transaction with digest {} not found",
+                    txn_digest
+                ))
+            })
+    }
+
+    fn multi_get_transactions_by_digests(
+        &self,
+        txn_digests: &[String],
+    ) -> Result<Vec<Transaction>, IndexerError> {
+        let ids: Vec<i64> = self
+            .tables
+            .transactions_by_digest
+            .multi_get(txn_digests)?
+            .into_iter()
+            .flatten()
+            .collect();
+        Ok(self
+            .tables
+            .transactions
+            .multi_get(&ids)?
+            .into_iter()
+            .flatten()
+            .collect())
+    }
+
+    fn get_all_transaction_digest_page(
+        &self,
+        start_sequence: Option<i64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<String>, IndexerError> {
+        Ok(
+            iter_from(&self.tables.transactions, start_sequence, is_descending)?
+                .take(limit + 1)
+                .map(|(_, transaction)| transaction.transaction_digest)
+                .collect(),
+        )
+    }
+
+    fn get_transaction_digest_page_by_mutated_object(
+        &self,
+        object_id: String,
+        start_sequence: Option<i64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<String>, IndexerError> {
+        Ok(read_index(
+            &self.tables.transactions_by_mutated_object,
+            &object_id,
+            start_sequence,
+            is_descending,
+        )?
+        .take(limit)
+        .map(|(_, digest)| digest)
+        .collect())
+    }
+
+    fn get_transaction_digest_page_by_sender_address(
+        &self,
+        sender_address: String,
+        start_sequence: Option<i64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<String>, IndexerError> {
+        Ok(read_index(
+            &self.tables.transactions_by_sender,
+            &sender_address,
+            start_sequence,
+            is_descending,
+        )?
+        .take(limit)
+        .map(|(_, digest)| digest)
+        .collect())
+    }
+
+    fn get_transaction_digest_page_by_recipient_address(
+        &self,
+        recipient_address: String,
+        start_sequence: Option<i64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<String>, IndexerError> {
+        // Recipients of one transaction have consecutive ids, keeping the first row of each
+        // transaction orders them the same way as grouping by transaction digest does.
+        let mut seen = HashSet::new();
+        Ok(read_index(
+            &self.tables.recipients_by_address,
+            &recipient_address,
+            start_sequence,
+            is_descending,
+        )?
+        .map(|(_, digest)| digest)
+        .filter(|digest| seen.insert(digest.clone()))
+        .take(limit)
+        .collect())
+    }
+
+    fn get_transaction_digest_page_by_move_call(
+        &self,
+        package: String,
+        module: Option<String>,
+        function: Option<String>,
+        start_sequence: Option<i64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<String>, IndexerError> {
+        let mut seen = HashSet::new();
+        Ok(read_index(
+            &self.tables.move_calls_by_package,
+            &package,
+            start_sequence,
+            is_descending,
+        )?
+        .map(|(_, move_call)| move_call)
+        .filter(|move_call| {
+            module
+                .as_ref()
+                .map_or(true, |m| *m == move_call.move_module)
+                && function
+                    .as_ref()
+                    .map_or(true, |f| *f == move_call.move_function)
+        })
+        .map(|move_call| move_call.transaction_digest)
+        .filter(|digest| seen.insert(digest.clone()))
+        .take(limit)
+        .collect())
+    }
+
+    fn get_transaction_sequence_by_digest(
+        &self,
+        txn_digest: Option<String>,
+        _is_descending: bool,
+    ) -> Result<Option<i64>, IndexerError> {
+        txn_digest
+            .map(|digest| {
+                self.tables
+                    .transactions_by_digest
+                    .get(&digest)?
+                    .ok_or_else(|| {
+                        IndexerError::RocksDbReadError(format!(
+                            "Transaction sequence with digest {} not found",
+                            digest
+                        ))
+                    })
+            })
+            .transpose()
+    }
+
+    fn get_move_call_sequence_by_digest(
+        &self,
+        txn_digest: Option<String>,
+        is_descending: bool,
+    ) -> Result<Option<i64>, IndexerError> {
+        txn_digest
+            .map(|digest| {
+                read_index(
+                    &self.tables.move_calls_by_transaction,
+                    &digest,
+                    None,
+                    is_descending,
+                )?
+                .next()
+                .map(|(id, _)| id)
+                .ok_or_else(|| {
+                    IndexerError::RocksDbReadError(format!(
+                        "Move call sequence with digest {} not found",
+                        digest
+                    ))
+                })
+            })
+            .transpose()
+    }
+
+    fn get_recipient_sequence_by_digest(
+        &self,
+        txn_digest: Option<String>,
+        is_descending: bool,
+    ) -> Result<Option<i64>, IndexerError> {
+        txn_digest
+            .map(|digest| {
+                read_index(
+                    &self.tables.recipients_by_transaction,
+                    &digest,
+                    None,
+                    is_descending,
+                )?
+                .next()
+                .map(|(id, _)| id)
+                .ok_or_else(|| {
+                    IndexerError::RocksDbReadError(format!(
+                        "Recipients sequence with digest {} not found",
+                        digest
+                    ))
+                })
+            })
+            .transpose()
+    }
+
+    fn read_transactions(
+        &self,
+        last_processed_id: i64,
+        limit: usize,
+    ) -> Result<Vec<Transaction>, IndexerError> {
+        Ok(
+            iter_from(&self.tables.transactions, Some(last_processed_id), false)?
+                .take(limit)
+                .map(|(_, transaction)| transaction)
+                .collect(),
+        )
+    }
+
+    fn get_balance_at_checkpoint(
+        &self,
+        owner: String,
+        coin_type: String,
+        checkpoint_sequence_number: i64,
+    ) -> Result<i128, IndexerError> {
+        let key = (owner, coin_type);
+        // Row ids grow with checkpoints, so the changes up to the checkpoint are a prefix.
+        read_index(&self.tables.balance_changes_by_owner, &key, None, false)?
+            .map(|(_, change)| change)
+            .take_while(|change| change.checkpoint_sequence_number <= checkpoint_sequence_number)
+            .try_fold(0i128, |balance, change| {
+                let amount = change.amount.parse::<i128>().map_err(|e| {
+                    IndexerError::RocksDbReadError(format!(
+                        "Failed parsing balance change amount {} with err: {:?}",
+                        change.amount, e
+                    ))
+                })?;
+                Ok(balance + amount)
+            })
+    }
+
+    fn get_balance_changes_start_checkpoint(&self) -> Result<Option<i64>, IndexerError> {
+        Ok(self.tables.balance_changes_start.get(&())?)
+    }
+
+    fn get_balance_change_page(
+        &self,
+        owner: Option<String>,
+        coin_type: String,
+        start_sequence: Option<i64>,
+        limit: usize,
+        is_descending: bool,
+    ) -> Result<Vec<BalanceChange>, IndexerError> {
+        let key = owner.map(|owner| (owner, coin_type.clone()));
+        let changes = match &key {
+            Some(key) => read_index(
+                &self.tables.balance_changes_by_owner,
+                key,
+                start_sequence,
+                is_descending,
+            )?,
+            None => read_index(
+                &self.tables.balance_changes_by_coin_type,
+                &coin_type,
+                start_sequence,
+                is_descending,
+            )?,
+        };
+        Ok(changes.take(limit).map(|(_, change)| change).collect())
+    }
+
+    fn persist_checkpoint(
+        &self,
+        data: &TemporaryCheckpointStore,
+        // Tables are not partitioned by epoch, there is nothing to do on epoch change.
+        _epoch: Option<&TemporaryEpochStore>,
+    ) -> Result<usize, IndexerError> {
+        let TemporaryCheckpointStore {
+            checkpoint,
+            transactions,
+            events,
+            objects_changes,
+            addresses,
+            packages,
+            move_calls,
+            recipients,
+            balance_changes,
+        } = data;
+
+        let _guard = self.write_lock.lock().unwrap();
+        let tables = &self.tables;
+
+        // Checkpoint already committed by a previous run
+        if tables
+            .checkpoints
+            .contains_key(&checkpoint.sequence_number)?
+        {
+            return Ok(0);
+        }
+
+        let next_transaction_id = next_id(&tables.transactions);
+        let transactions: Vec<_> = (next_transaction_id..)
+            .zip(transactions)
+            .map(|(id, transaction)| Transaction {
+                id: Some(id),
+                ..transaction.clone()
+            })
+            .collect();
+
+        let next_event_id = next_id(&tables.events);
+        let events: Vec<_> = (next_event_id..)
+            .zip(events)
+            .map(|(id, event)| Event {
+                id: Some(id),
+                ..event.clone()
+            })
+            .collect();
+
+        let next_move_call_id = next_id(&tables.move_calls);
+        let move_calls: Vec<_> = (next_move_call_id..)
+            .zip(move_calls)
+            .map(|(id, move_call)| MoveCall {
+                id: Some(id),
+                ..move_call.clone()
+            })
+            .collect();
+
+        let next_recipient_id = next_id(&tables.recipients);
+        let recipients: Vec<_> = (next_recipient_id..)
+            .zip(recipients)
+            .map(|(id, recipient)| Recipient {
+                id: Some(id),
+                ..recipient.clone()
+            })
+            .collect();
+
+        // Balance changes are indexed from this checkpoint on, unless they were already indexed
+        // for the previous one.
+        let balance_changes_start = match balance_changes {
+            Some(_) => Some(
+                tables
+                    .balance_changes_start
+                    .get(&())?
+                    .unwrap_or(checkpoint.sequence_number),
+            ),
+            None => None,
+        };
+        let next_balance_change_id = next_id(&tables.balance_changes);
+        let balance_changes: Vec<_> = (next_balance_change_id..)
+            .zip(balance_changes.iter().flatten())
+            .map(|(id, balance_change)| BalanceChange {
+                id: Some(id),
+                ..balance_change.clone()
+            })
+            .collect();
+
+        // Commit indexed checkpoint in one write batch
+        let write = || -> Result<(), TypedStoreError> {
+            // Apply object changes transaction by transaction, later changes to the same object
+            // in this checkpoint win, and every change is kept in the object history.
+            let mut objects = BTreeMap::new();
+            let mut objects_history = vec![];
+            for changes in objects_changes {
+                for object in &changes.mutated_objects {
+                    objects_history.push(object.clone());
+                    objects.insert(object.object_id.clone(), object.clone());
+                }
+                for deleted in &changes.deleted_objects {
+                    let object = match objects.remove(&deleted.object_id) {
+                        Some(object) => Some(object),
+                        None => tables.objects.get(&deleted.object_id)?,
+                    };
+                    let object = apply_deletion(object, deleted);
+                    objects_history.push(object.clone());
+                    objects.insert(object.object_id.clone(), object);
+                }
+            }
+
+            // Only insert once for address, skip if it already exists
+            let mut new_addresses = BTreeMap::new();
+            for address in addresses {
+                if !new_addresses.contains_key(&address.account_address)
+                    && !tables.addresses.contains_key(&address.account_address)?
+                {
+                    new_addresses.insert(address.account_address.clone(), address);
+                }
+            }
+
+            tables
+                .checkpoints
+                .batch()
+                .insert_batch(
+                    &tables.checkpoints,
+                    [(checkpoint.sequence_number, checkpoint)],
+                )?
+                .insert_batch(
+                    &tables.checkpoints_by_digest,
+                    [(
+                        checkpoint.checkpoint_digest.clone(),
+                        checkpoint.sequence_number,
+                    )],
+                )?
+                .insert_batch(
+                    &tables.transactions,
+                    transactions.iter().map(|t| (t.id.unwrap(), t)),
+                )?
+                .insert_batch(
+                    &tables.transactions_by_digest,
+                    transactions
+                        .iter()
+                        .map(|t| (t.transaction_digest.clone(), t.id.unwrap())),
+                )?
+                .insert_batch(
+                    &tables.transactions_by_sender,
+                    transactions.iter().map(|t| {
+                        (
+                            (t.sender.clone(), t.id.unwrap()),
+                            t.transaction_digest.clone(),
+                        )
+                    }),
+                )?
+                .insert_batch(
+                    &tables.transactions_by_mutated_object,
+                    transactions.iter().flat_map(|t| {
+                        t.mutated.iter().flatten().map(|object_id| {
+                            (
+                                (object_id.clone(), t.id.unwrap()),
+                                t.transaction_digest.clone(),
+                            )
+                        })
+                    }),
+                )?
+                .insert_batch(&tables.events, events.iter().map(|e| (e.id.unwrap(), e)))?
+                .insert_batch(
+                    &tables.events_by_event_id,
+                    events.iter().map(|e| {
+                        (
+                            (e.transaction_digest.clone(), e.event_sequence),
+                            e.id.unwrap(),
+                        )
+                    }),
+                )?
+                .insert_batch(
+                    &tables.events_by_transaction,
+                    events
+                        .iter()
+                        .map(|e| ((e.transaction_digest.clone(), e.id.unwrap()), ())),
+                )?
+                .insert_batch(
+                    &tables.events_by_module,
+                    events
+                        .iter()
+                        .map(|e| (((e.package.clone(), e.module.clone()), e.id.unwrap()), ())),
+                )?
+                .insert_batch(
+                    &tables.events_by_type,
+                    events
+                        .iter()
+                        .map(|e| ((e.event_type.clone(), e.id.unwrap()), ())),
+                )?
+                .insert_batch(
+                    &tables.events_by_sender,
+                    events
+                        .iter()
+                        .map(|e| ((e.sender.clone(), e.id.unwrap()), ())),
+                )?
+                .insert_batch(&tables.objects, objects)?
+                .insert_batch(
+                    &tables.objects_history,
+                    objects_history
+                        .iter()
+                        .map(|o| ((o.object_id.clone(), o.version), o)),
+                )?
+                .insert_batch(&tables.addresses, new_addresses)?
+                // Package with the same version number will not change, overwriting is a no-op.
+                .insert_batch(
+                    &tables.packages,
+                    packages
+                        .iter()
+                        .map(|p| ((p.package_id.clone(), p.version), p)),
+                )?
+                .insert_batch(
+                    &tables.move_calls,
+                    move_calls.iter().map(|m| (m.id.unwrap(), m)),
+                )?
+                .insert_batch(
+                    &tables.move_calls_by_package,
+                    move_calls
+                        .iter()
+                        .map(|m| ((m.move_package.clone(), m.id.unwrap()), m)),
+                )?
+                .insert_batch(
+                    &tables.move_calls_by_transaction,
+                    move_calls
+                        .iter()
+                        .map(|m| ((m.transaction_digest.clone(), m.id.unwrap()), ())),
+                )?
+                .insert_batch(
+                    &tables.recipients,
+                    recipients.iter().map(|r| (r.id.unwrap(), r)),
+                )?
+                .insert_batch(
+                    &tables.recipients_by_address,
+                    recipients.iter().map(|r| {
+                        (
+                            (r.recipient.clone(), r.id.unwrap()),
+                            r.transaction_digest.clone(),
+                        )
+                    }),
+                )?
+                .insert_batch(
+                    &tables.recipients_by_transaction,
+                    recipients
+                        .iter()
+                        .map(|r| ((r.transaction_digest.clone(), r.id.unwrap()), ())),
+                )?
+                .insert_batch(
+                    &tables.balance_changes,
+                    balance_changes.iter().map(|b| (b.id.unwrap(), b)),
+                )?
+                .insert_batch(
+                    &tables.balance_changes_by_owner,
+                    balance_changes
+                        .iter()
+                        .map(|b| (((b.owner.clone(), b.coin_type.clone()), b.id.unwrap()), b)),
+                )?
+                .insert_batch(
+                    &tables.balance_changes_by_coin_type,
+                    balance_changes
+                        .iter()
+                        .map(|b| ((b.coin_type.clone(), b.id.unwrap()), b)),
+                )?
+                .insert_batch(
+                    &tables.balance_changes_start,
+                    balance_changes_start.map(|start| ((), start)),
+                )?
+                .delete_batch(
+                    &tables.balance_changes_start,
+                    balance_changes_start.is_none().then_some(()),
+                )?
+                .write()
+        };
+        write().map_err(|e| {
+            IndexerError::RocksDbWriteError(format!(
+                "Failed writing checkpoint {} to RocksDB with error: {:?}",
+                checkpoint.sequence_number, e
+            ))
+        })?;
+        Ok(transactions.len())
+    }
+
+    fn log_errors(&self, errors: Vec<IndexerError>) -> Result<(), IndexerError> {
+        if !errors.is_empty() {
+            let _guard = self.write_lock.lock().unwrap();
+            let next_error_id = next_id(&self.tables.error_logs);
+            let new_error_logs = (next_error_id..).zip(errors).map(|(id, e)| {
+                let error_log: ErrorLog = e.into();
+                (
+                    id,
+                    ErrorLog {
+                        id: Some(id),
+                        ..error_log
+                    },
+                )
+            });
+            if let Err(e) = self.tables.error_logs.multi_insert(new_error_logs) {
+                error!("Failed writing error logs with error {:?}", e);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Resolves modules from the latest version of their package in the embedded database.
+pub struct RocksModuleResolver {
+    tables: Arc<IndexerTables>,
+}
+
+impl ModuleResolver for RocksModuleResolver {
+    type Error = IndexerError;
+
+    fn get_module(&self, id: &ModuleId) -> Result<Option<Vec<u8>>, Self::Error> {
+        let package_id = ObjectID::from(*id.address()).to_string();
+        let module_name = id.name().to_string();
+
+        let package = self
+            .tables
+            .packages
+            .iter()
+            .skip_prior_to(&(package_id.clone(), i64::MAX))?
+            .next()
+            .filter(|((package, _), _)| *package == package_id);
+        Ok(package.and_then(|(_, package)| {
+            package
+                .data
+                .into_iter()
+                .find(|NamedBcsBytes(name, _)| *name == module_name)
+                .map(|NamedBcsBytes(_, bytes)| bytes)
+        }))
+    }
+}
+
+/// The id following the last row of `table`, or 0 if the table is empty.
+fn next_id<V: Serialize + DeserializeOwned>(table: &DBMap<i64, V>) -> i64 {
+    table
+        .iter()
+        .skip_to_last()
+        .next()
+        .map(|(id, _)| id + 1)
+        .unwrap_or(0)
+}
+
+/// Iterates the rows of `table` with ids strictly after `start_sequence`, in ascending or
+/// descending id order.
+fn iter_from<V: Serialize + DeserializeOwned>(
+    table: &DBMap<i64, V>,
+    start_sequence: Option<i64>,
+    is_descending: bool,
+) -> Result<Box<dyn Iterator<Item = (i64, V)> + '_>, IndexerError> {
+    Ok(if is_descending {
+        match start_sequence {
+            Some(start) if start <= 0 => Box::new(std::iter::empty()),
+            Some(start) => Box::new(table.iter().skip_prior_to(&(start - 1))?.reverse()),
+            None => Box::new(table.iter().skip_to_last().reverse()),
+        }
+    } else {
+        Box::new(
+            table
+                .iter()
+                .skip_to(&start_sequence.map_or(0, |start| start + 1))?,
+        )
+    })
+}
+
+/// Iterates the entries of the `(key, id)` index `index` under `key`, with ids strictly after
+/// `start_sequence` in ascending or descending id order.
+fn read_index<'a, K, V>(
+    index: &'a DBMap<(K, i64), V>,
+    key: &'a K,
+    start_sequence: Option<i64>,
+    is_descending: bool,
+) -> Result<Box<dyn Iterator<Item = (i64, V)> + 'a>, IndexerError>
+where
+    K: Clone + PartialEq + Serialize + DeserializeOwned,
+    V: Serialize + DeserializeOwned,
+{
+    let iter: Box<dyn Iterator<Item = ((K, i64), V)> + 'a> = if is_descending {
+        match start_sequence {
+            Some(start) if start <= 0 => Box::new(std::iter::empty()),
+            start => Box::new(
+                index
+                    .iter()
+                    .skip_prior_to(&(key.clone(), start.map_or(i64::MAX, |start| start - 1)))?
+                    .reverse(),
+            ),
+        }
+    } else {
+        Box::new(
+            index
+                .iter()
+                .skip_to(&(key.clone(), start_sequence.map_or(0, |start| start + 1)))?,
+        )
+    };
+    Ok(Box::new(
+        iter.take_while(move |((k, _), _)| k == key)
+            .map(|((_, id), value)| (id, value)),
+    ))
+}
+
+/// Updates the columns a deletion changes, mirroring the upsert of `DeletedObject` in Postgres.
+fn apply_deletion(object: Option<Object>, deleted: &DeletedObject) -> Object {
+    match object {
+        Some(object) => Object {
+            epoch: deleted.epoch,
+            checkpoint: deleted.checkpoint,
+            version: deleted.version,
+            previous_transaction: deleted.previous_transaction.clone(),
+            object_status: deleted.object_status,
+            ..object
+        },
+        None => Object {
+            epoch: deleted.epoch,
+            checkpoint: deleted.checkpoint,
+            object_id: deleted.object_id.clone(),
+            version: deleted.version,
+            object_digest: deleted.object_digest.clone(),
+            owner_type: deleted.owner_type.clone(),
+            owner_address: None,
+            initial_shared_version: None,
+            previous_transaction: deleted.previous_transaction.clone(),
+            object_type: deleted.object_type.clone(),
+            object_status: deleted.object_status,
+            has_public_transfer: deleted.has_public_transfer,
+            storage_rebate: 0,
+            bcs: vec![],
+        },
+    }
+}
@@ -4,7 +4,9 @@
 use prometheus::Registry;
 use std::sync::{Arc, RwLock};
 use std::time::Duration;
+use sui_config::utils::available_local_socket_address;
 use sui_indexer::errors::IndexerError;
+use sui_indexer::models::balance_changes::BalanceChange;
 use sui_indexer::models::checkpoints::Checkpoint;
 use sui_indexer::models::objects::Object;
 use sui_indexer::models::transactions::Transaction;
@@ -22,7 +24,13 @@ async fn test_genesis() {
 
     let s = store.clone();
     let _handle = tokio::task::spawn(async move {
-        Indexer::start(test_cluster.rpc_url(), &Registry::default(), s).await
+        Indexer::start(
+            test_cluster.rpc_url(),
+            available_local_socket_address(),
+            &Registry::default(),
+            s,
+        )
+        .await
     });
 
     // Allow indexer to process the data
@@ -184,6 +192,30 @@ impl IndexerStore for InMemoryIndexerStore {
         todo!()
     }
 
+    fn get_balance_at_checkpoint(
+        &self,
+        _owner: String,
+        _coin_type: String,
+        _checkpoint_sequence_number: i64,
+    ) -> Result<i128, IndexerError> {
+        todo!()
+    }
+
+    fn get_balance_changes_start_checkpoint(&self) -> Result<Option<i64>, IndexerError> {
+        todo!()
+    }
+
+    fn get_balance_change_page(
+        &self,
+        _owner: Option<String>,
+        _coin_type: String,
+        _start_sequence: Option<i64>,
+        _limit: usize,
+        _is_descending: bool,
+    ) -> Result<Vec<BalanceChange>, IndexerError> {
+        todo!()
+    }
+
     fn read_transactions(
         &self,
         _last_processed_id: i64,
@@ -200,7 +232,11 @@ impl IndexerStore for InMemoryIndexerStore {
         todo!();
     }
 
-    fn persist_checkpoint(&self, data: &TemporaryCheckpointStore) -> Result<usize, IndexerError> {
+    fn persist_checkpoint(
+        &self,
+        data: &TemporaryCheckpointStore,
+        _epoch: Option<&TemporaryEpochStore>,
+    ) -> Result<usize, IndexerError> {
         let TemporaryCheckpointStore {
             objects_changes,
             checkpoint,
@@ -215,10 +251,6 @@ impl IndexerStore for InMemoryIndexerStore {
         Ok(0)
     }
 
-    fn persist_epoch(&self, _data: &TemporaryEpochStore) -> Result<usize, IndexerError> {
-        todo!()
-    }
-
     fn log_errors(&self, _errors: Vec<IndexerError>) -> Result<(), IndexerError> {
         todo!()
     }
@@ -43,3 +43,13 @@ pub struct Page<T, C> {
     pub next_cursor: Option<C>,
     pub has_next_page: bool,
 }
+
+/// An item of a subscription stream. Data items are sent as is, while a `lagged` notification
+/// tells the subscriber that it fell behind and the given number of items were dropped, in which
+/// case it should resync using the corresponding query API.
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum SubscriptionItem<T> {
+    Data(T),
+    Lagged { lagged: u64 },
+}
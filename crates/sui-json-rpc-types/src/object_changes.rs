@@ -7,8 +7,16 @@ use serde_with::DisplayFromStr;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
-use sui_types::base_types::{ObjectDigest, ObjectID, SequenceNumber, SuiAddress};
+use sui_types::base_types::{
+    ObjectDigest, ObjectID, SequenceNumber, SuiAddress, TransactionDigest,
+};
+use sui_types::filter::Filter;
 use sui_types::object::Owner;
+use sui_types::query::ObjectChangeFilter;
+
+use crate::{SubscriptionItem, SuiObjectRef};
+
+pub type ObjectChangeSubscriptionItem = SubscriptionItem<SuiObjectChangeEnvelope>;
 
 /// ObjectChange are derived from the object mutations in the TransactionEffect to provide richer object information.
 #[serde_as]
@@ -74,3 +82,72 @@ pub enum ObjectChange {
         digest: ObjectDigest,
     },
 }
+
+impl ObjectChange {
+    pub fn object_id(&self) -> ObjectID {
+        match self {
+            ObjectChange::Published { package_id, .. } => *package_id,
+            ObjectChange::Transferred { object_id, .. }
+            | ObjectChange::Mutated { object_id, .. }
+            | ObjectChange::Deleted { object_id, .. }
+            | ObjectChange::Wrapped { object_id, .. }
+            | ObjectChange::Created { object_id, .. } => *object_id,
+        }
+    }
+
+    /// The owner of the object after the change, if the object still exists.
+    pub fn owner(&self) -> Option<&Owner> {
+        match self {
+            ObjectChange::Transferred { recipient, .. } => Some(recipient),
+            ObjectChange::Mutated { owner, .. } | ObjectChange::Created { owner, .. } => {
+                Some(owner)
+            }
+            ObjectChange::Published { .. }
+            | ObjectChange::Deleted { .. }
+            | ObjectChange::Wrapped { .. } => None,
+        }
+    }
+
+    pub fn object_type(&self) -> Option<&StructTag> {
+        match self {
+            ObjectChange::Published { .. } => None,
+            ObjectChange::Transferred { object_type, .. }
+            | ObjectChange::Mutated { object_type, .. }
+            | ObjectChange::Deleted { object_type, .. }
+            | ObjectChange::Wrapped { object_type, .. }
+            | ObjectChange::Created { object_type, .. } => Some(object_type),
+        }
+    }
+}
+
+/// An object change streamed to the subscribers of object changes.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename = "ObjectChangeEnvelope", rename_all = "camelCase")]
+pub struct SuiObjectChangeEnvelope {
+    /// UTC timestamp in milliseconds since epoch (1/1/1970)
+    pub timestamp: u64,
+    /// Transaction digest of the transaction changing the object
+    pub tx_digest: TransactionDigest,
+    /// The owner of the object before the change, absent for created objects and published packages.
+    pub previous_owner: Option<Owner>,
+    /// The object reference after the change, the digest of deleted and wrapped objects is a marker digest.
+    pub reference: SuiObjectRef,
+    pub change: ObjectChange,
+}
+
+impl Filter<SuiObjectChangeEnvelope> for ObjectChangeFilter {
+    fn matches(&self, item: &SuiObjectChangeEnvelope) -> bool {
+        match self {
+            ObjectChangeFilter::ObjectId(object_id) => &item.change.object_id() == object_id,
+            ObjectChangeFilter::Owner(address) => item
+                .change
+                .owner()
+                .into_iter()
+                .chain(item.previous_owner.as_ref())
+                .any(|owner| matches!(owner, Owner::AddressOwner(a) if a == address)),
+            ObjectChangeFilter::StructType(struct_type) => {
+                item.change.object_type() == Some(struct_type)
+            }
+        }
+    }
+}
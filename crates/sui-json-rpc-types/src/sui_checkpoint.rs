@@ -13,6 +13,10 @@ use sui_types::messages_checkpoint::{
     CheckpointTimestamp, EndOfEpochData,
 };
 
+use crate::Page;
+
+pub type CheckpointPage = Page<Checkpoint, CheckpointSequenceNumber>;
+
 #[derive(Clone, Debug, JsonSchema, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Checkpoint {
@@ -45,6 +49,16 @@ pub struct Checkpoint {
 
 impl From<(CheckpointSummary, CheckpointContents)> for Checkpoint {
     fn from((summary, contents): (CheckpointSummary, CheckpointContents)) -> Self {
+        Checkpoint {
+            transactions: contents.iter().map(|digest| digest.transaction).collect(),
+            ..summary.into()
+        }
+    }
+}
+
+/// A checkpoint without its contents, `transactions` is left empty.
+impl From<CheckpointSummary> for Checkpoint {
+    fn from(summary: CheckpointSummary) -> Self {
         let digest = summary.digest();
         let CheckpointSummary {
             epoch,
@@ -66,7 +80,7 @@ impl From<(CheckpointSummary, CheckpointContents)> for Checkpoint {
             epoch_rolling_gas_cost_summary,
             timestamp_ms,
             end_of_epoch_data,
-            transactions: contents.iter().map(|digest| digest.transaction).collect(),
+            transactions: vec![],
             // TODO: populate commitment for rpc clients. Most likely, rpc clients don't need this
             // info (if they need it, they need to get signed BCS data anyway in order to trust
             // it).
@@ -21,9 +21,10 @@ use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::object::Owner;
 use sui_types::parse_sui_struct_tag;
 
-use crate::{type_and_fields_from_move_struct, Page, SuiMoveStruct};
+use crate::{type_and_fields_from_move_struct, Page, SubscriptionItem, SuiMoveStruct};
 
 pub type EventPage = Page<SuiEventEnvelope, EventID>;
+pub type EventSubscriptionItem = SubscriptionItem<SuiEventEnvelope>;
 #[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename = "EventEnvelope", rename_all = "camelCase")]
 pub struct SuiEventEnvelope {
@@ -8,7 +8,7 @@ use sui_json_rpc_types::{EventPage, SuiEventFilter};
 use sui_open_rpc_macros::open_rpc;
 use sui_types::event::EventID;
 
-use sui_json_rpc_types::SuiEventEnvelope;
+use sui_json_rpc_types::EventSubscriptionItem;
 use sui_types::query::EventQuery;
 
 #[open_rpc(namespace = "sui", tag = "Event Read API")]
@@ -28,11 +28,14 @@ pub trait EventReadApi {
         descending_order: Option<bool>,
     ) -> RpcResult<EventPage>;
 
-    /// Subscribe to a stream of Sui event
-    #[subscription(name = "subscribeEvent", item = SuiEventEnvelope)]
+    /// Subscribe to a stream of Sui event. A `lagged` notification is sent in place of the events
+    /// dropped when the subscriber falls behind.
+    #[subscription(name = "subscribeEvent", item = EventSubscriptionItem)]
     fn subscribe_event(
         &self,
         /// the filter criteria of the event stream, see the [Sui docs](https://docs.sui.io/build/pubsub#event-filters) for detailed examples.
         filter: SuiEventFilter,
+        /// optional exclusive cursor, stored events after the cursor are replayed before streaming new events.
+        cursor: Option<EventID>,
     );
 }
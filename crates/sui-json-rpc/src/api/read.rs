@@ -5,10 +5,11 @@ use jsonrpsee::core::RpcResult;
 use jsonrpsee_proc_macros::rpc;
 use std::collections::BTreeMap;
 use sui_json_rpc_types::{
-    Checkpoint, CheckpointId, DynamicFieldPage, MoveFunctionArgType, SuiGetPastObjectRequest,
-    SuiMoveNormalizedFunction, SuiMoveNormalizedModule, SuiMoveNormalizedStruct,
-    SuiObjectDataOptions, SuiObjectInfo, SuiObjectResponse, SuiPastObjectResponse,
-    SuiTransactionResponse, SuiTransactionResponseOptions, SuiTransactionResponseQuery,
+    Checkpoint, CheckpointId, CheckpointPage, DynamicFieldPage, MoveFunctionArgType,
+    ObjectChangeSubscriptionItem, SuiGetPastObjectRequest, SuiMoveNormalizedFunction,
+    SuiMoveNormalizedModule, SuiMoveNormalizedStruct, SuiObjectDataOptions, SuiObjectInfo,
+    SuiObjectResponse, SuiPastObjectResponse, SuiTransactionResponse,
+    SuiTransactionResponseOptions, SuiTransactionResponseQuery, TransactionSubscriptionItem,
     TransactionsPage,
 };
 use sui_open_rpc_macros::open_rpc;
@@ -17,6 +18,7 @@ use sui_types::base_types::{
 };
 use sui_types::dynamic_field::DynamicFieldName;
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::query::{ObjectChangeFilter, TransactionFilter};
 
 #[open_rpc(namespace = "sui", tag = "Read API")]
 #[rpc(server, client, namespace = "sui")]
@@ -204,4 +206,39 @@ pub trait ReadApi {
         /// Checkpoint identifier, can use either checkpoint digest, or checkpoint sequence number as input.
         id: CheckpointId,
     ) -> RpcResult<Checkpoint>;
+
+    /// Return a paginated list of checkpoints
+    #[method(name = "getCheckpoints")]
+    async fn get_checkpoints(
+        &self,
+        /// An optional paging cursor. If provided, the query will start from the next item after the specified cursor. Default to start from the first item if not specified.
+        cursor: Option<CheckpointSequenceNumber>,
+        /// Maximum item returned per page, default to QUERY_MAX_RESULT_LIMIT if not specified.
+        limit: Option<usize>,
+        /// query result ordering, default to false (ascending order), oldest record first.
+        descending_order: Option<bool>,
+        /// whether to include the transaction digests of each checkpoint, default to true.
+        show_transactions: Option<bool>,
+    ) -> RpcResult<CheckpointPage>;
+
+    /// Subscribe to a stream of executed transactions matching the filter. A `lagged` notification
+    /// is sent in place of the transactions dropped when the subscriber falls behind.
+    #[subscription(name = "subscribeTransaction", item = TransactionSubscriptionItem)]
+    fn subscribe_transaction(
+        &self,
+        /// the filter criteria of the transaction stream.
+        filter: TransactionFilter,
+        /// options for specifying the content to be returned
+        options: Option<SuiTransactionResponseOptions>,
+    );
+
+    /// Subscribe to a stream of object changes matching the filter, including the new version and
+    /// digest of each changed object. A `lagged` notification is sent in place of the transactions
+    /// dropped when the subscriber falls behind.
+    #[subscription(name = "subscribeObjectChanges", item = ObjectChangeSubscriptionItem)]
+    fn subscribe_object_changes(
+        &self,
+        /// the filter criteria of the object change stream.
+        filter: ObjectChangeFilter,
+    );
 }
@@ -12,6 +12,7 @@ use sui_json_rpc_types::{
 
 use sui_open_rpc_macros::open_rpc;
 use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::committee::EpochId;
 
 #[open_rpc(namespace = "sui", tag = "Transaction Builder API")]
 #[rpc(server, client, namespace = "sui")]
@@ -27,10 +28,12 @@ pub trait TransactionBuilder {
         object_id: ObjectID,
         /// gas object to be used in this transaction, node will pick one from the signer's possession if not provided
         gas: Option<ObjectID>,
-        /// the gas budget, the transaction will fail if the gas cost exceed the budget
-        gas_budget: u64,
+        /// the gas budget, the transaction will fail if the gas cost exceed the budget. It is estimated by dry running the transaction if not provided
+        gas_budget: Option<u64>,
         /// the recipient's Sui address
         recipient: SuiAddress,
+        /// the transaction will be rejected by validators after the end of this epoch, it never expires if not provided
+        expiration_epoch: Option<EpochId>,
     ) -> RpcResult<TransactionBytes>;
 
     /// Create an unsigned transaction to send SUI coin object to a Sui address. The SUI object is also used as the gas object.
@@ -41,12 +44,14 @@ pub trait TransactionBuilder {
         signer: SuiAddress,
         /// the Sui coin object to be used in this transaction
         sui_object_id: ObjectID,
-        /// the gas budget, the transaction will fail if the gas cost exceed the budget
-        gas_budget: u64,
+        /// the gas budget, the transaction will fail if the gas cost exceed the budget. It is estimated by dry running the transaction if not provided
+        gas_budget: Option<u64>,
         /// the recipient's Sui address
         recipient: SuiAddress,
         /// the amount to be split out and transferred
         amount: Option<u64>,
+        /// the transaction will be rejected by validators after the end of this epoch, it never expires if not provided
+        expiration_epoch: Option<EpochId>,
     ) -> RpcResult<TransactionBytes>;
 
     /// Send `Coin<T>` to a list of addresses, where `T` can be any coin type, following a list of amounts,
@@ -66,8 +71,10 @@ pub trait TransactionBuilder {
         amounts: Vec<BigInt>,
         /// gas object to be used in this transaction, node will pick one from the signer's possession if not provided
         gas: Option<ObjectID>,
-        /// the gas budget, the transaction will fail if the gas cost exceed the budget
-        gas_budget: u64,
+        /// the gas budget, the transaction will fail if the gas cost exceed the budget. It is estimated by dry running the transaction if not provided
+        gas_budget: Option<u64>,
+        /// the transaction will be rejected by validators after the end of this epoch, it never expires if not provided
+        expiration_epoch: Option<EpochId>,
     ) -> RpcResult<TransactionBytes>;
 
     /// Send SUI coins to a list of addresses, following a list of amounts.
@@ -90,8 +97,10 @@ pub trait TransactionBuilder {
         recipients: Vec<SuiAddress>,
         /// the amounts to be transferred to recipients, following the same order
         amounts: Vec<BigInt>,
-        /// the gas budget, the transaction will fail if the gas cost exceed the budget
-        gas_budget: u64,
+        /// the gas budget, the transaction will fail if the gas cost exceed the budget. It is estimated by dry running the transaction if not provided
+        gas_budget: Option<u64>,
+        /// the transaction will be rejected by validators after the end of this epoch, it never expires if not provided
+        expiration_epoch: Option<EpochId>,
     ) -> RpcResult<TransactionBytes>;
 
     /// Send all SUI coins to one recipient.
@@ -110,8 +119,10 @@ pub trait TransactionBuilder {
         input_coins: Vec<ObjectID>,
         /// the recipient address,
         recipient: SuiAddress,
-        /// the gas budget, the transaction will fail if the gas cost exceed the budget
-        gas_budget: u64,
+        /// the gas budget, the transaction will fail if the gas cost exceed the budget. It is estimated by dry running the transaction if not provided
+        gas_budget: Option<u64>,
+        /// the transaction will be rejected by validators after the end of this epoch, it never expires if not provided
+        expiration_epoch: Option<EpochId>,
     ) -> RpcResult<TransactionBytes>;
 
     /// Create an unsigned transaction to execute a Move call on the network, by calling the specified function in the module of a given package.
@@ -132,10 +143,12 @@ pub trait TransactionBuilder {
         arguments: Vec<SuiJsonValue>,
         /// gas object to be used in this transaction, node will pick one from the signer's possession if not provided
         gas: Option<ObjectID>,
-        /// the gas budget, the transaction will fail if the gas cost exceed the budget
-        gas_budget: u64,
+        /// the gas budget, the transaction will fail if the gas cost exceed the budget. It is estimated by dry running the transaction if not provided
+        gas_budget: Option<u64>,
         /// Whether this is a Normal transaction or a Dev Inspect Transaction. Default to be `SuiTransactionBuilderMode::Commit` when it's None.
         execution_mode: Option<SuiTransactionBuilderMode>,
+        /// the transaction will be rejected by validators after the end of this epoch, it never expires if not provided
+        expiration_epoch: Option<EpochId>,
     ) -> RpcResult<TransactionBytes>;
 
     /// Create an unsigned transaction to publish Move module.
@@ -148,8 +161,32 @@ pub trait TransactionBuilder {
         compiled_modules: Vec<Base64>,
         /// gas object to be used in this transaction, node will pick one from the signer's possession if not provided
         gas: Option<ObjectID>,
-        /// the gas budget, the transaction will fail if the gas cost exceed the budget
-        gas_budget: u64,
+        /// the gas budget, the transaction will fail if the gas cost exceed the budget. It is estimated by dry running the transaction if not provided
+        gas_budget: Option<u64>,
+        /// the transaction will be rejected by validators after the end of this epoch, it never expires if not provided
+        expiration_epoch: Option<EpochId>,
+    ) -> RpcResult<TransactionBytes>;
+
+    /// Create an unsigned transaction to upgrade a Move package.
+    #[method(name = "upgrade")]
+    async fn upgrade(
+        &self,
+        /// the transaction signer's Sui address
+        sender: SuiAddress,
+        /// the compiled bytes of the upgraded package's modules
+        compiled_modules: Vec<Base64>,
+        /// the IDs of the packages the upgraded package depends on, directly or transitively
+        dep_ids: Vec<ObjectID>,
+        /// the ID of the package's `UpgradeCap` object
+        upgrade_capability: ObjectID,
+        /// the upgrade policy to authorize the upgrade with: 0 (compatible), 128 (additive) or 192 (dependency only)
+        upgrade_policy: u8,
+        /// gas object to be used in this transaction, node will pick one from the signer's possession if not provided
+        gas: Option<ObjectID>,
+        /// the gas budget, the transaction will fail if the gas cost exceed the budget. It is estimated by dry running the transaction if not provided
+        gas_budget: Option<u64>,
+        /// the transaction will be rejected by validators after the end of this epoch, it never expires if not provided
+        expiration_epoch: Option<EpochId>,
     ) -> RpcResult<TransactionBytes>;
 
     /// Create an unsigned transaction to split a coin object into multiple coins.
@@ -164,8 +201,10 @@ pub trait TransactionBuilder {
         split_amounts: Vec<u64>,
         /// gas object to be used in this transaction, node will pick one from the signer's possession if not provided
         gas: Option<ObjectID>,
-        /// the gas budget, the transaction will fail if the gas cost exceed the budget
-        gas_budget: u64,
+        /// the gas budget, the transaction will fail if the gas cost exceed the budget. It is estimated by dry running the transaction if not provided
+        gas_budget: Option<u64>,
+        /// the transaction will be rejected by validators after the end of this epoch, it never expires if not provided
+        expiration_epoch: Option<EpochId>,
     ) -> RpcResult<TransactionBytes>;
 
     /// Create an unsigned transaction to split a coin object into multiple equal-size coins.
@@ -180,8 +219,10 @@ pub trait TransactionBuilder {
         split_count: u64,
         /// gas object to be used in this transaction, node will pick one from the signer's possession if not provided
         gas: Option<ObjectID>,
-        /// the gas budget, the transaction will fail if the gas cost exceed the budget
-        gas_budget: u64,
+        /// the gas budget, the transaction will fail if the gas cost exceed the budget. It is estimated by dry running the transaction if not provided
+        gas_budget: Option<u64>,
+        /// the transaction will be rejected by validators after the end of this epoch, it never expires if not provided
+        expiration_epoch: Option<EpochId>,
     ) -> RpcResult<TransactionBytes>;
 
     /// Create an unsigned transaction to merge multiple coins into one coin.
@@ -196,8 +237,10 @@ pub trait TransactionBuilder {
         coin_to_merge: ObjectID,
         /// gas object to be used in this transaction, node will pick one from the signer's possession if not provided
         gas: Option<ObjectID>,
-        /// the gas budget, the transaction will fail if the gas cost exceed the budget
-        gas_budget: u64,
+        /// the gas budget, the transaction will fail if the gas cost exceed the budget. It is estimated by dry running the transaction if not provided
+        gas_budget: Option<u64>,
+        /// the transaction will be rejected by validators after the end of this epoch, it never expires if not provided
+        expiration_epoch: Option<EpochId>,
     ) -> RpcResult<TransactionBytes>;
 
     /// Create an unsigned batched transaction.
@@ -210,10 +253,12 @@ pub trait TransactionBuilder {
         single_transaction_params: Vec<RPCTransactionRequestParams>,
         /// gas object to be used in this transaction, node will pick one from the signer's possession if not provided
         gas: Option<ObjectID>,
-        /// the gas budget, the transaction will fail if the gas cost exceed the budget
-        gas_budget: u64,
+        /// the gas budget, the transaction will fail if the gas cost exceed the budget. It is estimated by dry running the transaction if not provided
+        gas_budget: Option<u64>,
         /// Whether this is a regular transaction or a Dev Inspect Transaction
         txn_builder_mode: Option<SuiTransactionBuilderMode>,
+        /// the transaction will be rejected by validators after the end of this epoch, it never expires if not provided
+        expiration_epoch: Option<EpochId>,
     ) -> RpcResult<TransactionBytes>;
 
     /// Add stake to a validator's staking pool using multiple coins and amount.
@@ -230,8 +275,10 @@ pub trait TransactionBuilder {
         validator: SuiAddress,
         /// gas object to be used in this transaction, node will pick one from the signer's possession if not provided
         gas: Option<ObjectID>,
-        /// the gas budget, the transaction will fail if the gas cost exceed the budget
-        gas_budget: u64,
+        /// the gas budget, the transaction will fail if the gas cost exceed the budget. It is estimated by dry running the transaction if not provided
+        gas_budget: Option<u64>,
+        /// the transaction will be rejected by validators after the end of this epoch, it never expires if not provided
+        expiration_epoch: Option<EpochId>,
     ) -> RpcResult<TransactionBytes>;
 
     /// Withdraw stake from a validator's staking pool.
@@ -247,7 +294,9 @@ pub trait TransactionBuilder {
         staked_sui: ObjectID,
         /// gas object to be used in this transaction, node will pick one from the signer's possession if not provided
         gas: Option<ObjectID>,
-        /// the gas budget, the transaction will fail if the gas cost exceed the budget
-        gas_budget: u64,
+        /// the gas budget, the transaction will fail if the gas cost exceed the budget. It is estimated by dry running the transaction if not provided
+        gas_budget: Option<u64>,
+        /// the transaction will be rejected by validators after the end of this epoch, it never expires if not provided
+        expiration_epoch: Option<EpochId>,
     ) -> RpcResult<TransactionBytes>;
 }
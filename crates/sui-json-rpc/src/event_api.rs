@@ -17,16 +17,18 @@ use tracing::{debug, warn};
 
 use sui_core::authority::AuthorityState;
 use sui_core::event_handler::EventHandler;
-use sui_json_rpc_types::{EventPage, SuiEvent, SuiEventEnvelope, SuiEventFilter};
+use sui_core::streamer::StreamItem;
+use sui_json_rpc_types::{EventPage, SubscriptionItem, SuiEvent, SuiEventEnvelope, SuiEventFilter};
 use sui_open_rpc::Module;
 use sui_types::event::{EventEnvelope, EventID};
 use sui_types::query::EventQuery;
 
 use crate::api::cap_page_limit;
 use crate::api::EventReadApiServer;
+use crate::error::Error;
 use crate::SuiRpcModule;
 
-fn spawn_subscription<S, T, E>(mut sink: SubscriptionSink, rx: S)
+pub(crate) fn spawn_subscription<S, T, E>(mut sink: SubscriptionSink, rx: S)
 where
     S: TryStream<Ok = T, Error = E> + Unpin + Send + 'static,
     T: Serialize,
@@ -39,7 +41,7 @@ where
             }
             SubscriptionClosed::RemotePeerAborted => (),
             SubscriptionClosed::Failed(err) => {
-                warn!(error = ?err, "Event subscription closed.");
+                warn!(error = ?err, "Subscription closed.");
                 sink.close(err);
             }
         };
@@ -99,6 +101,7 @@ impl EventReadApiServer for EventReadApi {
         &self,
         mut sink: SubscriptionSink,
         filter: SuiEventFilter,
+        cursor: Option<EventID>,
     ) -> SubscriptionResult {
         let filter = match filter.try_into() {
             Ok(filter) => filter,
@@ -109,9 +112,24 @@ impl EventReadApiServer for EventReadApi {
             }
         };
 
+        let stream = match cursor {
+            Some(cursor) => match self.state.subscribe_events_from(filter, cursor) {
+                Ok(stream) => stream.boxed(),
+                Err(e) => {
+                    let e = jsonrpsee::core::Error::from(Error::from(e));
+                    warn!(error = ?e, "Rejecting subscription request.");
+                    return Ok(sink.reject(e)?);
+                }
+            },
+            None => self.event_handler.subscribe(filter).boxed(),
+        };
+
         let state = self.state.clone();
-        let stream = self.event_handler.subscribe(filter);
-        let stream = stream.map(move |e: EventEnvelope| {
+        let stream = stream.map(move |item| {
+            let e: EventEnvelope = match item {
+                StreamItem::Data(e) => e,
+                StreamItem::Lagged(lagged) => return Ok(SubscriptionItem::Lagged { lagged }),
+            };
             let event = SuiEvent::try_from(
                 e.event,
                 // threading the epoch_store through this API does not
@@ -124,11 +142,13 @@ impl EventReadApiServer for EventReadApi {
                     .module_cache()
                     .as_ref(),
             );
-            event.map(|event| SuiEventEnvelope {
-                timestamp: e.timestamp,
-                tx_digest: e.tx_digest,
-                id: EventID::from((e.tx_digest, e.event_num as i64)),
-                event,
+            event.map(|event| {
+                SubscriptionItem::Data(SuiEventEnvelope {
+                    timestamp: e.timestamp,
+                    tx_digest: e.tx_digest,
+                    id: EventID::from((e.tx_digest, e.event_num as i64)),
+                    event,
+                })
             })
         });
         spawn_subscription(sink, stream);
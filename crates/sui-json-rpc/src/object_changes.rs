@@ -1,11 +1,13 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::HashMap;
+
 use crate::ObjectProvider;
-use sui_json_rpc_types::ObjectChange;
+use sui_json_rpc_types::{ObjectChange, SuiObjectChangeEnvelope};
 use sui_types::base_types::{MoveObjectType, SuiAddress};
 use sui_types::governance::StakedSui;
-use sui_types::messages::TransactionEffectsAPI;
+use sui_types::messages::{TransactionEffectsAPI, TransactionEnvelope};
 use sui_types::storage::{DeleteKind, WriteKind};
 
 pub async fn get_object_change_from_effect<P: ObjectProvider<Error = E>, E>(
@@ -91,3 +93,44 @@ pub async fn get_object_change_from_effect<P: ObjectProvider<Error = E>, E>(
 
     Ok(object_changes)
 }
+
+/// Resolve the object changes of an executed transaction, together with the new reference and the
+/// previous owner of each changed object.
+pub async fn get_object_change_envelopes<P: ObjectProvider<Error = E>, E>(
+    object_provider: &P,
+    envelope: &TransactionEnvelope,
+) -> Result<Vec<SuiObjectChangeEnvelope>, E> {
+    let effects = &envelope.effects;
+    let sender = envelope.transaction.intent_message().value.sender();
+    let references = effects
+        .all_changed_objects()
+        .into_iter()
+        .map(|(oref, _, _)| oref)
+        .chain(effects.all_deleted().into_iter().map(|(oref, _)| oref))
+        .map(|oref| (oref.0, *oref))
+        .collect::<HashMap<_, _>>();
+    let previous_versions = effects
+        .modified_at_versions()
+        .iter()
+        .cloned()
+        .collect::<HashMap<_, _>>();
+
+    let mut envelopes = vec![];
+    for change in get_object_change_from_effect(object_provider, sender, effects).await? {
+        let object_id = change.object_id();
+        let previous_owner = match previous_versions.get(&object_id) {
+            Some(version) => Some(object_provider.get_object(&object_id, version).await?.owner),
+            None => None,
+        };
+        // Every change is derived from an object reference of the effects.
+        let reference = references[&object_id];
+        envelopes.push(SuiObjectChangeEnvelope {
+            timestamp: envelope.timestamp_ms,
+            tx_digest: *effects.transaction_digest(),
+            previous_owner,
+            reference: reference.into(),
+            change,
+        });
+    }
+    Ok(envelopes)
+}
@@ -8,9 +8,11 @@ use anyhow::anyhow;
 use async_trait::async_trait;
 use fastcrypto::encoding::Base64;
 use futures::future::join_all;
+use futures::StreamExt;
 use itertools::Itertools;
 use jsonrpsee::core::RpcResult;
-use jsonrpsee::RpcModule;
+use jsonrpsee::types::SubscriptionResult;
+use jsonrpsee::{RpcModule, SubscriptionSink};
 use linked_hash_map::LinkedHashMap;
 use move_binary_format::normalized::{Module as NormalizedModule, Type};
 use move_core_types::identifier::Identifier;
@@ -22,11 +24,12 @@ use tracing::debug;
 
 use shared_crypto::intent::{AppId, Intent, IntentMessage, IntentScope, IntentVersion};
 use sui_core::authority::AuthorityState;
+use sui_core::streamer::StreamItem;
 use sui_json_rpc_types::{
-    BalanceChange, Checkpoint, CheckpointId, DynamicFieldPage, MoveFunctionArgType, ObjectChange,
-    ObjectValueKind, Page, SuiEvent, SuiGetPastObjectRequest, SuiMoveNormalizedFunction,
-    SuiMoveNormalizedModule, SuiMoveNormalizedStruct, SuiMoveStruct, SuiMoveValue,
-    SuiObjectDataOptions, SuiObjectInfo, SuiObjectResponse, SuiPastObjectResponse,
+    BalanceChange, Checkpoint, CheckpointId, CheckpointPage, DynamicFieldPage, MoveFunctionArgType,
+    ObjectChange, ObjectValueKind, Page, SubscriptionItem, SuiEvent, SuiGetPastObjectRequest,
+    SuiMoveNormalizedFunction, SuiMoveNormalizedModule, SuiMoveNormalizedStruct, SuiMoveStruct,
+    SuiMoveValue, SuiObjectDataOptions, SuiObjectInfo, SuiObjectResponse, SuiPastObjectResponse,
     SuiTransactionEvents, SuiTransactionResponse, SuiTransactionResponseOptions,
     SuiTransactionResponseQuery, TransactionsPage,
 };
@@ -40,22 +43,24 @@ use sui_types::digests::TransactionEventsDigest;
 use sui_types::display::{DisplayCreatedEvent, DisplayObject};
 use sui_types::dynamic_field::DynamicFieldName;
 use sui_types::error::UserInputError;
+use sui_types::filter::Filter;
 use sui_types::messages::{
-    TransactionData, TransactionEffects, TransactionEffectsAPI, TransactionEvents,
-    VerifiedTransaction,
+    Transaction, TransactionData, TransactionEffects, TransactionEffectsAPI, TransactionEnvelope,
+    TransactionEvents, VerifiedTransaction,
 };
 use sui_types::messages_checkpoint::{CheckpointSequenceNumber, CheckpointTimestamp};
 use sui_types::move_package::normalize_modules;
 use sui_types::object::{Data, Object, ObjectRead, PastObjectRead};
-use sui_types::query::EventQuery;
+use sui_types::query::{EventQuery, ObjectChangeFilter, TransactionFilter};
 
 use crate::api::cap_page_limit;
 use crate::api::ReadApiServer;
 use crate::api::QUERY_MAX_RESULT_LIMIT;
 use crate::error::Error;
+use crate::event_api::spawn_subscription;
 use crate::{
-    get_balance_change_from_effect, get_object_change_from_effect, ObjectProviderCache,
-    SuiRpcModule,
+    get_balance_change_from_effect, get_object_change_envelopes, get_object_change_from_effect,
+    ObjectProviderCache, SuiRpcModule,
 };
 
 const MAX_DISPLAY_NESTED_LEVEL: usize = 10;
@@ -372,7 +377,7 @@ impl ReadApiServer for ReadApi {
                     .state
                     .get_transaction_events(digest)
                     .map_err(Error::from)?;
-                match to_sui_transaction_events(self, events) {
+                match to_sui_transaction_events(&self.state, events) {
                     Ok(e) => temp_response.events = Some(e),
                     Err(e) => temp_response.errors.push(e.to_string()),
                 };
@@ -556,7 +561,7 @@ impl ReadApiServer for ReadApi {
                     let events: Option<RpcResult<SuiTransactionEvents>> = event_digest_to_events
                         .remove(event_digest.as_ref().unwrap())
                         .expect("This can only happen if there are two or more transaction digests sharing the same event digests, which should never happen")
-                        .map(|e| to_sui_transaction_events(self, e));
+                        .map(|e| to_sui_transaction_events(&self.state, e));
                     match events {
                         Some(Ok(e)) => cache_entry.events = Some(e),
                         Some(Err(e)) => cache_entry.errors.push(e.to_string()),
@@ -746,6 +751,125 @@ impl ReadApiServer for ReadApi {
     async fn get_checkpoint(&self, id: CheckpointId) -> RpcResult<Checkpoint> {
         Ok(self.get_checkpoint_internal(id)?)
     }
+
+    async fn get_checkpoints(
+        &self,
+        // exclusive cursor if `Some`, otherwise start from the beginning
+        cursor: Option<CheckpointSequenceNumber>,
+        limit: Option<usize>,
+        descending_order: Option<bool>,
+        show_transactions: Option<bool>,
+    ) -> RpcResult<CheckpointPage> {
+        let limit = cap_page_limit(limit);
+        let descending = descending_order.unwrap_or_default();
+
+        // Retrieve 1 extra item for next cursor
+        let mut checkpoints = self
+            .state
+            .get_checkpoints(cursor, limit as u64 + 1, descending)?;
+
+        // extract next cursor
+        let has_next_page = checkpoints.len() > limit;
+        checkpoints.truncate(limit);
+        let next_cursor = checkpoints
+            .last()
+            .map(|checkpoint| *checkpoint.sequence_number())
+            .map_or(cursor, Some);
+
+        let summaries: Vec<_> = checkpoints
+            .into_iter()
+            .map(|checkpoint| checkpoint.into_inner().into_data())
+            .collect();
+        let data = if show_transactions.unwrap_or(true) {
+            let digests: Vec<_> = summaries
+                .iter()
+                .map(|summary| summary.content_digest)
+                .collect();
+            let contents = self.state.multi_get_checkpoint_contents(&digests)?;
+            summaries
+                .into_iter()
+                .zip(contents)
+                .map(Into::into)
+                .collect()
+        } else {
+            summaries.into_iter().map(Into::into).collect()
+        };
+
+        Ok(Page {
+            data,
+            next_cursor,
+            has_next_page,
+        })
+    }
+
+    fn subscribe_transaction(
+        &self,
+        sink: SubscriptionSink,
+        filter: TransactionFilter,
+        opts: Option<SuiTransactionResponseOptions>,
+    ) -> SubscriptionResult {
+        let opts = opts.unwrap_or_default();
+        let state = self.state.clone();
+        let stream = self
+            .state
+            .event_handler
+            .subscribe_transactions(filter)
+            .then(move |item| {
+                let state = state.clone();
+                let opts = opts.clone();
+                async move {
+                    match item {
+                        StreamItem::Data(envelope) => {
+                            to_transaction_response(state, envelope, &opts)
+                                .await
+                                .map(SubscriptionItem::Data)
+                        }
+                        StreamItem::Lagged(lagged) => Ok(SubscriptionItem::Lagged { lagged }),
+                    }
+                }
+            })
+            .boxed();
+        spawn_subscription(sink, stream);
+        Ok(())
+    }
+
+    fn subscribe_object_changes(
+        &self,
+        sink: SubscriptionSink,
+        filter: ObjectChangeFilter,
+    ) -> SubscriptionResult {
+        let state = self.state.clone();
+        let stream = self
+            .state
+            .event_handler
+            .subscribe_object_changes(filter.clone())
+            .then(move |item| {
+                let state = state.clone();
+                let filter = filter.clone();
+                async move {
+                    match item {
+                        StreamItem::Data(envelope) => {
+                            let object_cache = ObjectProviderCache::new(state);
+                            match get_object_change_envelopes(&object_cache, &envelope).await {
+                                Ok(changes) => changes
+                                    .into_iter()
+                                    .filter(|change| filter.matches(change))
+                                    .map(|change| Ok(SubscriptionItem::Data(change)))
+                                    .collect(),
+                                Err(e) => vec![Err(Error::SuiError(e))],
+                            }
+                        }
+                        StreamItem::Lagged(lagged) => {
+                            vec![Ok(SubscriptionItem::Lagged { lagged })]
+                        }
+                    }
+                }
+            })
+            .flat_map(futures::stream::iter)
+            .boxed();
+        spawn_subscription(sink, stream);
+        Ok(())
+    }
 }
 
 impl SuiRpcModule for ReadApi {
@@ -759,7 +883,7 @@ impl SuiRpcModule for ReadApi {
 }
 
 fn to_sui_transaction_events(
-    fullnode_api: &ReadApi,
+    state: &AuthorityState,
     events: TransactionEvents,
 ) -> RpcResult<SuiTransactionEvents> {
     Ok(SuiTransactionEvents::try_from(
@@ -769,14 +893,57 @@ fn to_sui_transaction_events(
         // the module cache out of it.
         // Notice that no matter what module cache we get things
         // should work
-        fullnode_api
-            .state
+        state
             .load_epoch_store_one_call_per_task()
             .module_cache()
             .as_ref(),
     )?)
 }
 
+async fn to_transaction_response(
+    state: Arc<AuthorityState>,
+    envelope: TransactionEnvelope,
+    opts: &SuiTransactionResponseOptions,
+) -> Result<SuiTransactionResponse, Error> {
+    let TransactionEnvelope {
+        timestamp_ms,
+        transaction,
+        effects,
+        events,
+    } = envelope;
+    let transaction = VerifiedTransaction::new_unchecked(Transaction::new(transaction));
+    let mut temp_response = IntermediateTransactionResponse::new(*effects.transaction_digest());
+    // The transaction is streamed before it is included in a checkpoint, use the execution time instead.
+    temp_response.timestamp = Some(timestamp_ms);
+
+    if opts.show_events {
+        match to_sui_transaction_events(&state, events) {
+            Ok(e) => temp_response.events = Some(e),
+            Err(e) => temp_response.errors.push(e.to_string()),
+        };
+    }
+
+    let object_cache = ObjectProviderCache::new(state);
+    if opts.show_balance_changes {
+        let balance_changes = get_balance_change_from_effect(&object_cache, &effects)
+            .await
+            .map_err(Error::SuiError)?;
+        temp_response.balance_changes = Some(balance_changes);
+    }
+
+    if opts.show_object_changes {
+        let object_changes =
+            get_object_change_from_effect(&object_cache, transaction.sender_address(), &effects)
+                .await
+                .map_err(Error::SuiError)?;
+        temp_response.object_changes = Some(object_changes);
+    }
+
+    temp_response.transaction = Some(transaction);
+    temp_response.effects = Some(effects);
+    Ok(convert_to_response(temp_response, opts))
+}
+
 async fn get_display_fields(
     fullnode_api: &ReadApi,
     original_object: &Object,
@@ -8,13 +8,15 @@ use jsonrpsee::core::RpcResult;
 use std::sync::Arc;
 use sui_core::authority::AuthorityState;
 use sui_json_rpc_types::{
-    BigInt, SuiObjectDataOptions, SuiObjectInfo, SuiObjectResponse, SuiTransactionBuilderMode,
-    SuiTypeTag, TransactionBytes,
+    BigInt, DryRunTransactionResponse, SuiObjectDataOptions, SuiObjectInfo, SuiObjectResponse,
+    SuiTransactionBuilderMode, SuiTypeTag, TransactionBytes,
 };
 use sui_open_rpc::Module;
 use sui_transaction_builder::{DataReader, TransactionBuilder};
 use sui_types::{
-    base_types::{ObjectID, SuiAddress},
+    base_types::{ObjectID, SuiAddress, TransactionDigest},
+    committee::EpochId,
+    crypto::user_hash,
     messages::TransactionData,
 };
 
@@ -76,6 +78,14 @@ impl DataReader for AuthorityStateDataReader {
         let epoch_store = self.0.load_epoch_store_one_call_per_task();
         Ok(epoch_store.reference_gas_price())
     }
+
+    async fn dry_run_transaction(
+        &self,
+        tx: TransactionData,
+    ) -> Result<DryRunTransactionResponse, anyhow::Error> {
+        let digest = TransactionDigest::new(user_hash(&tx));
+        self.0.dry_exec_transaction(tx, digest).await
+    }
 }
 
 #[async_trait]
@@ -85,12 +95,15 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         signer: SuiAddress,
         object_id: ObjectID,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
         recipient: SuiAddress,
+        expiration_epoch: Option<EpochId>,
     ) -> RpcResult<TransactionBytes> {
-        let data = self
-            .builder
-            .transfer_object(signer, object_id, gas, gas_budget, recipient)
+        let builder = self.builder.with_expiration(expiration_epoch);
+        let data = builder
+            .build_with_gas_budget(gas_budget, |gas_budget| {
+                builder.transfer_object(signer, object_id, gas, gas_budget, recipient)
+            })
             .await?;
         Ok(TransactionBytes::from_data(data)?)
     }
@@ -99,13 +112,16 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         &self,
         signer: SuiAddress,
         sui_object_id: ObjectID,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
         recipient: SuiAddress,
         amount: Option<u64>,
+        expiration_epoch: Option<EpochId>,
     ) -> RpcResult<TransactionBytes> {
-        let data = self
-            .builder
-            .transfer_sui(signer, sui_object_id, gas_budget, recipient, amount)
+        let builder = self.builder.with_expiration(expiration_epoch);
+        let data = builder
+            .build_with_gas_budget(gas_budget, |gas_budget| {
+                builder.transfer_sui(signer, sui_object_id, gas_budget, recipient, amount)
+            })
             .await?;
         Ok(TransactionBytes::from_data(data)?)
     }
@@ -117,18 +133,22 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         recipients: Vec<SuiAddress>,
         amounts: Vec<BigInt>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
+        expiration_epoch: Option<EpochId>,
     ) -> RpcResult<TransactionBytes> {
-        let data = self
-            .builder
-            .pay(
-                signer,
-                input_coins,
-                recipients,
-                amounts.into_iter().map(|a| a.into()).collect(),
-                gas,
-                gas_budget,
-            )
+        let amounts: Vec<u64> = amounts.into_iter().map(|a| a.into()).collect();
+        let builder = self.builder.with_expiration(expiration_epoch);
+        let data = builder
+            .build_with_gas_budget(gas_budget, |gas_budget| {
+                builder.pay(
+                    signer,
+                    input_coins.clone(),
+                    recipients.clone(),
+                    amounts.clone(),
+                    gas,
+                    gas_budget,
+                )
+            })
             .await?;
         Ok(TransactionBytes::from_data(data)?)
     }
@@ -139,17 +159,21 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         input_coins: Vec<ObjectID>,
         recipients: Vec<SuiAddress>,
         amounts: Vec<BigInt>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
+        expiration_epoch: Option<EpochId>,
     ) -> RpcResult<TransactionBytes> {
-        let data = self
-            .builder
-            .pay_sui(
-                signer,
-                input_coins,
-                recipients,
-                amounts.into_iter().map(|a| a.into()).collect(),
-                gas_budget,
-            )
+        let amounts: Vec<u64> = amounts.into_iter().map(|a| a.into()).collect();
+        let builder = self.builder.with_expiration(expiration_epoch);
+        let data = builder
+            .build_with_gas_budget(gas_budget, |gas_budget| {
+                builder.pay_sui(
+                    signer,
+                    input_coins.clone(),
+                    recipients.clone(),
+                    amounts.clone(),
+                    gas_budget,
+                )
+            })
             .await?;
         Ok(TransactionBytes::from_data(data)?)
     }
@@ -159,11 +183,14 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         signer: SuiAddress,
         input_coins: Vec<ObjectID>,
         recipient: SuiAddress,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
+        expiration_epoch: Option<EpochId>,
     ) -> RpcResult<TransactionBytes> {
-        let data = self
-            .builder
-            .pay_all_sui(signer, input_coins, recipient, gas_budget)
+        let builder = self.builder.with_expiration(expiration_epoch);
+        let data = builder
+            .build_with_gas_budget(gas_budget, |gas_budget| {
+                builder.pay_all_sui(signer, input_coins.clone(), recipient, gas_budget)
+            })
             .await?;
         Ok(TransactionBytes::from_data(data)?)
     }
@@ -173,15 +200,50 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         sender: SuiAddress,
         compiled_modules: Vec<Base64>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
+        expiration_epoch: Option<EpochId>,
+    ) -> RpcResult<TransactionBytes> {
+        let compiled_modules = compiled_modules
+            .into_iter()
+            .map(|data| data.to_vec().map_err(|e| anyhow::anyhow!(e)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let builder = self.builder.with_expiration(expiration_epoch);
+        let data = builder
+            .build_with_gas_budget(gas_budget, |gas_budget| {
+                builder.publish(sender, compiled_modules.clone(), gas, gas_budget)
+            })
+            .await?;
+        Ok(TransactionBytes::from_data(data)?)
+    }
+
+    async fn upgrade(
+        &self,
+        sender: SuiAddress,
+        compiled_modules: Vec<Base64>,
+        dep_ids: Vec<ObjectID>,
+        upgrade_capability: ObjectID,
+        upgrade_policy: u8,
+        gas: Option<ObjectID>,
+        gas_budget: Option<u64>,
+        expiration_epoch: Option<EpochId>,
     ) -> RpcResult<TransactionBytes> {
         let compiled_modules = compiled_modules
             .into_iter()
             .map(|data| data.to_vec().map_err(|e| anyhow::anyhow!(e)))
             .collect::<Result<Vec<_>, _>>()?;
-        let data = self
-            .builder
-            .publish(sender, compiled_modules, gas, gas_budget)
+        let builder = self.builder.with_expiration(expiration_epoch);
+        let data = builder
+            .build_with_gas_budget(gas_budget, |gas_budget| {
+                builder.upgrade(
+                    sender,
+                    compiled_modules.clone(),
+                    dep_ids.clone(),
+                    upgrade_capability,
+                    upgrade_policy,
+                    gas,
+                    gas_budget,
+                )
+            })
             .await?;
         Ok(TransactionBytes::from_data(data)?)
     }
@@ -192,11 +254,20 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         coin_object_id: ObjectID,
         split_amounts: Vec<u64>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
+        expiration_epoch: Option<EpochId>,
     ) -> RpcResult<TransactionBytes> {
-        let data = self
-            .builder
-            .split_coin(signer, coin_object_id, split_amounts, gas, gas_budget)
+        let builder = self.builder.with_expiration(expiration_epoch);
+        let data = builder
+            .build_with_gas_budget(gas_budget, |gas_budget| {
+                builder.split_coin(
+                    signer,
+                    coin_object_id,
+                    split_amounts.clone(),
+                    gas,
+                    gas_budget,
+                )
+            })
             .await?;
         Ok(TransactionBytes::from_data(data)?)
     }
@@ -207,11 +278,14 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         coin_object_id: ObjectID,
         split_count: u64,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
+        expiration_epoch: Option<EpochId>,
     ) -> RpcResult<TransactionBytes> {
-        let data = self
-            .builder
-            .split_coin_equal(signer, coin_object_id, split_count, gas, gas_budget)
+        let builder = self.builder.with_expiration(expiration_epoch);
+        let data = builder
+            .build_with_gas_budget(gas_budget, |gas_budget| {
+                builder.split_coin_equal(signer, coin_object_id, split_count, gas, gas_budget)
+            })
             .await?;
         Ok(TransactionBytes::from_data(data)?)
     }
@@ -222,11 +296,14 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         primary_coin: ObjectID,
         coin_to_merge: ObjectID,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
+        expiration_epoch: Option<EpochId>,
     ) -> RpcResult<TransactionBytes> {
-        let data = self
-            .builder
-            .merge_coins(signer, primary_coin, coin_to_merge, gas, gas_budget)
+        let builder = self.builder.with_expiration(expiration_epoch);
+        let data = builder
+            .build_with_gas_budget(gas_budget, |gas_budget| {
+                builder.merge_coins(signer, primary_coin, coin_to_merge, gas, gas_budget)
+            })
             .await?;
         Ok(TransactionBytes::from_data(data)?)
     }
@@ -240,37 +317,44 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         type_arguments: Vec<SuiTypeTag>,
         rpc_arguments: Vec<SuiJsonValue>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
         txn_builder_mode: Option<SuiTransactionBuilderMode>,
+        expiration_epoch: Option<EpochId>,
     ) -> RpcResult<TransactionBytes> {
         let mode = txn_builder_mode.unwrap_or(SuiTransactionBuilderMode::Commit);
         let data: TransactionData = match mode {
             SuiTransactionBuilderMode::DevInspect => {
-                self.dev_inspect_builder
-                    .move_call(
-                        signer,
-                        package_object_id,
-                        &module,
-                        &function,
-                        type_arguments,
-                        rpc_arguments,
-                        gas,
-                        gas_budget,
-                    )
+                let builder = self.dev_inspect_builder.with_expiration(expiration_epoch);
+                builder
+                    .build_with_gas_budget(gas_budget, |gas_budget| {
+                        builder.move_call(
+                            signer,
+                            package_object_id,
+                            &module,
+                            &function,
+                            type_arguments.clone(),
+                            rpc_arguments.clone(),
+                            gas,
+                            gas_budget,
+                        )
+                    })
                     .await?
             }
             SuiTransactionBuilderMode::Commit => {
-                self.builder
-                    .move_call(
-                        signer,
-                        package_object_id,
-                        &module,
-                        &function,
-                        type_arguments,
-                        rpc_arguments,
-                        gas,
-                        gas_budget,
-                    )
+                let builder = self.builder.with_expiration(expiration_epoch);
+                builder
+                    .build_with_gas_budget(gas_budget, |gas_budget| {
+                        builder.move_call(
+                            signer,
+                            package_object_id,
+                            &module,
+                            &function,
+                            type_arguments.clone(),
+                            rpc_arguments.clone(),
+                            gas,
+                            gas_budget,
+                        )
+                    })
                     .await?
             }
         };
@@ -282,19 +366,26 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         signer: SuiAddress,
         params: Vec<RPCTransactionRequestParams>,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
         txn_builder_mode: Option<SuiTransactionBuilderMode>,
+        expiration_epoch: Option<EpochId>,
     ) -> RpcResult<TransactionBytes> {
         let mode = txn_builder_mode.unwrap_or(SuiTransactionBuilderMode::Commit);
         let data = match mode {
             SuiTransactionBuilderMode::DevInspect => {
-                self.dev_inspect_builder
-                    .batch_transaction(signer, params, gas, gas_budget)
+                let builder = self.dev_inspect_builder.with_expiration(expiration_epoch);
+                builder
+                    .build_with_gas_budget(gas_budget, |gas_budget| {
+                        builder.batch_transaction(signer, params.clone(), gas, gas_budget)
+                    })
                     .await?
             }
             SuiTransactionBuilderMode::Commit => {
-                self.builder
-                    .batch_transaction(signer, params, gas, gas_budget)
+                let builder = self.builder.with_expiration(expiration_epoch);
+                builder
+                    .build_with_gas_budget(gas_budget, |gas_budget| {
+                        builder.batch_transaction(signer, params.clone(), gas, gas_budget)
+                    })
                     .await?
             }
         };
@@ -308,11 +399,22 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         amount: Option<u64>,
         validator: SuiAddress,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
+        expiration_epoch: Option<EpochId>,
     ) -> RpcResult<TransactionBytes> {
+        let builder = self.builder.with_expiration(expiration_epoch);
         Ok(TransactionBytes::from_data(
-            self.builder
-                .request_add_stake(signer, coins, amount, validator, gas, gas_budget)
+            builder
+                .build_with_gas_budget(gas_budget, |gas_budget| {
+                    builder.request_add_stake(
+                        signer,
+                        coins.clone(),
+                        amount,
+                        validator,
+                        gas,
+                        gas_budget,
+                    )
+                })
                 .await?,
         )?)
     }
@@ -323,11 +425,15 @@ impl TransactionBuilderServer for TransactionBuilderApi {
         delegation: ObjectID,
         staked_sui: ObjectID,
         gas: Option<ObjectID>,
-        gas_budget: u64,
+        gas_budget: Option<u64>,
+        expiration_epoch: Option<EpochId>,
     ) -> RpcResult<TransactionBytes> {
+        let builder = self.builder.with_expiration(expiration_epoch);
         Ok(TransactionBytes::from_data(
-            self.builder
-                .request_withdraw_stake(signer, delegation, staked_sui, gas, gas_budget)
+            builder
+                .build_with_gas_budget(gas_budget, |gas_budget| {
+                    builder.request_withdraw_stake(signer, delegation, staked_sui, gas, gas_budget)
+                })
                 .await?,
         )?)
     }
@@ -26,7 +26,9 @@ use sui_types::base_types::ObjectID;
 use sui_types::base_types::TransactionDigest;
 use sui_types::coin::{TreasuryCap, COIN_MODULE_NAME, LOCKED_COIN_MODULE_NAME};
 use sui_types::gas_coin::GAS;
-use sui_types::messages::ExecuteTransactionRequestType;
+use sui_types::messages::{
+    ExecuteTransactionRequestType, TransactionDataAPI, TransactionExpiration,
+};
 use sui_types::object::Owner;
 use sui_types::query::{EventQuery, TransactionFilter};
 use sui_types::utils::to_sender_signed_transaction;
@@ -70,8 +72,9 @@ async fn test_public_transfer_object() -> Result<(), anyhow::Error> {
             *address,
             objects.first().unwrap().object_id,
             Some(objects.last().unwrap().object_id),
-            1000,
+            Some(1000),
             *address,
+            None,
         )
         .await?;
 
@@ -99,6 +102,178 @@ async fn test_public_transfer_object() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_transfer_object_with_expiration() -> Result<(), anyhow::Error> {
+    let cluster = TestClusterBuilder::new().build().await?;
+    let http_client = cluster.rpc_client();
+    let address = cluster.accounts.first().unwrap();
+
+    let objects = http_client.get_objects_owned_by_address(*address).await?;
+
+    let transaction_bytes: TransactionBytes = http_client
+        .transfer_object(
+            *address,
+            objects.first().unwrap().object_id,
+            Some(objects.last().unwrap().object_id),
+            Some(1000),
+            *address,
+            Some(0),
+        )
+        .await?;
+    let data = transaction_bytes.to_data()?;
+    assert_eq!(data.expiration(), &TransactionExpiration::Epoch(0));
+
+    // The transaction is still valid during its expiration epoch.
+    let keystore_path = cluster.swarm.dir().join(SUI_KEYSTORE_FILENAME);
+    let keystore = Keystore::from(FileBasedKeystore::new(&keystore_path)?);
+    let tx = to_sender_signed_transaction(data, keystore.get_key(address)?);
+    let (tx_bytes, signatures) = tx.to_tx_bytes_and_signatures();
+    let tx_response: SuiTransactionResponse = http_client
+        .execute_transaction(
+            tx_bytes,
+            signatures,
+            Some(SuiTransactionResponseOptions::new().with_effects()),
+            Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+        )
+        .await?;
+    assert_eq!(
+        tx_response.effects.unwrap().status(),
+        &SuiExecutionStatus::Success
+    );
+    Ok(())
+}
+
+#[sim_test]
+async fn test_transfer_object_with_estimated_gas_budget() -> Result<(), anyhow::Error> {
+    let cluster = TestClusterBuilder::new().build().await?;
+    let http_client = cluster.rpc_client();
+    let address = cluster.accounts.first().unwrap();
+
+    let objects = http_client.get_objects_owned_by_address(*address).await?;
+
+    let transaction_bytes: TransactionBytes = http_client
+        .transfer_object(
+            *address,
+            objects.first().unwrap().object_id,
+            None,
+            None,
+            *address,
+            None,
+        )
+        .await?;
+    let data = transaction_bytes.to_data()?;
+
+    let keystore_path = cluster.swarm.dir().join(SUI_KEYSTORE_FILENAME);
+    let keystore = Keystore::from(FileBasedKeystore::new(&keystore_path)?);
+    let tx = to_sender_signed_transaction(data.clone(), keystore.get_key(address)?);
+    let (tx_bytes, signatures) = tx.to_tx_bytes_and_signatures();
+    let tx_response: SuiTransactionResponse = http_client
+        .execute_transaction(
+            tx_bytes,
+            signatures,
+            Some(SuiTransactionResponseOptions::new().with_effects()),
+            Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+        )
+        .await?;
+    let effects = tx_response.effects.unwrap();
+    assert_eq!(effects.status(), &SuiExecutionStatus::Success);
+
+    // The estimate covers the cost of the transaction, with the default margin on top.
+    let gas_used = effects.gas_used();
+    assert!(data.gas_budget() >= gas_used.computation_cost + gas_used.storage_cost);
+    Ok(())
+}
+
+#[sim_test]
+async fn test_transfer_sui_with_estimated_gas_budget() -> Result<(), anyhow::Error> {
+    let cluster = TestClusterBuilder::new().build().await?;
+    let http_client = cluster.rpc_client();
+    let address = cluster.accounts.first().unwrap();
+    let recipient = cluster.accounts.last().unwrap();
+
+    let objects = http_client.get_objects_owned_by_address(*address).await?;
+    // More than the 1 SUI held by a mock gas coin, the dry run pays with the transferred coin.
+    let amount = 2_000_000_000;
+
+    let transaction_bytes: TransactionBytes = http_client
+        .transfer_sui(
+            *address,
+            objects.first().unwrap().object_id,
+            None,
+            *recipient,
+            Some(amount),
+            None,
+        )
+        .await?;
+    let data = transaction_bytes.to_data()?;
+
+    let keystore_path = cluster.swarm.dir().join(SUI_KEYSTORE_FILENAME);
+    let keystore = Keystore::from(FileBasedKeystore::new(&keystore_path)?);
+    let tx = to_sender_signed_transaction(data, keystore.get_key(address)?);
+    let (tx_bytes, signatures) = tx.to_tx_bytes_and_signatures();
+    let tx_response: SuiTransactionResponse = http_client
+        .execute_transaction(
+            tx_bytes,
+            signatures,
+            Some(SuiTransactionResponseOptions::new().with_effects()),
+            Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+        )
+        .await?;
+    assert_eq!(
+        tx_response.effects.unwrap().status(),
+        &SuiExecutionStatus::Success
+    );
+
+    let coins: CoinPage = http_client.get_coins(*recipient, None, None, None).await?;
+    assert!(coins.data.iter().any(|coin| coin.balance == amount));
+    Ok(())
+}
+
+#[sim_test]
+async fn test_pay_sui_with_estimated_gas_budget() -> Result<(), anyhow::Error> {
+    let cluster = TestClusterBuilder::new().build().await?;
+    let http_client = cluster.rpc_client();
+    let address = cluster.accounts.first().unwrap();
+    let recipient = cluster.accounts.last().unwrap();
+
+    let objects = http_client.get_objects_owned_by_address(*address).await?;
+    // More than the 1 SUI held by a mock gas coin, the dry run pays with the input coins.
+    let amount = 3_000_000_000;
+
+    let transaction_bytes: TransactionBytes = http_client
+        .pay_sui(
+            *address,
+            objects.iter().take(2).map(|o| o.object_id).collect(),
+            vec![*recipient],
+            vec![amount.into()],
+            None,
+            None,
+        )
+        .await?;
+    let data = transaction_bytes.to_data()?;
+
+    let keystore_path = cluster.swarm.dir().join(SUI_KEYSTORE_FILENAME);
+    let keystore = Keystore::from(FileBasedKeystore::new(&keystore_path)?);
+    let tx = to_sender_signed_transaction(data, keystore.get_key(address)?);
+    let (tx_bytes, signatures) = tx.to_tx_bytes_and_signatures();
+    let tx_response: SuiTransactionResponse = http_client
+        .execute_transaction(
+            tx_bytes,
+            signatures,
+            Some(SuiTransactionResponseOptions::new().with_effects()),
+            Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+        )
+        .await?;
+    assert_eq!(
+        tx_response.effects.unwrap().status(),
+        &SuiExecutionStatus::Success
+    );
+
+    let coins: CoinPage = http_client.get_coins(*recipient, None, None, None).await?;
+    assert!(coins.data.iter().any(|coin| coin.balance == amount));
+    Ok(())
+}
+
 #[sim_test]
 async fn test_publish() -> Result<(), anyhow::Error> {
     let cluster = TestClusterBuilder::new().build().await?;
@@ -113,7 +288,13 @@ async fn test_publish() -> Result<(), anyhow::Error> {
         .get_package_base64(/* with_unpublished_deps */ false);
 
     let transaction_bytes: TransactionBytes = http_client
-        .publish(*address, compiled_modules, Some(gas.object_id), 10000)
+        .publish(
+            *address,
+            compiled_modules,
+            Some(gas.object_id),
+            Some(10000),
+            None,
+        )
         .await?;
 
     let keystore_path = cluster.swarm.dir().join(SUI_KEYSTORE_FILENAME);
@@ -162,7 +343,8 @@ async fn test_move_call() -> Result<(), anyhow::Error> {
             vec![GAS::type_tag().into()],
             json_args,
             Some(gas.object_id),
-            10_000,
+            Some(10_000),
+            None,
             None,
         )
         .await?;
@@ -278,7 +460,13 @@ async fn test_get_metadata() -> Result<(), anyhow::Error> {
         .get_package_base64(/* with_unpublished_deps */ false);
 
     let transaction_bytes: TransactionBytes = http_client
-        .publish(*address, compiled_modules, Some(gas.object_id), 10000)
+        .publish(
+            *address,
+            compiled_modules,
+            Some(gas.object_id),
+            Some(10000),
+            None,
+        )
         .await?;
 
     let keystore_path = cluster.swarm.dir().join(SUI_KEYSTORE_FILENAME);
@@ -339,7 +527,13 @@ async fn test_get_total_supply() -> Result<(), anyhow::Error> {
         .get_package_base64(/* with_unpublished_deps */ false);
 
     let transaction_bytes: TransactionBytes = http_client
-        .publish(*address, compiled_modules, Some(gas.object_id), 10000)
+        .publish(
+            *address,
+            compiled_modules,
+            Some(gas.object_id),
+            Some(10000),
+            None,
+        )
         .await?;
 
     let keystore_path = cluster.swarm.dir().join(SUI_KEYSTORE_FILENAME);
@@ -414,7 +608,8 @@ async fn test_get_total_supply() -> Result<(), anyhow::Error> {
                 SuiJsonValue::from_str(&address.to_string()).unwrap(),
             ],
             Some(gas.object_id),
-            10_000,
+            Some(10_000),
+            None,
             None,
         )
         .await?;
@@ -456,7 +651,14 @@ async fn test_get_transaction() -> Result<(), anyhow::Error> {
     let mut tx_responses: Vec<SuiTransactionResponse> = Vec::new();
     for oref in &objects[..objects.len() - 1] {
         let transaction_bytes: TransactionBytes = http_client
-            .transfer_object(*address, oref.object_id, Some(gas_id), 1000, *address)
+            .transfer_object(
+                *address,
+                oref.object_id,
+                Some(gas_id),
+                Some(1000),
+                *address,
+                None,
+            )
             .await?;
         let keystore_path = cluster.swarm.dir().join(SUI_KEYSTORE_FILENAME);
         let keystore = Keystore::from(FileBasedKeystore::new(&keystore_path)?);
@@ -538,7 +740,14 @@ async fn test_get_fullnode_transaction() -> Result<(), anyhow::Error> {
         for oref in &objects[..objects.len() - 1] {
             let data = client
                 .transaction_builder()
-                .transfer_object(*address, oref.object_id, Some(gas_id), 1000, *address)
+                .transfer_object(
+                    *address,
+                    oref.object_id,
+                    Some(gas_id),
+                    Some(1000),
+                    *address,
+                    None,
+                )
                 .await?;
             let tx = to_sender_signed_transaction(data, keystore.get_key(address).unwrap());
 
@@ -684,7 +893,14 @@ async fn test_get_fullnode_events() -> Result<(), anyhow::Error> {
         for oref in &objects[..objects.len() - 1] {
             let data = client
                 .transaction_builder()
-                .transfer_object(*address, oref.object_id, Some(gas_id), 1000, *address)
+                .transfer_object(
+                    *address,
+                    oref.object_id,
+                    Some(gas_id),
+                    Some(1000),
+                    *address,
+                    None,
+                )
                 .await?;
             let tx = to_sender_signed_transaction(data, keystore.get_key(address).unwrap());
 
@@ -835,7 +1051,8 @@ async fn test_locked_sui() -> Result<(), anyhow::Error> {
                 SuiJsonValue::from_bcs_bytes(&bcs::to_bytes(&"20")?)?,
             ],
             None,
-            1000,
+            Some(1000),
+            None,
             None,
         )
         .await?;
@@ -895,7 +1112,8 @@ async fn test_staking() -> Result<(), anyhow::Error> {
             Some(1000000),
             validator,
             None,
-            10000,
+            Some(10000),
+            None,
         )
         .await?;
     let keystore_path = cluster.swarm.dir().join(SUI_KEYSTORE_FILENAME);
@@ -957,7 +1175,8 @@ async fn test_staking_multiple_coins() -> Result<(), anyhow::Error> {
             Some(1000000),
             validator,
             None,
-            10000,
+            Some(10000),
+            None,
         )
         .await?;
     let keystore_path = cluster.swarm.dir().join(SUI_KEYSTORE_FILENAME);
@@ -998,3 +1217,58 @@ async fn test_staking_multiple_coins() -> Result<(), anyhow::Error> {
 
     Ok(())
 }
+
+#[sim_test]
+async fn test_get_checkpoints() -> Result<(), anyhow::Error> {
+    let cluster = TestClusterBuilder::new().build().await?;
+    let http_client = cluster.rpc_client();
+
+    // Wait for a few checkpoints to be executed
+    while http_client.get_latest_checkpoint_sequence_number().await? < 4 {
+        sleep(Duration::from_millis(100)).await;
+    }
+
+    let first_page = http_client
+        .get_checkpoints(None, Some(2), None, None)
+        .await?;
+    assert_eq!(
+        vec![0, 1],
+        first_page
+            .data
+            .iter()
+            .map(|c| c.sequence_number)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(Some(1), first_page.next_cursor);
+    assert!(first_page.has_next_page);
+    // The genesis checkpoint contains the genesis transaction
+    assert!(!first_page.data[0].transactions.is_empty());
+
+    let second_page = http_client
+        .get_checkpoints(first_page.next_cursor, Some(2), None, Some(false))
+        .await?;
+    assert_eq!(
+        vec![2, 3],
+        second_page
+            .data
+            .iter()
+            .map(|c| c.sequence_number)
+            .collect::<Vec<_>>()
+    );
+    assert!(second_page.data.iter().all(|c| c.transactions.is_empty()));
+
+    let descending = http_client
+        .get_checkpoints(Some(3), None, Some(true), None)
+        .await?;
+    assert_eq!(
+        vec![2, 1, 0],
+        descending
+            .data
+            .iter()
+            .map(|c| c.sequence_number)
+            .collect::<Vec<_>>()
+    );
+    assert!(!descending.has_next_page);
+
+    Ok(())
+}
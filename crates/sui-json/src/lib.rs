@@ -5,6 +5,7 @@ use anyhow::{anyhow, bail};
 use fastcrypto::encoding::{Encoding, Hex};
 use move_binary_format::{
     access::ModuleAccess, binary_views::BinaryIndexedView, file_format::SignatureToken,
+    CompiledModule,
 };
 use move_core_types::account_address::AccountAddress;
 use move_core_types::u256::U256;
@@ -657,8 +658,63 @@ pub fn resolve_move_function_args(
     combined_args_json: Vec<SuiJsonValue>,
     allow_arbitrary_function_call: bool,
 ) -> Result<Vec<(SuiJsonCallArg, SignatureToken)>, anyhow::Error> {
+    let (module, parameters) = function_parameters(
+        package,
+        &module_ident,
+        &function,
+        combined_args_json.len(),
+        allow_arbitrary_function_call,
+    )?;
+    let view = BinaryIndexedView::Module(&module);
+
+    // Check that the args are valid and convert to the correct format
+    let call_args = resolve_call_args(&view, type_args, &combined_args_json, &parameters)?;
+    let tupled_call_args = call_args
+        .iter()
+        .zip(parameters.iter())
+        .map(|(arg, expected_type)| (arg.clone(), expected_type.clone()))
+        .collect::<Vec<(SuiJsonCallArg, SignatureToken)>>();
+    Ok(tupled_call_args)
+}
+
+/// Resolve the args of a function called from a programmable transaction, where an arg can also
+/// be the result of an earlier command. Those are passed as `None` and left unresolved, their type
+/// is only checked when the transaction is executed.
+pub fn resolve_programmable_move_call_args(
+    package: &MovePackage,
+    module_ident: Identifier,
+    function: Identifier,
+    type_args: &[TypeTag],
+    args: Vec<Option<SuiJsonValue>>,
+) -> Result<Vec<(Option<SuiJsonCallArg>, SignatureToken)>, anyhow::Error> {
+    // Visibility is checked by the execution, programmable transactions can call public functions
+    let (module, parameters) =
+        function_parameters(package, &module_ident, &function, args.len(), true)?;
+    let view = BinaryIndexedView::Module(&module);
+    args.iter()
+        .zip(parameters)
+        .enumerate()
+        .map(|(idx, (arg, param))| {
+            let arg = arg
+                .as_ref()
+                .map(|arg| resolve_call_arg(&view, type_args, idx, arg, &param))
+                .transpose()?;
+            Ok((arg, param))
+        })
+        .collect()
+}
+
+/// Returns the module defining `function` and the parameters of the function, after checking that
+/// `num_args` args are expected.
+fn function_parameters(
+    package: &MovePackage,
+    module_ident: &Identifier,
+    function: &Identifier,
+    num_args: usize,
+    allow_arbitrary_function_call: bool,
+) -> Result<(CompiledModule, Vec<SignatureToken>), anyhow::Error> {
     // Extract the expected function signature
-    let module = package.deserialize_module(&module_ident)?;
+    let module = package.deserialize_module(module_ident)?;
     let function_str = function.as_ident_str();
     let fdef = module
         .function_defs
@@ -674,7 +730,7 @@ pub fn resolve_move_function_args(
             )
         })?;
     let function_signature = module.function_handle_at(fdef.function);
-    let parameters = &module.signature_at(function_signature.parameters).0;
+    let parameters = module.signature_at(function_signature.parameters).0.clone();
 
     if !allow_arbitrary_function_call && !fdef.is_entry {
         bail!(
@@ -691,22 +747,10 @@ pub fn resolve_move_function_args(
         Some(param) if is_tx_context(&view, param) != TxContextKind::None => parameters.len() - 1,
         _ => parameters.len(),
     };
-    if combined_args_json.len() != expected_len {
-        bail!(
-            "Expected {} args, found {}",
-            expected_len,
-            combined_args_json.len()
-        );
+    if num_args != expected_len {
+        bail!("Expected {} args, found {}", expected_len, num_args);
     }
-
-    // Check that the args are valid and convert to the correct format
-    let call_args = resolve_call_args(&view, type_args, &combined_args_json, parameters)?;
-    let tupled_call_args = call_args
-        .iter()
-        .zip(parameters.iter())
-        .map(|(arg, expected_type)| (arg.clone(), expected_type.clone()))
-        .collect::<Vec<(SuiJsonCallArg, SignatureToken)>>();
-    Ok(tupled_call_args)
+    Ok((module, parameters))
 }
 
 fn convert_string_to_u256(s: &str) -> Result<U256, anyhow::Error> {
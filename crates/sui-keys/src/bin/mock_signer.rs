@@ -0,0 +1,24 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A reference external signer serving the keys of a plaintext keystore file over stdin and
+//! stdout, see [sui_keys::external_signer] for the protocol.
+//!
+//! Usage: sui-mock-signer <path to sui.keystore>
+
+use std::io;
+use std::path::PathBuf;
+
+use anyhow::anyhow;
+use sui_keys::external_signer::MockSigner;
+use sui_keys::keystore::FileBasedKeystore;
+
+fn main() -> Result<(), anyhow::Error> {
+    let path = std::env::args()
+        .nth(1)
+        .map(PathBuf::from)
+        .ok_or_else(|| anyhow!("Usage: sui-mock-signer <path to sui.keystore>"))?;
+    let signer = MockSigner::new(FileBasedKeystore::new(&path)?);
+    signer.serve(io::stdin().lock(), io::stdout().lock())?;
+    Ok(())
+}
@@ -0,0 +1,369 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A keystore backed by an external signing process. Requests are exchanged as newline delimited
+//! JSON over a Unix socket, or over the stdin and stdout of a spawned signer process:
+//!
+//! ```text
+//! -> {"method":"keys"}
+//! <- {"keys":["<base64 flag || pk>", ...]}
+//! -> {"method":"signSecure","params":{"address":"0x..","intent":{..},"intentMessage":"<base64 bcs>"}}
+//! <- {"signature":"<base64 flag || sig || pk>"}
+//! <- {"error":"<reason>"}
+//! ```
+//!
+//! `signSecure` carries the BCS bytes of the [IntentMessage] so the signer can inspect what it is
+//! signing before producing the signature.
+
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, BufReader, Write};
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+#[cfg(unix)]
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::{Child, Command, Stdio};
+use std::sync::Mutex;
+#[cfg(unix)]
+use std::thread::JoinHandle;
+
+use anyhow::anyhow;
+use fastcrypto::encoding::{Base64, Encoding};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use shared_crypto::intent::{Intent, IntentMessage};
+use sui_types::base_types::SuiAddress;
+use sui_types::crypto::{
+    EncodeDecodeBase64, PublicKey, Signature, Signer, SuiKeyPair, SuiSignature,
+};
+
+use crate::keystore::{AccountKeystore, Aliases};
+
+/// How to reach the external signer.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ExternalSignerConfig {
+    /// Connect to a signing daemon listening on a Unix socket.
+    UnixSocket(PathBuf),
+    /// Spawn the signer and talk to it over its stdin and stdout.
+    Command {
+        program: PathBuf,
+        #[serde(default)]
+        args: Vec<String>,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "method", content = "params", rename_all = "camelCase")]
+pub enum SignerRequest {
+    /// List the public keys held by the signer.
+    Keys,
+    /// Sign the raw message bytes (Base64 encoded).
+    Sign {
+        address: SuiAddress,
+        message: String,
+    },
+    /// Sign the BCS serialized [IntentMessage] (Base64 encoded).
+    SignSecure {
+        address: SuiAddress,
+        intent: Intent,
+        #[serde(rename = "intentMessage")]
+        intent_message: String,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SignerResponse {
+    /// Public keys encoded as Base64 `flag || pk`.
+    Keys(Vec<String>),
+    Signature(Signature),
+    Error(String),
+}
+
+struct SignerConnection {
+    reader: Box<dyn BufRead + Send>,
+    writer: Box<dyn Write + Send>,
+    child: Option<Child>,
+}
+
+impl SignerConnection {
+    fn connect(config: &ExternalSignerConfig) -> Result<Self, anyhow::Error> {
+        match config {
+            #[cfg(unix)]
+            ExternalSignerConfig::UnixSocket(path) => {
+                let stream = UnixStream::connect(path)
+                    .map_err(|e| anyhow!("Cannot connect to external signer at {:?}: {e}", path))?;
+                Ok(Self {
+                    reader: Box::new(BufReader::new(stream.try_clone()?)),
+                    writer: Box::new(stream),
+                    child: None,
+                })
+            }
+            #[cfg(not(unix))]
+            ExternalSignerConfig::UnixSocket(_) => Err(anyhow!(
+                "Unix socket signers are not supported on this platform"
+            )),
+            ExternalSignerConfig::Command { program, args } => {
+                let mut child = Command::new(program)
+                    .args(args)
+                    .stdin(Stdio::piped())
+                    .stdout(Stdio::piped())
+                    .spawn()
+                    .map_err(|e| anyhow!("Cannot start external signer {:?}: {e}", program))?;
+                let stdin = child.stdin.take().expect("stdin is piped");
+                let stdout = child.stdout.take().expect("stdout is piped");
+                Ok(Self {
+                    reader: Box::new(BufReader::new(stdout)),
+                    writer: Box::new(stdin),
+                    child: Some(child),
+                })
+            }
+        }
+    }
+
+    fn request(&mut self, request: &SignerRequest) -> Result<SignerResponse, anyhow::Error> {
+        let mut line = serde_json::to_string(request)?;
+        line.push('\n');
+        self.writer.write_all(line.as_bytes())?;
+        self.writer.flush()?;
+
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Err(anyhow!("External signer closed the connection"));
+        }
+        match serde_json::from_str(&line)? {
+            SignerResponse::Error(e) => Err(anyhow!("External signer error: {e}")),
+            response => Ok(response),
+        }
+    }
+}
+
+impl Drop for SignerConnection {
+    fn drop(&mut self) {
+        if let Some(child) = &mut self.child {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+/// A keystore that never holds private keys, signing requests are forwarded to an external
+/// signer. The signer's public keys are fetched once when connecting.
+pub struct ExternalKeystore {
+    config: ExternalSignerConfig,
+    keys: BTreeMap<SuiAddress, PublicKey>,
+    /// Aliases are only kept in memory, the signer's configuration has no file to persist them.
+    aliases: Aliases,
+    connection: Mutex<SignerConnection>,
+}
+
+impl ExternalKeystore {
+    pub fn new(config: ExternalSignerConfig) -> Result<Self, anyhow::Error> {
+        let mut connection = SignerConnection::connect(&config)?;
+        let keys = match connection.request(&SignerRequest::Keys)? {
+            SignerResponse::Keys(keys) => keys
+                .iter()
+                .map(|pk| PublicKey::decode_base64(pk).map(|pk| ((&pk).into(), pk)))
+                .collect::<Result<BTreeMap<_, _>, _>>()
+                .map_err(|e| anyhow!("Invalid public key from external signer: {e}"))?,
+            response => {
+                return Err(anyhow!(
+                    "Unexpected response from external signer: {:?}",
+                    response
+                ))
+            }
+        };
+        Ok(Self {
+            config,
+            keys,
+            aliases: Aliases::default(),
+            connection: Mutex::new(connection),
+        })
+    }
+
+    pub fn config(&self) -> &ExternalSignerConfig {
+        &self.config
+    }
+
+    fn request_signature(
+        &self,
+        address: &SuiAddress,
+        request: SignerRequest,
+    ) -> Result<Signature, anyhow::Error> {
+        if !self.keys.contains_key(address) {
+            return Err(anyhow!("Cannot find key for address: [{address}]"));
+        }
+        let response = self
+            .connection
+            .lock()
+            .map_err(|_| anyhow!("External signer connection poisoned"))?
+            .request(&request)?;
+        let signature = match response {
+            SignerResponse::Signature(signature) => signature,
+            response => {
+                return Err(anyhow!(
+                    "Unexpected response from external signer: {:?}",
+                    response
+                ))
+            }
+        };
+        let signer: SuiAddress = (&signature.to_public_key()?).into();
+        if &signer != address {
+            return Err(anyhow!(
+                "External signer signed for [{signer}] instead of [{address}]"
+            ));
+        }
+        Ok(signature)
+    }
+}
+
+impl Serialize for ExternalKeystore {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.config.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ExternalKeystore {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+        ExternalKeystore::new(ExternalSignerConfig::deserialize(deserializer)?)
+            .map_err(D::Error::custom)
+    }
+}
+
+impl AccountKeystore for ExternalKeystore {
+    #[warn(deprecated)]
+    fn sign(&self, address: &SuiAddress, msg: &[u8]) -> Result<Signature, signature::Error> {
+        let request = SignerRequest::Sign {
+            address: *address,
+            message: Base64::encode(msg),
+        };
+        self.request_signature(address, request)
+            .map_err(|e| signature::Error::from_source(e.to_string()))
+    }
+
+    fn sign_secure<T>(
+        &self,
+        address: &SuiAddress,
+        msg: &T,
+        intent: Intent,
+    ) -> Result<Signature, signature::Error>
+    where
+        T: Serialize,
+    {
+        let intent_msg = IntentMessage::new(intent.clone(), msg);
+        let request = SignerRequest::SignSecure {
+            address: *address,
+            intent,
+            intent_message: Base64::encode(
+                bcs::to_bytes(&intent_msg).map_err(signature::Error::from_source)?,
+            ),
+        };
+        let signature = self
+            .request_signature(address, request)
+            .map_err(|e| signature::Error::from_source(e.to_string()))?;
+        signature
+            .verify_secure(&intent_msg, *address)
+            .map_err(signature::Error::from_source)?;
+        Ok(signature)
+    }
+
+    fn add_key(&mut self, _keypair: SuiKeyPair) -> Result<(), anyhow::Error> {
+        Err(anyhow!("Cannot add keys to an external signer keystore"))
+    }
+
+    fn keys(&self) -> Vec<PublicKey> {
+        self.keys.values().cloned().collect()
+    }
+
+    fn get_key(&self, address: &SuiAddress) -> Result<&SuiKeyPair, anyhow::Error> {
+        Err(anyhow!(
+            "The key for address [{address}] is held by the external signer"
+        ))
+    }
+
+    fn aliases(&self) -> &Aliases {
+        &self.aliases
+    }
+
+    fn aliases_mut(&mut self) -> &mut Aliases {
+        &mut self.aliases
+    }
+}
+
+/// A reference signer serving the external signer protocol from a local keystore, used for tests
+/// and as an example for signer implementations.
+pub struct MockSigner<K> {
+    keystore: K,
+}
+
+impl<K: AccountKeystore + 'static> MockSigner<K> {
+    pub fn new(keystore: K) -> Self {
+        Self { keystore }
+    }
+
+    /// Serves requests read from `reader` until it is closed.
+    pub fn serve(&self, reader: impl BufRead, mut writer: impl Write) -> io::Result<()> {
+        for line in reader.lines() {
+            let response = match serde_json::from_str(&line?) {
+                Ok(request) => self.handle(request),
+                Err(e) => SignerResponse::Error(format!("Invalid request: {e}")),
+            };
+            let mut line = serde_json::to_string(&response)?;
+            line.push('\n');
+            writer.write_all(line.as_bytes())?;
+            writer.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Binds a Unix socket at `path` and serves each connection on its own thread.
+    #[cfg(unix)]
+    pub fn spawn_unix_socket(self, path: &Path) -> io::Result<JoinHandle<()>> {
+        let listener = UnixListener::bind(path)?;
+        let signer = std::sync::Arc::new(self);
+        Ok(std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(stream) = stream else {
+                    continue;
+                };
+                let signer = signer.clone();
+                std::thread::spawn(move || {
+                    if let Ok(reader) = stream.try_clone() {
+                        let _ = signer.serve(BufReader::new(reader), stream);
+                    }
+                });
+            }
+        }))
+    }
+
+    fn handle(&self, request: SignerRequest) -> SignerResponse {
+        match request {
+            SignerRequest::Keys => SignerResponse::Keys(
+                self.keystore
+                    .keys()
+                    .iter()
+                    .map(EncodeDecodeBase64::encode_base64)
+                    .collect(),
+            ),
+            // A signature over the BCS bytes of the intent message is what `Signature::new_secure`
+            // produces, a real signer would inspect the intent and the message here.
+            SignerRequest::Sign { address, message }
+            | SignerRequest::SignSecure {
+                address,
+                intent_message: message,
+                ..
+            } => match (self.keystore.get_key(&address), Base64::decode(&message)) {
+                (Ok(key), Ok(message)) => SignerResponse::Signature(key.sign(&message)),
+                (Err(e), _) => SignerResponse::Error(e.to_string()),
+                (_, Err(e)) => SignerResponse::Error(format!("Invalid message: {e}")),
+            },
+        }
+    }
+}
@@ -0,0 +1,59 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use sui_types::base_types::SuiAddress;
+
+/// An address given either as a hex [SuiAddress] or as the alias of a keystore address, resolved
+/// with [crate::keystore::AccountKeystore::get_address].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum KeyIdentity {
+    Address(SuiAddress),
+    Alias(String),
+}
+
+impl KeyIdentity {
+    /// Aliases start with a letter and contain only letters, digits, `-` and `_`.
+    pub fn validate_alias(alias: &str) -> Result<(), anyhow::Error> {
+        let mut chars = alias.chars();
+        let valid = chars.next().map_or(false, |c| c.is_ascii_alphabetic())
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if valid {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "Invalid alias [{alias}], an alias must start with a letter and contain only letters, digits, '-' and '_'"
+            ))
+        }
+    }
+}
+
+impl FromStr for KeyIdentity {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(address) = SuiAddress::from_str(s) {
+            return Ok(KeyIdentity::Address(address));
+        }
+        Self::validate_alias(s)?;
+        Ok(KeyIdentity::Alias(s.to_string()))
+    }
+}
+
+impl Display for KeyIdentity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KeyIdentity::Address(address) => write!(f, "{address}"),
+            KeyIdentity::Alias(alias) => write!(f, "{alias}"),
+        }
+    }
+}
+
+impl From<SuiAddress> for KeyIdentity {
+    fn from(address: SuiAddress) -> Self {
+        KeyIdentity::Address(address)
+    }
+}
@@ -1,18 +1,24 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
 use anyhow::anyhow;
 use bip32::DerivationPath;
 use bip39::{Language, Mnemonic, Seed};
-use rand::{rngs::StdRng, SeedableRng};
+use fastcrypto::encoding::{Base64, Encoding};
+use hmac::Hmac;
+use once_cell::sync::OnceCell;
+use rand::{rngs::StdRng, RngCore, SeedableRng};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sha2::Sha256;
 use shared_crypto::intent::{Intent, IntentMessage};
 use std::collections::BTreeMap;
 use std::fmt::Write;
 use std::fmt::{Display, Formatter};
 use std::fs;
 use std::fs::File;
-use std::io::BufReader;
+use std::io::{self, BufReader};
 use std::path::{Path, PathBuf};
 
 use sui_types::base_types::SuiAddress;
@@ -20,14 +26,19 @@ use sui_types::crypto::{
     enum_dispatch, get_key_pair_from_rng, EncodeDecodeBase64, PublicKey, Signature,
     SignatureScheme, Signer, SuiKeyPair,
 };
+use zeroize::Zeroizing;
 
+use crate::external_signer::ExternalKeystore;
 use crate::key_derive::{derive_key_pair_from_path, generate_new_key};
+use crate::key_identity::KeyIdentity;
 
 #[derive(Serialize, Deserialize)]
 #[enum_dispatch(AccountKeystore)]
 pub enum Keystore {
     File(FileBasedKeystore),
     InMem(InMemKeystore),
+    Encrypted(EncryptedFileKeystore),
+    External(ExternalKeystore),
 }
 #[enum_dispatch]
 pub trait AccountKeystore: Send + Sync {
@@ -36,6 +47,8 @@ pub trait AccountKeystore: Send + Sync {
     fn add_key(&mut self, keypair: SuiKeyPair) -> Result<(), anyhow::Error>;
     fn keys(&self) -> Vec<PublicKey>;
     fn get_key(&self, address: &SuiAddress) -> Result<&SuiKeyPair, anyhow::Error>;
+    fn aliases(&self) -> &Aliases;
+    fn aliases_mut(&mut self) -> &mut Aliases;
 
     fn sign_secure<T>(
         &self,
@@ -49,6 +62,29 @@ pub trait AccountKeystore: Send + Sync {
         self.keys().iter().map(|k| k.into()).collect()
     }
 
+    fn get_alias(&self, address: &SuiAddress) -> Option<&str> {
+        self.aliases().get_alias(address)
+    }
+
+    /// Attaches `alias` to a keystore address, replacing its previous alias.
+    fn set_alias(&mut self, address: &SuiAddress, alias: String) -> Result<(), anyhow::Error> {
+        if !self.addresses().contains(address) {
+            return Err(anyhow!("Cannot find key for address: [{address}]"));
+        }
+        self.aliases_mut().set(*address, alias)
+    }
+
+    /// Resolves an address or an alias of a keystore address.
+    fn get_address(&self, identity: &KeyIdentity) -> Result<SuiAddress, anyhow::Error> {
+        match identity {
+            KeyIdentity::Address(address) => Ok(*address),
+            KeyIdentity::Alias(alias) => self
+                .aliases()
+                .get_address(alias)
+                .ok_or_else(|| anyhow!("Cannot find address for alias [{alias}]")),
+        }
+    }
+
     fn generate_and_add_new_key(
         &mut self,
         key_scheme: SignatureScheme,
@@ -91,6 +127,17 @@ impl Display for Keystore {
                 writeln!(writer, "Keystore Type : InMem")?;
                 write!(f, "{}", writer)
             }
+            Keystore::Encrypted(file) => {
+                writeln!(writer, "Keystore Type : Encrypted")?;
+                writeln!(writer, "Keystore Path : {:?}", file.path)?;
+                write!(writer, "Keystore Locked : {}", file.is_locked())?;
+                write!(f, "{}", writer)
+            }
+            Keystore::External(external) => {
+                writeln!(writer, "Keystore Type : External")?;
+                write!(writer, "Keystore Signer : {:?}", external.config())?;
+                write!(f, "{}", writer)
+            }
         }
     }
 }
@@ -98,6 +145,7 @@ impl Display for Keystore {
 #[derive(Default)]
 pub struct FileBasedKeystore {
     keys: BTreeMap<SuiAddress, SuiKeyPair>,
+    aliases: Aliases,
     path: Option<PathBuf>,
 }
 
@@ -173,11 +221,25 @@ impl AccountKeystore for FileBasedKeystore {
             None => Err(anyhow!("Cannot find key for address: [{address}]")),
         }
     }
+
+    fn aliases(&self) -> &Aliases {
+        &self.aliases
+    }
+
+    fn aliases_mut(&mut self) -> &mut Aliases {
+        &mut self.aliases
+    }
 }
 
 impl FileBasedKeystore {
     pub fn new(path: &PathBuf) -> Result<Self, anyhow::Error> {
         let keys = if path.exists() {
+            if EncryptedFileKeystore::is_encrypted(path) {
+                return Err(anyhow!(
+                    "Keystore file {:?} is encrypted, use the Encrypted keystore type to open it",
+                    path
+                ));
+            }
             let reader = BufReader::new(File::open(path)?);
             let kp_strings: Vec<String> = serde_json::from_reader(reader)?;
             kp_strings
@@ -194,14 +256,20 @@ impl FileBasedKeystore {
 
         Ok(Self {
             keys,
+            aliases: Aliases::new(path)?,
             path: Some(path.to_path_buf()),
         })
     }
 
     pub fn set_path(&mut self, path: &Path) {
+        self.aliases.set_keystore_path(path);
         self.path = Some(path.to_path_buf());
     }
 
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
     pub fn save(&self) -> Result<(), anyhow::Error> {
         if let Some(path) = &self.path {
             let store = serde_json::to_string_pretty(
@@ -222,9 +290,399 @@ impl FileBasedKeystore {
     }
 }
 
+/// Environment variable holding the passphrase used to unlock an [EncryptedFileKeystore] when it
+/// is loaded from the client config.
+pub const SUI_KEYSTORE_PASSWORD_ENV: &str = "SUI_KEYSTORE_PASSWORD";
+
+const ENCRYPTED_KEYSTORE_VERSION: u32 = 1;
+const KDF_PBKDF2_HMAC_SHA256: &str = "pbkdf2-hmac-sha256";
+const CIPHER_AES_256_GCM: &str = "aes-256-gcm";
+const DEFAULT_KDF_ITERATIONS: u32 = 600_000;
+/// Keystore files with fewer KDF iterations are rejected, their passphrase is too cheap to guess.
+const MIN_KDF_ITERATIONS: u32 = 100_000;
+const SALT_LENGTH: usize = 16;
+const NONCE_LENGTH: usize = 12;
+
+/// The on-disk format of the [EncryptedFileKeystore]. Public keys are kept in the clear so the
+/// addresses can be listed while the keystore is locked.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EncryptedKeystoreFile {
+    version: u32,
+    kdf: KdfParams,
+    cipher: CipherParams,
+    public_keys: Vec<String>,
+    ciphertext: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct KdfParams {
+    name: String,
+    salt: String,
+    iterations: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CipherParams {
+    name: String,
+    nonce: String,
+}
+
+/// Asks for the passphrase of a locked [EncryptedFileKeystore].
+pub type PassphrasePrompt = Box<dyn Fn() -> Result<String, anyhow::Error> + Send + Sync>;
+
+/// A file based keystore with the keypairs encrypted at rest with AES-256-GCM, using a key derived
+/// from a passphrase with PBKDF2-HMAC-SHA256. The keystore is locked when loaded from disk and
+/// must be unlocked before signing, either explicitly or through its [PassphrasePrompt].
+pub struct EncryptedFileKeystore {
+    public_keys: BTreeMap<SuiAddress, PublicKey>,
+    /// Only set while the keystore is unlocked.
+    unlocked: OnceCell<UnlockedKeys>,
+    passphrase_prompt: Option<PassphrasePrompt>,
+    aliases: Aliases,
+    salt: Vec<u8>,
+    iterations: u32,
+    nonce: Vec<u8>,
+    ciphertext: Vec<u8>,
+    path: Option<PathBuf>,
+}
+
+struct UnlockedKeys {
+    keys: BTreeMap<SuiAddress, SuiKeyPair>,
+    /// The key derived from the passphrase.
+    cipher_key: Zeroizing<[u8; 32]>,
+}
+
+impl Serialize for EncryptedFileKeystore {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(
+            self.path
+                .as_ref()
+                .unwrap_or(&PathBuf::default())
+                .to_str()
+                .unwrap_or(""),
+        )
+    }
+}
+
+impl<'de> Deserialize<'de> for EncryptedFileKeystore {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+        let mut keystore =
+            EncryptedFileKeystore::new(&PathBuf::from(String::deserialize(deserializer)?))
+                .map_err(D::Error::custom)?;
+        if let Ok(password) = std::env::var(SUI_KEYSTORE_PASSWORD_ENV) {
+            keystore.unlock(&password).map_err(D::Error::custom)?;
+        }
+        Ok(keystore)
+    }
+}
+
+impl AccountKeystore for EncryptedFileKeystore {
+    #[warn(deprecated)]
+    fn sign(&self, address: &SuiAddress, msg: &[u8]) -> Result<Signature, signature::Error> {
+        Ok(self
+            .get_key(address)
+            .map_err(|e| signature::Error::from_source(e.to_string()))?
+            .sign(msg))
+    }
+
+    fn sign_secure<T>(
+        &self,
+        address: &SuiAddress,
+        msg: &T,
+        intent: Intent,
+    ) -> Result<Signature, signature::Error>
+    where
+        T: Serialize,
+    {
+        Ok(Signature::new_secure(
+            &IntentMessage::new(intent, msg),
+            self.get_key(address)
+                .map_err(|e| signature::Error::from_source(e.to_string()))?,
+        ))
+    }
+
+    fn add_key(&mut self, keypair: SuiKeyPair) -> Result<(), anyhow::Error> {
+        self.unlocked_keys()?;
+        let public_key = keypair.public();
+        let address: SuiAddress = (&public_key).into();
+        self.public_keys.insert(address, public_key);
+        if let Some(unlocked) = self.unlocked.get_mut() {
+            unlocked.keys.insert(address, keypair);
+        }
+        self.save()?;
+        Ok(())
+    }
+
+    fn keys(&self) -> Vec<PublicKey> {
+        self.public_keys.values().cloned().collect()
+    }
+
+    fn get_key(&self, address: &SuiAddress) -> Result<&SuiKeyPair, anyhow::Error> {
+        match self.unlocked_keys()?.keys.get(address) {
+            Some(key) => Ok(key),
+            None => Err(anyhow!("Cannot find key for address: [{address}]")),
+        }
+    }
+
+    fn aliases(&self) -> &Aliases {
+        &self.aliases
+    }
+
+    fn aliases_mut(&mut self) -> &mut Aliases {
+        &mut self.aliases
+    }
+}
+
+impl EncryptedFileKeystore {
+    /// Loads the encrypted keystore at `path`, the keystore stays locked until [Self::unlock] is called.
+    pub fn new(path: &PathBuf) -> Result<Self, anyhow::Error> {
+        let reader = BufReader::new(File::open(path)?);
+        let file: EncryptedKeystoreFile = serde_json::from_reader(reader)
+            .map_err(|e| anyhow!("Invalid encrypted keystore file {:?}: {e}", path))?;
+        if file.version != ENCRYPTED_KEYSTORE_VERSION
+            || file.kdf.name != KDF_PBKDF2_HMAC_SHA256
+            || file.cipher.name != CIPHER_AES_256_GCM
+        {
+            return Err(anyhow!(
+                "Unsupported encrypted keystore file {:?}, version: {}, kdf: {}, cipher: {}",
+                path,
+                file.version,
+                file.kdf.name,
+                file.cipher.name
+            ));
+        }
+        let public_keys = file
+            .public_keys
+            .iter()
+            .map(|pk| PublicKey::decode_base64(pk).map(|pk| ((&pk).into(), pk)))
+            .collect::<Result<BTreeMap<_, _>, _>>()
+            .map_err(|e| anyhow!("Invalid public key in keystore file {:?}: {e}", path))?;
+        let nonce = Base64::decode(&file.cipher.nonce)?;
+        if nonce.len() != NONCE_LENGTH {
+            return Err(anyhow!("Invalid nonce length in keystore file {:?}", path));
+        }
+        if file.kdf.iterations < MIN_KDF_ITERATIONS {
+            return Err(anyhow!(
+                "Keystore file {:?} uses {} KDF iterations, at least {} are required",
+                path,
+                file.kdf.iterations,
+                MIN_KDF_ITERATIONS
+            ));
+        }
+        Ok(Self {
+            public_keys,
+            unlocked: OnceCell::new(),
+            passphrase_prompt: None,
+            aliases: Aliases::new(path)?,
+            salt: Base64::decode(&file.kdf.salt)?,
+            iterations: file.kdf.iterations,
+            nonce,
+            ciphertext: Base64::decode(&file.ciphertext)?,
+            path: Some(path.to_path_buf()),
+        })
+    }
+
+    /// Creates an empty encrypted keystore at `path` protected by `password`. The returned keystore
+    /// is unlocked.
+    pub fn create(path: &Path, password: &str) -> Result<Self, anyhow::Error> {
+        Self::create_with_keys(path, password, BTreeMap::new(), Aliases::new(path)?)
+    }
+
+    /// Encrypts the keys of a plaintext [FileBasedKeystore] with `password`, overwriting the
+    /// plaintext keystore file.
+    pub fn from_file_keystore(
+        keystore: FileBasedKeystore,
+        password: &str,
+    ) -> Result<Self, anyhow::Error> {
+        let path = keystore
+            .path
+            .ok_or_else(|| anyhow!("Cannot encrypt a keystore without a file path"))?;
+        Self::create_with_keys(&path, password, keystore.keys, keystore.aliases)
+    }
+
+    fn create_with_keys(
+        path: &Path,
+        password: &str,
+        keys: BTreeMap<SuiAddress, SuiKeyPair>,
+        aliases: Aliases,
+    ) -> Result<Self, anyhow::Error> {
+        let mut salt = vec![0u8; SALT_LENGTH];
+        rand::thread_rng().fill_bytes(&mut salt);
+        let public_keys = keys
+            .iter()
+            .map(|(address, key)| (*address, key.public()))
+            .collect();
+        let unlocked = OnceCell::new();
+        let _ = unlocked.set(UnlockedKeys {
+            keys,
+            cipher_key: derive_cipher_key(password, &salt, DEFAULT_KDF_ITERATIONS),
+        });
+        let mut keystore = Self {
+            public_keys,
+            unlocked,
+            passphrase_prompt: None,
+            aliases,
+            salt,
+            iterations: DEFAULT_KDF_ITERATIONS,
+            nonce: vec![],
+            ciphertext: vec![],
+            path: Some(path.to_path_buf()),
+        };
+        keystore.save()?;
+        Ok(keystore)
+    }
+
+    /// Returns true if the file at `path` is an encrypted keystore.
+    pub fn is_encrypted(path: &Path) -> bool {
+        fs::read(path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<EncryptedKeystoreFile>(&bytes).ok())
+            .is_some()
+    }
+
+    /// Decrypts the keypairs with `password`, the keystore can sign until [Self::lock] is called.
+    pub fn unlock(&mut self, password: &str) -> Result<(), anyhow::Error> {
+        let unlocked = self.decrypt(password)?;
+        self.unlocked = OnceCell::new();
+        let _ = self.unlocked.set(unlocked);
+        Ok(())
+    }
+
+    /// Sets the prompt asked for the passphrase when the keystore is used while locked.
+    pub fn set_passphrase_prompt(&mut self, prompt: PassphrasePrompt) {
+        self.passphrase_prompt = Some(prompt);
+    }
+
+    /// The decrypted keys, asking the [PassphrasePrompt] to unlock the keystore if needed.
+    fn unlocked_keys(&self) -> Result<&UnlockedKeys, anyhow::Error> {
+        self.unlocked
+            .get_or_try_init(|| match &self.passphrase_prompt {
+                Some(prompt) => self.decrypt(&Zeroizing::new(prompt()?)),
+                None => Err(anyhow!(
+                    "Keystore is locked, unlock it before using its keys"
+                )),
+            })
+    }
+
+    fn decrypt(&self, password: &str) -> Result<UnlockedKeys, anyhow::Error> {
+        let cipher_key = derive_cipher_key(password, &self.salt, self.iterations);
+        let plaintext = Zeroizing::new(
+            Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&cipher_key[..]))
+                .decrypt(Nonce::from_slice(&self.nonce), self.ciphertext.as_ref())
+                .map_err(|_| anyhow!("Incorrect passphrase for keystore {:?}", self.path))?,
+        );
+        let kp_strings: Zeroizing<Vec<String>> =
+            Zeroizing::new(serde_json::from_slice(&plaintext)?);
+        let keys = kp_strings
+            .iter()
+            .map(|kpstr| {
+                let key = SuiKeyPair::decode_base64(kpstr);
+                key.map(|k| (Into::<SuiAddress>::into(&k.public()), k))
+            })
+            .collect::<Result<BTreeMap<_, _>, _>>()
+            .map_err(|e| anyhow!("Invalid Keypair in keystore {:?}: {e}", self.path))?;
+        if !keys.keys().eq(self.public_keys.keys()) {
+            return Err(anyhow!(
+                "Public keys of keystore {:?} do not match its encrypted keypairs",
+                self.path
+            ));
+        }
+        Ok(UnlockedKeys { keys, cipher_key })
+    }
+
+    /// Drops the decrypted keypairs and the derived key from memory.
+    pub fn lock(&mut self) {
+        self.unlocked.take();
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.unlocked.get().is_none()
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Re-encrypts the keypairs under a fresh nonce and writes the keystore to its path.
+    pub fn save(&mut self) -> Result<(), anyhow::Error> {
+        let unlocked = self.unlocked_keys()?;
+        let kp_strings = Zeroizing::new(
+            unlocked
+                .keys
+                .values()
+                .map(EncodeDecodeBase64::encode_base64)
+                .collect::<Vec<_>>(),
+        );
+        let plaintext = Zeroizing::new(serde_json::to_vec(&*kp_strings)?);
+        let mut nonce = vec![0u8; NONCE_LENGTH];
+        rand::thread_rng().fill_bytes(&mut nonce);
+        let ciphertext = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&unlocked.cipher_key[..]))
+            .encrypt(Nonce::from_slice(&nonce), plaintext.as_ref())
+            .map_err(|e| anyhow!("Failed to encrypt keystore: {e}"))?;
+        self.ciphertext = ciphertext;
+        self.nonce = nonce;
+        if let Some(path) = &self.path {
+            let file = EncryptedKeystoreFile {
+                version: ENCRYPTED_KEYSTORE_VERSION,
+                kdf: KdfParams {
+                    name: KDF_PBKDF2_HMAC_SHA256.to_string(),
+                    salt: Base64::encode(&self.salt),
+                    iterations: self.iterations,
+                },
+                cipher: CipherParams {
+                    name: CIPHER_AES_256_GCM.to_string(),
+                    nonce: Base64::encode(&self.nonce),
+                },
+                public_keys: self
+                    .public_keys
+                    .values()
+                    .map(EncodeDecodeBase64::encode_base64)
+                    .collect(),
+                ciphertext: Base64::encode(&self.ciphertext),
+            };
+            write_atomically(path, serde_json::to_string_pretty(&file)?.as_bytes())?
+        }
+        Ok(())
+    }
+}
+
+fn derive_cipher_key(password: &str, salt: &[u8], iterations: u32) -> Zeroizing<[u8; 32]> {
+    let mut key = Zeroizing::new([0u8; 32]);
+    pbkdf2::pbkdf2::<Hmac<Sha256>>(password.as_bytes(), salt, iterations, &mut key[..]);
+    key
+}
+
+/// Writes `contents` to a temporary file next to `path` and renames it over `path`, so that a
+/// crash leaves either the previous or the new file behind, never a truncated one.
+fn write_atomically(path: &Path, contents: &[u8]) -> Result<(), anyhow::Error> {
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_path);
+    let mut file = File::create(&tmp_path)?;
+    io::Write::write_all(&mut file, contents)?;
+    file.sync_all()?;
+    fs::rename(&tmp_path, path)?;
+    // Persist the rename itself.
+    #[cfg(unix)]
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        File::open(dir)?.sync_all()?;
+    }
+    Ok(())
+}
+
 #[derive(Default, Serialize, Deserialize)]
 pub struct InMemKeystore {
     keys: BTreeMap<SuiAddress, SuiKeyPair>,
+    #[serde(skip)]
+    aliases: Aliases,
 }
 
 impl AccountKeystore for InMemKeystore {
@@ -272,6 +730,14 @@ impl AccountKeystore for InMemKeystore {
             None => Err(anyhow!("Cannot find key for address: [{address}]")),
         }
     }
+
+    fn aliases(&self) -> &Aliases {
+        &self.aliases
+    }
+
+    fn aliases_mut(&mut self) -> &mut Aliases {
+        &mut self.aliases
+    }
 }
 
 impl InMemKeystore {
@@ -282,6 +748,86 @@ impl InMemKeystore {
             .map(|(ad, k)| (ad, SuiKeyPair::Ed25519(k)))
             .collect::<BTreeMap<SuiAddress, SuiKeyPair>>();
 
-        Self { keys }
+        Self {
+            keys,
+            aliases: Aliases::default(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct AddressAlias {
+    alias: String,
+    address: SuiAddress,
+}
+
+/// Human readable aliases of keystore addresses. File based keystores keep their aliases next to
+/// the keystore file, e.g. `sui.aliases` for `sui.keystore`.
+#[derive(Default)]
+pub struct Aliases {
+    aliases: BTreeMap<SuiAddress, String>,
+    path: Option<PathBuf>,
+}
+
+impl Aliases {
+    pub fn new(keystore_path: &Path) -> Result<Self, anyhow::Error> {
+        let path = keystore_path.with_extension("aliases");
+        let aliases = if path.exists() {
+            let reader = BufReader::new(File::open(&path)?);
+            let aliases: Vec<AddressAlias> = serde_json::from_reader(reader)
+                .map_err(|e| anyhow!("Invalid aliases file {:?}: {e}", path))?;
+            aliases
+                .into_iter()
+                .map(|AddressAlias { alias, address }| (address, alias))
+                .collect()
+        } else {
+            BTreeMap::new()
+        };
+        Ok(Self {
+            aliases,
+            path: Some(path),
+        })
+    }
+
+    pub fn set_keystore_path(&mut self, keystore_path: &Path) {
+        self.path = Some(keystore_path.with_extension("aliases"));
+    }
+
+    pub fn get_alias(&self, address: &SuiAddress) -> Option<&str> {
+        self.aliases.get(address).map(String::as_str)
+    }
+
+    pub fn get_address(&self, alias: &str) -> Option<SuiAddress> {
+        self.aliases
+            .iter()
+            .find(|(_, a)| a.as_str() == alias)
+            .map(|(address, _)| *address)
+    }
+
+    pub fn set(&mut self, address: SuiAddress, alias: String) -> Result<(), anyhow::Error> {
+        KeyIdentity::validate_alias(&alias)?;
+        match self.get_address(&alias) {
+            Some(existing) if existing != address => {
+                return Err(anyhow!("Alias [{alias}] is already used by [{existing}]"))
+            }
+            _ => {}
+        }
+        self.aliases.insert(address, alias);
+        self.save()
+    }
+
+    pub fn save(&self) -> Result<(), anyhow::Error> {
+        if let Some(path) = &self.path {
+            let aliases = self
+                .aliases
+                .iter()
+                .map(|(address, alias)| AddressAlias {
+                    alias: alias.clone(),
+                    address: *address,
+                })
+                .collect::<Vec<_>>();
+            fs::write(path, serde_json::to_string_pretty(&aliases)?)?
+        }
+        Ok(())
     }
 }
@@ -3,10 +3,15 @@
 use std::str::FromStr;
 
 use fastcrypto::hash::{HashFunction, Sha3_256};
+use shared_crypto::intent::Intent;
 use tempfile::TempDir;
 
-use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
-use sui_types::crypto::{SignatureScheme, SuiSignatureInner};
+use sui_keys::external_signer::{ExternalKeystore, ExternalSignerConfig, MockSigner};
+use sui_keys::key_identity::KeyIdentity;
+use sui_keys::keystore::{
+    AccountKeystore, EncryptedFileKeystore, FileBasedKeystore, InMemKeystore, Keystore,
+};
+use sui_types::crypto::{EncodeDecodeBase64, SignatureScheme, SuiSignatureInner};
 use sui_types::{
     base_types::{SuiAddress, SUI_ADDRESS_LENGTH},
     crypto::Ed25519SuiSignature,
@@ -69,3 +74,154 @@ fn keystore_display_test() -> Result<(), anyhow::Error> {
     assert!(!keystore.to_string().contains("keys:"));
     Ok(())
 }
+
+#[test]
+fn encrypted_keystore_lock_unlock_test() -> Result<(), anyhow::Error> {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = Keystore::from(EncryptedFileKeystore::create(&keystore_path, "password")?);
+    let (address, _, _) = keystore.generate_and_add_new_key(SignatureScheme::ED25519, None)?;
+    keystore.sign_secure(&address, &"hello", Intent::default())?;
+
+    let contents = std::fs::read_to_string(&keystore_path)?;
+    assert!(!contents.contains(&keystore.get_key(&address)?.encode_base64()));
+    assert!(FileBasedKeystore::new(&keystore_path).is_err());
+
+    // A reloaded keystore lists its addresses but cannot sign until unlocked.
+    let mut reloaded = EncryptedFileKeystore::new(&keystore_path)?;
+    assert!(reloaded.is_locked());
+    assert_eq!(reloaded.addresses(), vec![address]);
+    assert!(reloaded
+        .sign_secure(&address, &"hello", Intent::default())
+        .is_err());
+    assert!(reloaded.unlock("wrong password").is_err());
+    reloaded.unlock("password")?;
+    reloaded.sign_secure(&address, &"hello", Intent::default())?;
+
+    reloaded.lock();
+    assert!(reloaded.get_key(&address).is_err());
+    Ok(())
+}
+
+#[test]
+fn encrypted_keystore_passphrase_prompt_test() -> Result<(), anyhow::Error> {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = Keystore::from(EncryptedFileKeystore::create(&keystore_path, "password")?);
+    let (address, _, _) = keystore.generate_and_add_new_key(SignatureScheme::ED25519, None)?;
+
+    // The prompt is only asked once the keys are used.
+    let mut reloaded = EncryptedFileKeystore::new(&keystore_path)?;
+    reloaded.set_passphrase_prompt(Box::new(|| Ok("password".to_string())));
+    assert!(reloaded.is_locked());
+    reloaded.sign_secure(&address, &"hello", Intent::default())?;
+    assert!(!reloaded.is_locked());
+
+    let mut wrong = EncryptedFileKeystore::new(&keystore_path)?;
+    wrong.set_passphrase_prompt(Box::new(|| Ok("wrong password".to_string())));
+    assert!(wrong
+        .sign_secure(&address, &"hello", Intent::default())
+        .is_err());
+    assert!(wrong.is_locked());
+    Ok(())
+}
+
+#[test]
+fn encrypted_keystore_migration_test() -> Result<(), anyhow::Error> {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = FileBasedKeystore::new(&keystore_path)?;
+    keystore.generate_and_add_new_key(SignatureScheme::ED25519, None)?;
+    keystore.generate_and_add_new_key(SignatureScheme::Secp256k1, None)?;
+    let addresses = keystore.addresses();
+
+    assert!(!EncryptedFileKeystore::is_encrypted(&keystore_path));
+    EncryptedFileKeystore::from_file_keystore(keystore, "password")?;
+    assert!(EncryptedFileKeystore::is_encrypted(&keystore_path));
+
+    let mut encrypted = EncryptedFileKeystore::new(&keystore_path)?;
+    encrypted.unlock("password")?;
+    assert_eq!(encrypted.addresses(), addresses);
+    for address in addresses {
+        encrypted.sign_secure(&address, &"hello", Intent::default())?;
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn external_keystore_unix_socket_test() -> Result<(), anyhow::Error> {
+    let temp_dir = TempDir::new().unwrap();
+    let socket_path = temp_dir.path().join("signer.sock");
+    let signer_keystore = InMemKeystore::new(2);
+    let addresses = signer_keystore.addresses();
+    MockSigner::new(signer_keystore).spawn_unix_socket(&socket_path)?;
+
+    let keystore = Keystore::from(ExternalKeystore::new(ExternalSignerConfig::UnixSocket(
+        socket_path,
+    ))?);
+    assert_eq!(keystore.addresses(), addresses);
+    for address in &addresses {
+        keystore.sign_secure(address, &"hello", Intent::default())?;
+    }
+    assert!(keystore.get_key(&addresses[0]).is_err());
+    assert!(keystore
+        .sign_secure(
+            &SuiAddress::random_for_testing_only(),
+            &"hello",
+            Intent::default()
+        )
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn external_keystore_command_test() -> Result<(), anyhow::Error> {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut signer_keystore = FileBasedKeystore::new(&keystore_path)?;
+    let (address, _, _) =
+        signer_keystore.generate_and_add_new_key(SignatureScheme::Secp256k1, None)?;
+
+    let keystore = ExternalKeystore::new(ExternalSignerConfig::Command {
+        program: env!("CARGO_BIN_EXE_sui-mock-signer").into(),
+        args: vec![keystore_path.to_string_lossy().to_string()],
+    })?;
+    assert_eq!(keystore.addresses(), vec![address]);
+    keystore.sign_secure(&address, &"hello", Intent::default())?;
+    Ok(())
+}
+
+#[test]
+fn keystore_alias_test() -> Result<(), anyhow::Error> {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = Keystore::from(FileBasedKeystore::new(&keystore_path)?);
+    let (alice, _, _) = keystore.generate_and_add_new_key(SignatureScheme::ED25519, None)?;
+    let (bob, _, _) = keystore.generate_and_add_new_key(SignatureScheme::ED25519, None)?;
+
+    keystore.set_alias(&alice, "alice".to_string())?;
+    assert!(keystore.set_alias(&bob, "alice".to_string()).is_err());
+    assert!(keystore.set_alias(&bob, "0xb0b".to_string()).is_err());
+    assert!(keystore
+        .set_alias(&SuiAddress::random_for_testing_only(), "carol".to_string())
+        .is_err());
+
+    // Aliases are persisted next to the keystore file.
+    let keystore = Keystore::from(FileBasedKeystore::new(&keystore_path)?);
+    assert!(temp_dir.path().join("sui.aliases").exists());
+    assert_eq!(keystore.get_alias(&alice), Some("alice"));
+    assert_eq!(keystore.get_alias(&bob), None);
+    assert_eq!(
+        keystore.get_address(&KeyIdentity::from_str("alice")?)?,
+        alice
+    );
+    assert_eq!(
+        keystore.get_address(&KeyIdentity::from_str(&bob.to_string())?)?,
+        bob
+    );
+    assert!(keystore
+        .get_address(&KeyIdentity::from_str("bob")?)
+        .is_err());
+    Ok(())
+}
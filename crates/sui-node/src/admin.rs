@@ -1,26 +1,70 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::SuiNode;
 use axum::{
-    extract::Extension,
+    extract::{Extension, Query},
     http::StatusCode,
     routing::{get, post},
-    Router,
+    Json, Router,
 };
 use mysten_metrics::spawn_monitored_task;
+use narwhal_network::connectivity::ConnectionStatus;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use sui_protocol_config::ProtocolVersion;
+use sui_types::committee::EpochId;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use telemetry_subscribers::FilterHandle;
+use tokio::sync::OnceCell;
 use tracing::info;
 
 const LOGGING_ROUTE: &str = "/logging";
+const SPAN_SAMPLING_ROUTE: &str = "/span-sampling";
+const EPOCH_ROUTE: &str = "/epoch";
+const CHECKPOINTS_ROUTE: &str = "/checkpoints";
+const TRANSACTION_MANAGER_ROUTE: &str = "/transaction-manager";
+const PEERS_ROUTE: &str = "/peers";
+const DB_CHECKPOINT_ROUTE: &str = "/db-checkpoint";
+const COMPACT_ROUTE: &str = "/compact";
 
-pub fn start_admin_server(port: u16, filter_handle: FilterHandle) {
+/// The node inspected by the admin server, set once it has started. The admin server starts
+/// before the node so that logging can be adjusted during startup, the endpoints inspecting the
+/// node respond with 503 until then.
+pub type AdminNode = Arc<OnceCell<Arc<SuiNode>>>;
+
+// Endpoints, all served on localhost only:
+//
+//   GET  /logging                   current log filter
+//   POST /logging                   replace the log filter, e.g. `info,sui_core=debug`
+//   GET  /span-sampling             fraction of spans sampled for span latency metrics
+//   POST /span-sampling             set the span sampling fraction, between 0 and 1
+//   GET  /epoch                     current epoch and protocol version
+//   GET  /checkpoints               highest synced, certified, verified and executed checkpoints
+//   GET  /transaction-manager       number of certificates pending or being executed
+//   GET  /peers                     connection status of the known peers
+//   POST /db-checkpoint             take a RocksDB checkpoint under the db checkpoint path
+//   POST /compact?table=<name>      run a manual compaction over a perpetual table
+pub fn start_admin_server(port: u16, node: AdminNode, filter_handle: FilterHandle) {
     let filter = filter_handle.get().unwrap();
 
     let app = Router::new()
         .route(LOGGING_ROUTE, get(get_filter))
         .route(LOGGING_ROUTE, post(set_filter))
-        .layer(Extension(filter_handle));
+        .route(SPAN_SAMPLING_ROUTE, get(get_span_sampling))
+        .route(SPAN_SAMPLING_ROUTE, post(set_span_sampling))
+        .route(EPOCH_ROUTE, get(get_epoch))
+        .route(CHECKPOINTS_ROUTE, get(get_checkpoints))
+        .route(TRANSACTION_MANAGER_ROUTE, get(get_transaction_manager))
+        .route(PEERS_ROUTE, get(get_peers))
+        .route(DB_CHECKPOINT_ROUTE, post(db_checkpoint))
+        .route(COMPACT_ROUTE, post(compact))
+        .layer(Extension(filter_handle))
+        .layer(Extension(node));
 
     let socket_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port);
     info!(
@@ -56,3 +100,231 @@ async fn set_filter(
         Err(err) => (StatusCode::BAD_REQUEST, err.to_string()),
     }
 }
+
+async fn get_span_sampling(
+    Extension(filter_handle): Extension<FilterHandle>,
+) -> (StatusCode, String) {
+    (
+        StatusCode::OK,
+        filter_handle.get_span_sampling_rate().to_string(),
+    )
+}
+
+async fn set_span_sampling(
+    Extension(filter_handle): Extension<FilterHandle>,
+    rate: String,
+) -> (StatusCode, String) {
+    let rate = match rate.trim().parse::<f64>() {
+        Ok(rate) => rate,
+        Err(err) => return (StatusCode::BAD_REQUEST, err.to_string()),
+    };
+    match filter_handle.set_span_sampling_rate(rate) {
+        Ok(()) => {
+            info!(rate, "Span sampling rate updated");
+            (StatusCode::OK, "".into())
+        }
+        Err(err) => (StatusCode::BAD_REQUEST, err.to_string()),
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct EpochInfo {
+    epoch: EpochId,
+    protocol_version: ProtocolVersion,
+}
+
+fn started(node: &AdminNode) -> Result<&Arc<SuiNode>, (StatusCode, String)> {
+    node.get().ok_or_else(|| {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Node is still starting".into(),
+        )
+    })
+}
+
+async fn get_epoch(
+    Extension(node): Extension<AdminNode>,
+) -> Result<Json<EpochInfo>, (StatusCode, String)> {
+    let epoch_store = started(&node)?.state.load_epoch_store_one_call_per_task();
+    Ok(Json(EpochInfo {
+        epoch: epoch_store.epoch(),
+        protocol_version: epoch_store.protocol_version(),
+    }))
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CheckpointInfo {
+    highest_synced: Option<CheckpointSequenceNumber>,
+    highest_certified: Option<CheckpointSequenceNumber>,
+    highest_verified: Option<CheckpointSequenceNumber>,
+    highest_executed: Option<CheckpointSequenceNumber>,
+}
+
+async fn get_checkpoints(
+    Extension(node): Extension<AdminNode>,
+) -> Result<Json<CheckpointInfo>, (StatusCode, String)> {
+    let store = &started(&node)?.checkpoint_store;
+    let read = || -> Result<CheckpointInfo, typed_store::rocks::TypedStoreError> {
+        Ok(CheckpointInfo {
+            highest_synced: store.get_highest_synced_checkpoint_seq_number()?,
+            highest_certified: store
+                .get_latest_certified_checkpoint()
+                .map(|c| *c.sequence_number()),
+            highest_verified: store
+                .get_highest_verified_checkpoint()?
+                .map(|c| *c.sequence_number()),
+            highest_executed: store.get_highest_executed_checkpoint_seq_number()?,
+        })
+    };
+    read()
+        .map(Json)
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TransactionManagerInfo {
+    execution_queue_len: usize,
+}
+
+async fn get_transaction_manager(
+    Extension(node): Extension<AdminNode>,
+) -> Result<Json<TransactionManagerInfo>, (StatusCode, String)> {
+    Ok(Json(TransactionManagerInfo {
+        execution_queue_len: started(&node)?
+            .state
+            .transaction_manager()
+            .execution_queue_len(),
+    }))
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PeerInfo {
+    peer_id: String,
+    /// The validator's authority name, if the peer is a committee member.
+    authority: Option<String>,
+    connected: bool,
+}
+
+async fn get_peers(
+    Extension(node): Extension<AdminNode>,
+) -> Result<Json<Vec<PeerInfo>>, (StatusCode, String)> {
+    let status = &started(&node)?.connection_monitor_status;
+    let authorities: HashMap<_, _> = status
+        .authority_names_to_peer_ids
+        .load()
+        .iter()
+        .map(|(name, peer_id)| (*peer_id, name.to_string()))
+        .collect();
+    let mut peers: Vec<_> = status
+        .connection_statuses
+        .iter()
+        .map(|entry| PeerInfo {
+            peer_id: entry.key().to_string(),
+            authority: authorities.get(entry.key()).cloned(),
+            connected: *entry.value() == ConnectionStatus::Connected,
+        })
+        .collect();
+    peers.sort_by(|a, b| a.peer_id.cmp(&b.peer_id));
+    Ok(Json(peers))
+}
+
+async fn db_checkpoint(Extension(node): Extension<AdminNode>) -> (StatusCode, String) {
+    let node = match started(&node) {
+        Ok(node) => node.clone(),
+        Err(err) => return err,
+    };
+    let timestamp_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis();
+    let path: PathBuf = node
+        .db_checkpoint_path()
+        .join(format!("admin_{timestamp_ms}"));
+    let result = tokio::task::spawn_blocking({
+        let path = path.clone();
+        move || {
+            let epoch_store = node.state.load_epoch_store_one_call_per_task();
+            node.state.checkpoint_all_dbs(&path, &epoch_store)
+        }
+    })
+    .await;
+    match result {
+        Ok(Ok(())) => {
+            info!(?path, "Db checkpoint taken");
+            (StatusCode::OK, path.display().to_string())
+        }
+        Ok(Err(err)) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
+
+#[derive(Deserialize)]
+struct CompactParams {
+    table: String,
+}
+
+async fn compact(
+    Extension(node): Extension<AdminNode>,
+    Query(CompactParams { table }): Query<CompactParams>,
+) -> (StatusCode, String) {
+    let node = match started(&node) {
+        Ok(node) => node.clone(),
+        Err(err) => return err,
+    };
+    let result = tokio::task::spawn_blocking({
+        let table = table.clone();
+        move || node.state.db().compact_perpetual_table(&table)
+    })
+    .await;
+    match result {
+        Ok(Ok(())) => {
+            info!(table, "Manual compaction finished");
+            (StatusCode::OK, "".into())
+        }
+        Ok(Err(err)) => (StatusCode::BAD_REQUEST, err.to_string()),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn node_endpoints_are_unavailable_while_starting() {
+        let node = AdminNode::default();
+        let unavailable = |status: StatusCode| assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+
+        unavailable(get_epoch(Extension(node.clone())).await.err().unwrap().0);
+        unavailable(
+            get_checkpoints(Extension(node.clone()))
+                .await
+                .err()
+                .unwrap()
+                .0,
+        );
+        unavailable(
+            get_transaction_manager(Extension(node.clone()))
+                .await
+                .err()
+                .unwrap()
+                .0,
+        );
+        unavailable(get_peers(Extension(node.clone())).await.err().unwrap().0);
+        unavailable(db_checkpoint(Extension(node.clone())).await.0);
+        unavailable(
+            compact(
+                Extension(node),
+                Query(CompactParams {
+                    table: "objects".into(),
+                }),
+            )
+            .await
+            .0,
+        );
+    }
+}
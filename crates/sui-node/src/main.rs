@@ -94,9 +94,16 @@ async fn main() -> Result<()> {
         }
     });
 
-    sui_node::admin::start_admin_server(config.admin_interface_port, filter_handle);
+    let admin_node = sui_node::admin::AdminNode::default();
+    sui_node::admin::start_admin_server(
+        config.admin_interface_port,
+        admin_node.clone(),
+        filter_handle,
+    );
+
+    let node = sui_node::SuiNode::start(&config, registry_service).await?;
+    let _ = admin_node.set(node);
 
-    let _node = sui_node::SuiNode::start(&config, registry_service).await?;
     // TODO: Do we want to provide a way for the node to gracefully shutdown?
     loop {
         tokio::time::sleep(Duration::from_secs(1000)).await;
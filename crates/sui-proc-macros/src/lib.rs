@@ -4,6 +4,8 @@
 use proc_macro::TokenStream;
 use quote::quote;
 
+mod move_struct;
+
 #[proc_macro_attribute]
 pub fn init_static_initializers(_args: TokenStream, item: TokenStream) -> TokenStream {
     let mut input = syn::parse_macro_input!(item as syn::ItemFn);
@@ -220,3 +222,28 @@ pub fn sim_test(args: TokenStream, item: TokenStream) -> TokenStream {
 
     result.into()
 }
+
+/// Derives `MoveTypeTagTrait` and `MoveStructType` for a struct mirroring a Move struct, so the
+/// SDK can decode objects of that type and check the struct against the on-chain layout:
+///
+/// ```ignore
+/// #[derive(Deserialize, MoveStruct)]
+/// #[move_struct(address = "0xc0ffee", module = "counter", name = "Counter")]
+/// struct Counter<T> {
+///     id: UID,
+///     owner: SuiAddress,
+///     value: u64,
+///     kind: PhantomData<T>,
+/// }
+/// ```
+///
+/// `name` defaults to the name of the Rust struct. Type parameters map to the type parameters of
+/// the Move struct, `PhantomData` fields are skipped. The generated code refers to
+/// `::sui_sdk::types`, which can be changed with `crate = "::sui_types"`.
+#[proc_macro_derive(MoveStruct, attributes(move_struct))]
+pub fn derive_move_struct(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    move_struct::derive(input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
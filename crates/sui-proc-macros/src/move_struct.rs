@@ -0,0 +1,220 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{
+    parse_quote, Data, DeriveInput, Error, Fields, Lit, Meta, NestedMeta, Path, Type, TypeParam,
+};
+
+const ADDRESS_LENGTH: usize = 32;
+
+struct MoveStructAttributes {
+    address: [u8; ADDRESS_LENGTH],
+    module: String,
+    name: Option<String>,
+    krate: Path,
+}
+
+pub fn derive(input: DeriveInput) -> Result<TokenStream, Error> {
+    let attributes = parse_attributes(&input)?;
+    let krate = &attributes.krate;
+    let ident = &input.ident;
+    let address = attributes.address;
+    let module = &attributes.module;
+    let name = attributes.name.unwrap_or_else(|| ident.to_string());
+    check_identifier(&name, ident.span())?;
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new(
+                    input.span(),
+                    "MoveStruct can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                input.span(),
+                "MoveStruct can only be derived for structs",
+            ))
+        }
+    };
+
+    // `PhantomData` fields stand for the phantom type parameters of the Move struct, they have no
+    // BCS representation and no Move counterpart.
+    let (field_names, field_types): (Vec<_>, Vec<_>) = fields
+        .iter()
+        .filter(|field| !is_phantom_data(&field.ty))
+        .map(|field| {
+            let name = field.ident.as_ref().unwrap().to_string();
+            let name = name.trim_start_matches("r#").to_string();
+            (name, &field.ty)
+        })
+        .unzip();
+
+    let type_params: Vec<&TypeParam> = input.generics.type_params().collect();
+    let type_param_idents: Vec<_> = type_params.iter().map(|param| &param.ident).collect();
+    let mut generics = input.generics.clone();
+    {
+        let where_clause = generics.make_where_clause();
+        for param in &type_param_idents {
+            where_clause
+                .predicates
+                .push(parse_quote!(#param: #krate::MoveTypeTagTrait));
+        }
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    // Decoding may require bounds on the type parameters that only the Deserialize impl knows.
+    let mut struct_generics = generics.clone();
+    struct_generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(#ident #ty_generics: #krate::__private::DeserializeOwned));
+    let struct_where_clause = &struct_generics.where_clause;
+
+    let struct_tag = quote! {
+        #krate::__private::StructTag {
+            address: #krate::__private::AccountAddress::new([#(#address),*]),
+            module: #krate::__private::Identifier::new(#module).unwrap(),
+            name: #krate::__private::Identifier::new(#name).unwrap(),
+            type_params: vec![
+                #(<#type_param_idents as #krate::MoveTypeTagTrait>::get_type_tag()),*
+            ],
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics #krate::MoveTypeTagTrait for #ident #ty_generics #where_clause {
+            fn get_type_tag() -> #krate::__private::TypeTag {
+                #krate::__private::TypeTag::Struct(Box::new(#struct_tag))
+            }
+        }
+
+        impl #impl_generics #krate::MoveStructType for #ident #ty_generics #struct_where_clause {
+            fn struct_tag() -> #krate::__private::StructTag {
+                #struct_tag
+            }
+
+            fn fields() -> Vec<(&'static str, #krate::__private::TypeTag)> {
+                vec![
+                    #((#field_names, <#field_types as #krate::MoveTypeTagTrait>::get_type_tag())),*
+                ]
+            }
+        }
+    })
+}
+
+fn parse_attributes(input: &DeriveInput) -> Result<MoveStructAttributes, Error> {
+    let mut address = None;
+    let mut module = None;
+    let mut name = None;
+    let mut krate: Path = parse_quote!(::sui_sdk::types);
+
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("move_struct"))
+    {
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            meta => {
+                return Err(Error::new(
+                    meta.span(),
+                    "expected #[move_struct(address = \"..\", module = \"..\")]",
+                ))
+            }
+        };
+        for nested in list.nested {
+            let name_value = match nested {
+                NestedMeta::Meta(Meta::NameValue(name_value)) => name_value,
+                nested => return Err(Error::new(nested.span(), "expected `key = \"value\"`")),
+            };
+            let value = match &name_value.lit {
+                Lit::Str(value) => value,
+                lit => return Err(Error::new(lit.span(), "expected a string literal")),
+            };
+            let span = value.span();
+            if name_value.path.is_ident("address") {
+                address = Some(parse_address(&value.value(), span)?);
+            } else if name_value.path.is_ident("module") {
+                check_identifier(&value.value(), span)?;
+                module = Some(value.value());
+            } else if name_value.path.is_ident("name") {
+                name = Some(value.value());
+            } else if name_value.path.is_ident("crate") {
+                krate = value.parse()?;
+            } else {
+                return Err(Error::new(
+                    name_value.path.span(),
+                    "unknown move_struct attribute, expected one of `address`, `module`, `name` or `crate`",
+                ));
+            }
+        }
+    }
+
+    match (address, module) {
+        (Some(address), Some(module)) => Ok(MoveStructAttributes {
+            address,
+            module,
+            name,
+            krate,
+        }),
+        _ => Err(Error::new(
+            input.ident.span(),
+            "MoveStruct requires #[move_struct(address = \"..\", module = \"..\")]",
+        )),
+    }
+}
+
+/// Parses a hex address such as `0x2`, left padded with zeros to the address length.
+fn parse_address(address: &str, span: Span) -> Result<[u8; ADDRESS_LENGTH], Error> {
+    let error = || Error::new(span, format!("invalid Move address `{address}`"));
+    let hex = address.strip_prefix("0x").ok_or_else(error)?;
+    if hex.is_empty()
+        || hex.len() > ADDRESS_LENGTH * 2
+        || !hex.chars().all(|c| c.is_ascii_hexdigit())
+    {
+        return Err(error());
+    }
+    let hex = format!("{hex:0>width$}", width = ADDRESS_LENGTH * 2);
+    let mut bytes = [0u8; ADDRESS_LENGTH];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).map_err(|_| error())?;
+    }
+    Ok(bytes)
+}
+
+/// Move identifiers start with a letter, or an underscore followed by more characters, and only
+/// contain alphanumeric characters and underscores.
+fn check_identifier(identifier: &str, span: Span) -> Result<(), Error> {
+    let mut chars = identifier.chars();
+    let valid = match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() => true,
+        Some('_') => identifier.len() > 1,
+        _ => false,
+    } && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(Error::new(
+            span,
+            format!("invalid Move identifier `{identifier}`"),
+        ))
+    }
+}
+
+fn is_phantom_data(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .map_or(false, |segment| segment.ident == "PhantomData"),
+        _ => false,
+    }
+}
@@ -7,30 +7,50 @@ use fastcrypto::encoding::Base64;
 use futures::stream;
 use futures_core::Stream;
 use jsonrpsee::core::client::Subscription;
-use std::collections::BTreeMap;
-use std::future;
+use shared_crypto::intent::Intent;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, VecDeque};
+use std::future::{self, Future};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use sui_json_rpc::api::GovernanceReadApiClient;
 use sui_json_rpc_types::{
-    Balance, Checkpoint, CheckpointId, Coin, CoinPage, DelegatedStake, DryRunTransactionResponse,
-    DynamicFieldPage, EventPage, SuiCoinMetadata, SuiCommittee, SuiEventEnvelope, SuiEventFilter,
-    SuiGetPastObjectRequest, SuiMoveNormalizedModule, SuiObjectDataOptions, SuiObjectInfo,
-    SuiObjectResponse, SuiPastObjectResponse, SuiTransactionEffectsAPI, SuiTransactionResponse,
-    SuiTransactionResponseOptions, SuiTransactionResponseQuery, TransactionsPage,
+    Balance, Checkpoint, CheckpointId, CheckpointPage, Coin, CoinPage, DelegatedStake,
+    DevInspectResults, DryRunTransactionResponse, DynamicFieldPage, EventPage,
+    EventSubscriptionItem, ObjectChangeSubscriptionItem, Page, SubscriptionItem, SuiCoinMetadata,
+    SuiCommittee, SuiEventEnvelope, SuiEventFilter, SuiGetPastObjectRequest,
+    SuiMoveNormalizedModule, SuiMoveNormalizedType, SuiObjectChangeEnvelope, SuiObjectDataOptions,
+    SuiObjectInfo, SuiObjectResponse, SuiPastObjectResponse, SuiRawData, SuiTransactionEffectsAPI,
+    SuiTransactionResponse, SuiTransactionResponseOptions, SuiTransactionResponseQuery,
+    TransactionSubscriptionItem, TransactionsPage,
 };
 use sui_types::balance::Supply;
 use sui_types::base_types::{
     ObjectID, SequenceNumber, SuiAddress, TransactionDigest, TxSequenceNumber,
 };
 use sui_types::committee::EpochId;
+use sui_types::dynamic_field::{
+    derive_dynamic_field_id, dynamic_field_type, dynamic_object_field_name_type, DynamicFieldInfo,
+    Field,
+};
 use sui_types::error::TRANSACTION_NOT_FOUND_MSG_PREFIX;
 use sui_types::event::EventID;
-use sui_types::messages::{ExecuteTransactionRequestType, TransactionData, VerifiedTransaction};
+use sui_types::id::ID;
+use sui_types::messages::{
+    ExecuteTransactionRequestType, Transaction, TransactionData, TransactionKind,
+    VerifiedTransaction,
+};
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
-use sui_types::query::EventQuery;
+use sui_types::query::{EventQuery, ObjectChangeFilter, TransactionFilter};
+use sui_types::signature::GenericSignature;
+use sui_types::{parse_sui_struct_tag, MoveStructType, MoveTypeTagTrait};
 
-use futures::StreamExt;
+use futures::{StreamExt, TryStreamExt};
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::{StructTag, TypeTag};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 use sui_json_rpc::api::{CoinReadApiClient, EventReadApiClient, ReadApiClient, WriteApiClient};
 use sui_types::sui_system_state::sui_system_state_summary::SuiSystemStateSummary;
 
@@ -51,6 +71,22 @@ impl ReadApi {
         Ok(self.api.http.get_objects_owned_by_address(address).await?)
     }
 
+    /// Stream the objects owned by `address`. The fullnode returns them in a single response, so
+    /// only the retry settings of `options` apply.
+    pub fn get_owned_objects_stream(
+        &self,
+        address: SuiAddress,
+        options: StreamOptions,
+    ) -> impl Stream<Item = SuiRpcResult<SuiObjectInfo>> + '_ {
+        stream::once(async move {
+            retry_transient(&options, || self.get_objects_owned_by_address(address)).await
+        })
+        .flat_map(|result| match result {
+            Ok(objects) => stream::iter(objects.into_iter().map(Ok)).left_stream(),
+            Err(e) => stream::once(future::ready(Err(e))).right_stream(),
+        })
+    }
+
     pub async fn get_dynamic_fields(
         &self,
         object_id: ObjectID,
@@ -64,6 +100,18 @@ impl ReadApi {
             .await?)
     }
 
+    /// Stream the dynamic fields of `object_id`, following the exclusive `cursor`.
+    pub fn get_dynamic_fields_stream(
+        &self,
+        object_id: ObjectID,
+        cursor: Option<ObjectID>,
+        options: StreamOptions,
+    ) -> impl Stream<Item = SuiRpcResult<DynamicFieldInfo>> + '_ {
+        paginated_stream(cursor, options, move |cursor, limit| {
+            self.get_dynamic_fields(object_id, cursor, Some(limit))
+        })
+    }
+
     pub async fn try_get_parsed_past_object(
         &self,
         object_id: ObjectID,
@@ -101,6 +149,134 @@ impl ReadApi {
             .await?)
     }
 
+    /// Fetch the object `object_id` and decode it into `T`. The object must have the type of
+    /// `T`, whose fields are checked against the on-chain Move struct before decoding.
+    pub async fn get_typed_object<T: MoveStructType>(
+        &self,
+        object_id: ObjectID,
+    ) -> SuiRpcResult<T> {
+        let bcs_bytes = self.get_move_object_bcs(object_id, T::struct_tag()).await?;
+        self.check_struct_layout::<T>().await?;
+        Ok(bcs::from_bytes(&bcs_bytes)?)
+    }
+
+    /// Fetch the dynamic field `name` of `parent_object_id` and decode its value. The field value
+    /// must have the Move type of `V`, use [ReadApi::get_typed_dynamic_object_field] for fields
+    /// added with `sui::dynamic_object_field`.
+    pub async fn get_typed_dynamic_field<K, V>(
+        &self,
+        parent_object_id: ObjectID,
+        name: &K,
+    ) -> SuiRpcResult<V>
+    where
+        K: MoveTypeTagTrait + Serialize + DeserializeOwned,
+        V: MoveTypeTagTrait + DeserializeOwned,
+    {
+        let name_type = K::get_type_tag();
+        let field_id =
+            derive_dynamic_field_id(parent_object_id, &name_type, &bcs::to_bytes(name)?)?;
+        let bcs_bytes = self
+            .get_move_object_bcs(field_id, dynamic_field_type(name_type, V::get_type_tag()))
+            .await?;
+        Ok(bcs::from_bytes::<Field<K, V>>(&bcs_bytes)?.value)
+    }
+
+    /// Fetch the object stored in the dynamic object field `name` of `parent_object_id`, and
+    /// decode it like [ReadApi::get_typed_object].
+    pub async fn get_typed_dynamic_object_field<K, V>(
+        &self,
+        parent_object_id: ObjectID,
+        name: &K,
+    ) -> SuiRpcResult<V>
+    where
+        K: MoveTypeTagTrait + Serialize + DeserializeOwned,
+        V: MoveStructType,
+    {
+        // The field stores the ID of the object under a wrapped name, both have the same BCS
+        // encoding as the unwrapped values.
+        let name_type = dynamic_object_field_name_type(K::get_type_tag());
+        let field_id =
+            derive_dynamic_field_id(parent_object_id, &name_type, &bcs::to_bytes(name)?)?;
+        let bcs_bytes = self
+            .get_move_object_bcs(field_id, dynamic_field_type(name_type, ID::get_type_tag()))
+            .await?;
+        let object_id = bcs::from_bytes::<Field<K, ObjectID>>(&bcs_bytes)?.value;
+        self.get_typed_object(object_id).await
+    }
+
+    /// Fetch the BCS contents of the Move object `object_id`, checking its type is `expected`.
+    async fn get_move_object_bcs(
+        &self,
+        object_id: ObjectID,
+        expected: StructTag,
+    ) -> SuiRpcResult<Vec<u8>> {
+        let object = self
+            .get_object_with_options(object_id, SuiObjectDataOptions::new().with_bcs())
+            .await?
+            .into_object()?;
+        let move_object = match object.bcs {
+            Some(SuiRawData::MoveObject(move_object)) => move_object,
+            _ => {
+                return Err(Error::DataError(format!(
+                    "Object [{object_id}] is not a Move object"
+                )))
+            }
+        };
+        let actual = parse_sui_struct_tag(&move_object.type_)
+            .map_err(|e| Error::DataError(e.to_string()))?;
+        if actual != expected {
+            return Err(Error::ObjectTypeMismatch {
+                object_id,
+                expected: expected.to_string(),
+                actual: actual.to_string(),
+            });
+        }
+        Ok(move_object.bcs_bytes)
+    }
+
+    /// Check the fields of `T` have the names and types of the fields of the on-chain Move
+    /// struct, in the same order. Nested structs are only compared by type.
+    async fn check_struct_layout<T: MoveStructType>(&self) -> SuiRpcResult<()> {
+        let struct_tag = T::struct_tag();
+        let mismatch = |reason: String| Error::StructLayoutMismatch {
+            struct_tag: struct_tag.to_string(),
+            reason,
+        };
+        let normalized = self
+            .api
+            .http
+            .get_normalized_move_struct(
+                struct_tag.address.into(),
+                struct_tag.module.to_string(),
+                struct_tag.name.to_string(),
+            )
+            .await?;
+        let fields = T::fields();
+        if fields.len() != normalized.fields.len() {
+            return Err(mismatch(format!(
+                "expected {} fields but the Move struct has {}",
+                fields.len(),
+                normalized.fields.len()
+            )));
+        }
+        for ((name, type_), field) in fields.iter().zip(&normalized.fields) {
+            if field.name != *name {
+                return Err(mismatch(format!(
+                    "expected field `{name}` but found `{}`",
+                    field.name
+                )));
+            }
+            let move_type = normalized_type_tag(&field.type_, &struct_tag.type_params)
+                .ok_or_else(|| mismatch(format!("unsupported type for field `{name}`")))?;
+            if move_type != *type_ {
+                return Err(mismatch(format!(
+                    "field `{name}` has type {move_type} but {type_} was expected"
+                )));
+            }
+        }
+        Ok(())
+    }
+
     pub async fn multi_get_object_with_options(
         &self,
         object_ids: Vec<ObjectID>,
@@ -171,11 +347,73 @@ impl ReadApi {
             .await?)
     }
 
+    /// Subscribe to the transactions executed by the fullnode matching the `filter`.
+    pub async fn subscribe_transaction(
+        &self,
+        filter: TransactionFilter,
+        options: SuiTransactionResponseOptions,
+    ) -> SuiRpcResult<impl Stream<Item = SuiRpcResult<SuiTransactionResponse>>> {
+        match &self.api.ws {
+            Some(c) => {
+                let subscription: Subscription<TransactionSubscriptionItem> =
+                    c.subscribe_transaction(filter, Some(options)).await?;
+                Ok(subscription.map(|item| match item? {
+                    SubscriptionItem::Data(response) => Ok(response),
+                    SubscriptionItem::Lagged { lagged } => Err(Error::SubscriptionLagged(lagged)),
+                }))
+            }
+            _ => Err(Error::Subscription(
+                "Subscription only supported by WebSocket client.".to_string(),
+            )),
+        }
+    }
+
+    /// Subscribe to the changes of the objects matching the `filter`.
+    pub async fn subscribe_object_changes(
+        &self,
+        filter: ObjectChangeFilter,
+    ) -> SuiRpcResult<impl Stream<Item = SuiRpcResult<SuiObjectChangeEnvelope>>> {
+        match &self.api.ws {
+            Some(c) => {
+                let subscription: Subscription<ObjectChangeSubscriptionItem> =
+                    c.subscribe_object_changes(filter).await?;
+                Ok(subscription.map(|item| match item? {
+                    SubscriptionItem::Data(change) => Ok(change),
+                    SubscriptionItem::Lagged { lagged } => Err(Error::SubscriptionLagged(lagged)),
+                }))
+            }
+            _ => Err(Error::Subscription(
+                "Subscription only supported by WebSocket client.".to_string(),
+            )),
+        }
+    }
+
     /// Return a checkpoint
     pub async fn get_checkpoint(&self, id: CheckpointId) -> SuiRpcResult<Checkpoint> {
         Ok(self.api.http.get_checkpoint(id).await?)
     }
 
+    /// Return a page of checkpoints following the exclusive `cursor`. Transaction digests are only
+    /// included when `show_transactions` is set.
+    pub async fn get_checkpoints(
+        &self,
+        cursor: Option<CheckpointSequenceNumber>,
+        limit: Option<usize>,
+        descending_order: bool,
+        show_transactions: bool,
+    ) -> SuiRpcResult<CheckpointPage> {
+        Ok(self
+            .api
+            .http
+            .get_checkpoints(
+                cursor,
+                limit,
+                Some(descending_order),
+                Some(show_transactions),
+            )
+            .await?)
+    }
+
     /// Return the sequence number of the latest checkpoint that has been executed
     pub async fn get_latest_checkpoint_sequence_number(
         &self,
@@ -187,31 +425,48 @@ impl ReadApi {
             .await?)
     }
 
+    /// Stream the checkpoints following the exclusive `cursor` in ascending order. With
+    /// [StreamOptions::follow] the stream never ends and yields new checkpoints as they are
+    /// executed by the fullnode.
+    pub fn get_checkpoints_stream(
+        &self,
+        cursor: Option<CheckpointSequenceNumber>,
+        show_transactions: bool,
+        options: StreamOptions,
+    ) -> impl Stream<Item = SuiRpcResult<Checkpoint>> + '_ {
+        paginated_stream(cursor, options, move |cursor, limit| {
+            self.get_checkpoints(cursor, Some(limit), false, show_transactions)
+        })
+    }
+
+    /// Stream the transactions matching `query`, the stream ends at the first error.
     pub fn get_transactions_stream(
         &self,
         query: SuiTransactionResponseQuery,
         cursor: Option<TransactionDigest>,
         descending_order: bool,
     ) -> impl Stream<Item = SuiTransactionResponse> + '_ {
-        stream::unfold(
-            (vec![], cursor, true, query),
-            move |(mut data, cursor, first, query)| async move {
-                if let Some(item) = data.pop() {
-                    Some((item, (data, cursor, false, query)))
-                } else if (cursor.is_none() && first) || cursor.is_some() {
-                    let page = self
-                        .query_transactions(query.clone(), cursor, Some(100), descending_order)
-                        .await
-                        .ok()?;
-                    let mut data = page.data;
-                    data.reverse();
-                    data.pop()
-                        .map(|item| (item, (data, page.next_cursor, false, query)))
-                } else {
-                    None
-                }
-            },
+        self.get_transactions_stream_with_options(
+            query,
+            cursor,
+            descending_order,
+            StreamOptions::default(),
         )
+        .filter_map(|result| future::ready(result.ok()))
+    }
+
+    /// Stream the transactions matching `query` following the exclusive `cursor`. Following the
+    /// tip with [StreamOptions::follow] only makes sense in ascending order.
+    pub fn get_transactions_stream_with_options(
+        &self,
+        query: SuiTransactionResponseQuery,
+        cursor: Option<TransactionDigest>,
+        descending_order: bool,
+        options: StreamOptions,
+    ) -> impl Stream<Item = SuiRpcResult<SuiTransactionResponse>> + '_ {
+        paginated_stream(cursor, options, move |cursor, limit| {
+            self.query_transactions(query.clone(), cursor, Some(limit), descending_order)
+        })
     }
 
     pub async fn get_normalized_move_modules_by_package(
@@ -239,6 +494,26 @@ impl ReadApi {
             .dry_run_transaction(Base64::from_bytes(&bcs::to_bytes(&tx)?))
             .await?)
     }
+
+    /// Run `tx_kind` in dev-inspect mode, returning the results of each of its commands along
+    /// with the effects. Gas is computed but not charged, and defaults to the reference gas price.
+    pub async fn dev_inspect_transaction(
+        &self,
+        sender: SuiAddress,
+        tx_kind: TransactionKind,
+        gas_price: Option<u64>,
+    ) -> SuiRpcResult<DevInspectResults> {
+        Ok(self
+            .api
+            .http
+            .dev_inspect_transaction(
+                sender,
+                Base64::from_bytes(&bcs::to_bytes(&tx_kind)?),
+                gas_price,
+                None,
+            )
+            .await?)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -274,30 +549,25 @@ impl CoinReadApi {
         Ok(self.api.http.get_all_coins(owner, cursor, limit).await?)
     }
 
+    /// Stream the coins of `owner`, the stream ends at the first error.
     pub fn get_coins_stream(
         &self,
         owner: SuiAddress,
         coin_type: Option<String>,
     ) -> impl Stream<Item = Coin> + '_ {
-        stream::unfold(
-            (vec![], None, true, coin_type),
-            move |(mut data, cursor, first, coin_type)| async move {
-                if let Some(item) = data.pop() {
-                    Some((item, (data, cursor, false, coin_type)))
-                } else if (cursor.is_none() && first) || cursor.is_some() {
-                    let page = self
-                        .get_coins(owner, coin_type.clone(), cursor, Some(100))
-                        .await
-                        .ok()?;
-                    let mut data = page.data;
-                    data.reverse();
-                    data.pop()
-                        .map(|item| (item, (data, page.next_cursor, false, coin_type)))
-                } else {
-                    None
-                }
-            },
-        )
+        self.get_coins_stream_with_options(owner, coin_type, StreamOptions::default())
+            .filter_map(|result| future::ready(result.ok()))
+    }
+
+    pub fn get_coins_stream_with_options(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+        options: StreamOptions,
+    ) -> impl Stream<Item = SuiRpcResult<Coin>> + '_ {
+        paginated_stream(None, options, move |cursor, limit| {
+            self.get_coins(owner, coin_type.clone(), cursor, Some(limit))
+        })
     }
 
     pub async fn select_coins(
@@ -308,26 +578,83 @@ impl CoinReadApi {
         locked_until_epoch: Option<EpochId>,
         exclude: Vec<ObjectID>,
     ) -> SuiRpcResult<Vec<Coin>> {
-        let mut total = 0u128;
-        let coins = self
-            .get_coins_stream(address, coin_type)
-            .filter(|coin: &Coin| {
+        let selection = CoinSelection {
+            locked_until_epoch,
+            exclude,
+            ..Default::default()
+        };
+        self.select_coins_with_strategy(address, coin_type, amount, &selection)
+            .await
+    }
+
+    /// Select coins of `address` whose balances add up to at least `amount`, following the
+    /// strategy and constraints of `selection`.
+    pub async fn select_coins_with_strategy(
+        &self,
+        address: SuiAddress,
+        coin_type: Option<String>,
+        amount: u128,
+        selection: &CoinSelection,
+    ) -> SuiRpcResult<Vec<Coin>> {
+        let eligible_coins = self
+            .get_coins_stream_with_options(address, coin_type, StreamOptions::default())
+            .try_filter(|coin: &Coin| {
                 future::ready(
-                    locked_until_epoch == coin.locked_until_epoch
-                        && !exclude.contains(&coin.coin_object_id),
+                    selection.locked_until_epoch == coin.locked_until_epoch
+                        && !selection.exclude.contains(&coin.coin_object_id),
                 )
-            })
-            .take_while(|coin: &Coin| {
-                let ready = future::ready(total < amount);
-                total += coin.balance as u128;
-                ready
-            })
-            .collect::<Vec<_>>()
-            .await;
+            });
+
+        let coins = match selection.strategy {
+            // Stop fetching coins once the amount is covered.
+            CoinSelectionStrategy::FirstFit => {
+                let mut total = 0u128;
+                eligible_coins
+                    .try_take_while(|coin: &Coin| {
+                        let ready = future::ready(Ok(total < amount));
+                        total += coin.balance as u128;
+                        ready
+                    })
+                    .try_collect::<Vec<_>>()
+                    .await?
+            }
+            CoinSelectionStrategy::LargestFirst => {
+                let mut coins = eligible_coins.try_collect::<Vec<_>>().await?;
+                coins.sort_by_key(|coin| Reverse(coin.balance));
+                take_coins_until(coins, amount)
+            }
+            CoinSelectionStrategy::SmallestFirst => {
+                let mut coins = eligible_coins.try_collect::<Vec<_>>().await?;
+                coins.sort_by_key(|coin| coin.balance);
+                take_coins_until(coins, amount)
+            }
+            CoinSelectionStrategy::MinimizeInputCount => {
+                let mut coins = eligible_coins.try_collect::<Vec<_>>().await?;
+                coins.sort_by_key(|coin| Reverse(coin.balance));
+                // The smallest coin covering the amount on its own, if any.
+                match coins
+                    .iter()
+                    .rposition(|coin| coin.balance as u128 >= amount)
+                {
+                    Some(index) => vec![coins.swap_remove(index)],
+                    None => take_coins_until(coins, amount),
+                }
+            }
+        };
 
+        let total: u128 = coins.iter().map(|coin| coin.balance as u128).sum();
         if total < amount {
             return Err(Error::InsufficientFund { address, amount });
         }
+        if let Some(max_coins) = selection.max_coins {
+            if coins.len() > max_coins {
+                return Err(Error::TooManyCoins {
+                    address,
+                    amount,
+                    max_coins,
+                });
+            }
+        }
         Ok(coins)
     }
 
@@ -352,6 +679,45 @@ impl CoinReadApi {
     }
 }
 
+/// The order in which [CoinReadApi::select_coins_with_strategy] picks coins.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CoinSelectionStrategy {
+    /// Coins in the order they are returned by the fullnode.
+    #[default]
+    FirstFit,
+    /// The largest coins first, which takes the fewest coins among greedy selections.
+    LargestFirst,
+    /// The smallest coins first, which consolidates dust coins into the payment.
+    SmallestFirst,
+    /// The smallest coin covering the amount on its own, falling back to the largest coins first.
+    MinimizeInputCount,
+}
+
+/// Constraints of a coin selection.
+#[derive(Clone, Debug, Default)]
+pub struct CoinSelection {
+    pub strategy: CoinSelectionStrategy,
+    /// Only select coins locked until this epoch, or unlocked coins if not set.
+    pub locked_until_epoch: Option<EpochId>,
+    /// Coins that must not be selected, e.g. because they are reserved by other transactions.
+    pub exclude: Vec<ObjectID>,
+    /// Maximum number of coins a transaction can take, e.g. the protocol's
+    /// `max_gas_payment_objects` for gas coins.
+    pub max_coins: Option<usize>,
+}
+
+fn take_coins_until(coins: Vec<Coin>, amount: u128) -> Vec<Coin> {
+    let mut total = 0u128;
+    coins
+        .into_iter()
+        .take_while(|coin| {
+            let take = total < amount;
+            total += coin.balance as u128;
+            take
+        })
+        .collect()
+}
+
 #[derive(Clone)]
 pub struct EventApi {
     api: Arc<RpcClient>,
@@ -365,12 +731,33 @@ impl EventApi {
     pub async fn subscribe_event(
         &self,
         filter: SuiEventFilter,
+    ) -> SuiRpcResult<impl Stream<Item = SuiRpcResult<SuiEventEnvelope>>> {
+        self.subscribe_event_with_cursor(filter, None).await
+    }
+
+    /// Resume an event subscription after the exclusive `cursor`, events stored by the fullnode
+    /// after the cursor are replayed before new events are streamed.
+    pub async fn subscribe_event_from(
+        &self,
+        filter: SuiEventFilter,
+        cursor: EventID,
+    ) -> SuiRpcResult<impl Stream<Item = SuiRpcResult<SuiEventEnvelope>>> {
+        self.subscribe_event_with_cursor(filter, Some(cursor)).await
+    }
+
+    async fn subscribe_event_with_cursor(
+        &self,
+        filter: SuiEventFilter,
+        cursor: Option<EventID>,
     ) -> SuiRpcResult<impl Stream<Item = SuiRpcResult<SuiEventEnvelope>>> {
         match &self.api.ws {
             Some(c) => {
-                let subscription: Subscription<SuiEventEnvelope> =
-                    c.subscribe_event(filter).await?;
-                Ok(subscription.map(|item| Ok(item?)))
+                let subscription: Subscription<EventSubscriptionItem> =
+                    c.subscribe_event(filter, cursor).await?;
+                Ok(subscription.map(|item| match item? {
+                    SubscriptionItem::Data(envelope) => Ok(envelope),
+                    SubscriptionItem::Lagged { lagged } => Err(Error::SubscriptionLagged(lagged)),
+                }))
             }
             _ => Err(Error::Subscription(
                 "Subscription only supported by WebSocket client.".to_string(),
@@ -392,31 +779,35 @@ impl EventApi {
             .await?)
     }
 
+    /// Stream the events matching `query`, the stream ends at the first error.
     pub fn get_events_stream(
         &self,
         query: EventQuery,
         cursor: Option<EventID>,
         descending_order: bool,
     ) -> impl Stream<Item = SuiEventEnvelope> + '_ {
-        stream::unfold(
-            (vec![], cursor, true, query),
-            move |(mut data, cursor, first, query)| async move {
-                if let Some(item) = data.pop() {
-                    Some((item, (data, cursor, false, query)))
-                } else if (cursor.is_none() && first) || cursor.is_some() {
-                    let page = self
-                        .get_events(query.clone(), cursor, Some(100), descending_order)
-                        .await
-                        .ok()?;
-                    let mut data = page.data;
-                    data.reverse();
-                    data.pop()
-                        .map(|item| (item, (data, page.next_cursor, false, query)))
-                } else {
-                    None
-                }
-            },
+        self.get_events_stream_with_options(
+            query,
+            cursor,
+            descending_order,
+            StreamOptions::default(),
         )
+        .filter_map(|result| future::ready(result.ok()))
+    }
+
+    /// Stream the events matching `query` following the exclusive `cursor`. Unlike
+    /// [EventApi::subscribe_event], following the tip with [StreamOptions::follow] only needs an
+    /// HTTP client, and only makes sense in ascending order.
+    pub fn get_events_stream_with_options(
+        &self,
+        query: EventQuery,
+        cursor: Option<EventID>,
+        descending_order: bool,
+        options: StreamOptions,
+    ) -> impl Stream<Item = SuiRpcResult<SuiEventEnvelope>> + '_ {
+        paginated_stream(cursor, options, move |cursor, limit| {
+            self.get_events(query.clone(), cursor, Some(limit), descending_order)
+        })
     }
 }
 
@@ -480,6 +871,23 @@ impl QuorumDriver {
         })
     }
 
+    /// Execute a transaction with the signatures of all of its signers, e.g. a sponsored
+    /// transaction signed by both the sender and the gas owner. The signatures are verified
+    /// against `tx_data` before the transaction is submitted, see [Self::execute_transaction]
+    /// for `request_type`.
+    pub async fn execute_transaction_with_signatures(
+        &self,
+        tx_data: TransactionData,
+        signatures: Vec<GenericSignature>,
+        options: SuiTransactionResponseOptions,
+        request_type: Option<ExecuteTransactionRequestType>,
+    ) -> SuiRpcResult<SuiTransactionResponse> {
+        let tx = Transaction::from_generic_sig_data(tx_data, Intent::default(), signatures)
+            .verify()
+            .map_err(|e| Error::DataError(format!("Invalid transaction signatures: {e}")))?;
+        self.execute_transaction(tx, options, request_type).await
+    }
+
     async fn wait_until_fullnode_sees_tx(
         c: &RpcClient,
         tx_digest: TransactionDigest,
@@ -546,3 +954,181 @@ impl GovernanceApi {
         Ok(self.api.http.get_reference_gas_price().await?)
     }
 }
+
+/// Resolve a normalized Move type to a type tag, substituting the struct's `type_params`.
+fn normalized_type_tag(type_: &SuiMoveNormalizedType, type_params: &[TypeTag]) -> Option<TypeTag> {
+    Some(match type_ {
+        SuiMoveNormalizedType::Bool => TypeTag::Bool,
+        SuiMoveNormalizedType::U8 => TypeTag::U8,
+        SuiMoveNormalizedType::U16 => TypeTag::U16,
+        SuiMoveNormalizedType::U32 => TypeTag::U32,
+        SuiMoveNormalizedType::U64 => TypeTag::U64,
+        SuiMoveNormalizedType::U128 => TypeTag::U128,
+        SuiMoveNormalizedType::U256 => TypeTag::U256,
+        SuiMoveNormalizedType::Address => TypeTag::Address,
+        SuiMoveNormalizedType::Vector(type_) => {
+            TypeTag::Vector(Box::new(normalized_type_tag(type_, type_params)?))
+        }
+        SuiMoveNormalizedType::Struct {
+            address,
+            module,
+            name,
+            type_arguments,
+        } => TypeTag::Struct(Box::new(StructTag {
+            address: AccountAddress::from_hex_literal(address).ok()?,
+            module: Identifier::new(module.as_str()).ok()?,
+            name: Identifier::new(name.as_str()).ok()?,
+            type_params: type_arguments
+                .iter()
+                .map(|type_| normalized_type_tag(type_, type_params))
+                .collect::<Option<_>>()?,
+        })),
+        SuiMoveNormalizedType::TypeParameter(index) => type_params.get(*index as usize)?.clone(),
+        SuiMoveNormalizedType::Signer
+        | SuiMoveNormalizedType::Reference(_)
+        | SuiMoveNormalizedType::MutableReference(_) => return None,
+    })
+}
+
+/// Settings of the paginated streams, e.g. [ReadApi::get_checkpoints_stream].
+#[derive(Clone, Debug)]
+pub struct StreamOptions {
+    /// Number of items requested per page.
+    pub page_size: usize,
+    /// Number of times a page request failing with a transient error is retried before the
+    /// error is yielded and the stream ends.
+    pub max_retries: usize,
+    /// Delay before the first retry, doubled after every failed attempt.
+    pub retry_backoff: Duration,
+    /// When set, the stream does not end after the last item but polls for new items at this
+    /// interval.
+    pub follow: Option<Duration>,
+}
+
+impl Default for StreamOptions {
+    fn default() -> Self {
+        Self {
+            page_size: 100,
+            max_retries: 3,
+            retry_backoff: Duration::from_millis(200),
+            follow: None,
+        }
+    }
+}
+
+impl StreamOptions {
+    /// Keep the stream open after the last item, polling for new items every `poll_interval`.
+    pub fn follow(mut self, poll_interval: Duration) -> Self {
+        self.follow = Some(poll_interval);
+        self
+    }
+
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        self.page_size = page_size;
+        self
+    }
+
+    pub fn with_retries(mut self, max_retries: usize, retry_backoff: Duration) -> Self {
+        self.max_retries = max_retries;
+        self.retry_backoff = retry_backoff;
+        self
+    }
+}
+
+struct PaginatedStreamState<T, C, F> {
+    data: VecDeque<T>,
+    cursor: Option<C>,
+    done: bool,
+    options: StreamOptions,
+    fetch_page: F,
+}
+
+/// Turns a paginated query into a stream of items. `fetch_page` is called with the cursor and the
+/// page size, and the `next_cursor` of each page is the exclusive cursor of the next request.
+fn paginated_stream<'a, T, C, F, Fut>(
+    cursor: Option<C>,
+    options: StreamOptions,
+    fetch_page: F,
+) -> impl Stream<Item = SuiRpcResult<T>> + 'a
+where
+    T: 'a,
+    C: Clone + 'a,
+    F: Fn(Option<C>, usize) -> Fut + 'a,
+    Fut: Future<Output = SuiRpcResult<Page<T, C>>> + 'a,
+{
+    let state = PaginatedStreamState {
+        data: VecDeque::new(),
+        cursor,
+        done: false,
+        options,
+        fetch_page,
+    };
+    stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(item) = state.data.pop_front() {
+                return Some((Ok(item), state));
+            }
+            if state.done {
+                return None;
+            }
+            let page = retry_transient(&state.options, || {
+                (state.fetch_page)(state.cursor.clone(), state.options.page_size)
+            })
+            .await;
+            let page = match page {
+                Ok(page) => page,
+                Err(e) => {
+                    state.done = true;
+                    return Some((Err(e), state));
+                }
+            };
+            let reached_end = !page.has_next_page || page.data.is_empty();
+            // An empty page has no cursor of its own, keep polling from the previous one.
+            if page.next_cursor.is_some() {
+                state.cursor = page.next_cursor;
+            }
+            state.data.extend(page.data);
+            if reached_end {
+                match state.options.follow {
+                    Some(poll_interval) if state.data.is_empty() => {
+                        tokio::time::sleep(poll_interval).await
+                    }
+                    Some(_) => {}
+                    None => state.done = true,
+                }
+            }
+        }
+    })
+}
+
+/// Runs `request`, retrying errors that are likely to go away, such as timeouts and connection
+/// failures, with an exponential backoff.
+async fn retry_transient<T, F, Fut>(options: &StreamOptions, request: F) -> SuiRpcResult<T>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = SuiRpcResult<T>>,
+{
+    let mut backoff = options.retry_backoff;
+    let mut attempt = 0;
+    loop {
+        match request().await {
+            Err(e) if attempt < options.max_retries && is_transient(&e) => {
+                attempt += 1;
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+            }
+            result => return result,
+        }
+    }
+}
+
+fn is_transient(error: &Error) -> bool {
+    matches!(
+        error,
+        Error::RpcError(
+            jsonrpsee::core::Error::Transport(_)
+                | jsonrpsee::core::Error::RequestTimeout
+                | jsonrpsee::core::Error::RestartNeeded(_)
+        )
+    )
+}
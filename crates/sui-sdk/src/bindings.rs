@@ -0,0 +1,577 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Generates typed Rust bindings for the entry and public functions of a Move package, from the
+//! normalized modules returned by `sui_getNormalizedMoveModulesByPackage`. Each function becomes
+//! a Rust function adding a Move call to a [ProgrammableTransactionBuilder]:
+//!
+//! ```ignore
+//! // build.rs
+//! let client = SuiClientBuilder::default().build(rpc_url).await?;
+//! let bindings = generate_package_bindings(client.read_api(), package_id).await?;
+//! std::fs::write(out_dir.join("my_package.rs"), bindings)?;
+//!
+//! // src/lib.rs
+//! mod my_package {
+//!     include!(concat!(env!("OUT_DIR"), "/my_package.rs"));
+//! }
+//!
+//! let mut builder = ProgrammableTransactionBuilder::new();
+//! let counter = my_package::counter::create(&mut builder, 10)?;
+//! my_package::counter::increment(&mut builder, counter, 1)?;
+//! ```
+//!
+//! Pure parameters take their Rust value, e.g. `u64`, `SuiAddress` or `Vec<u8>`, object
+//! parameters take an [ObjectInput] and vectors of objects take a list of [ObjectArg]. Move type
+//! parameters are passed as an array of type tags, and `TxContext` parameters are left out.
+//!
+//! Structs whose fields all have a Rust counterpart, i.e. primitives, strings, `UID`, `ID`,
+//! options, vectors and other such structs of the package, become Rust structs deriving
+//! [MoveStruct](crate::MoveStruct), so objects can be decoded with
+//! [ReadApi::get_typed_object]:
+//!
+//! ```ignore
+//! let counter: my_package::counter::Counter = client.read_api().get_typed_object(id).await?;
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
+use sui_json_rpc_types::{
+    SuiMoveNormalizedModule, SuiMoveNormalizedStruct, SuiMoveNormalizedType, SuiMoveVisibility,
+};
+use sui_types::base_types::ObjectID;
+use sui_types::messages::{Argument, ObjectArg};
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_types::{MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS};
+
+use crate::apis::ReadApi;
+use crate::error::SuiRpcResult;
+
+pub use move_core_types::language_storage::TypeTag;
+pub use move_core_types::u256::U256;
+pub use serde;
+
+/// The result of a generated Move call, the call's result argument.
+pub type CallResult = anyhow::Result<Argument>;
+
+/// An object argument of a generated Move call: an input object, or the result of a previous
+/// command in the same transaction.
+#[derive(Clone, Copy, Debug)]
+pub enum ObjectInput {
+    Object(ObjectArg),
+    Argument(Argument),
+}
+
+impl ObjectInput {
+    pub fn into_argument(self, builder: &mut ProgrammableTransactionBuilder) -> CallResult {
+        match self {
+            ObjectInput::Object(object) => builder.obj(object),
+            ObjectInput::Argument(argument) => Ok(argument),
+        }
+    }
+}
+
+impl From<ObjectArg> for ObjectInput {
+    fn from(object: ObjectArg) -> Self {
+        ObjectInput::Object(object)
+    }
+}
+
+impl From<Argument> for ObjectInput {
+    fn from(argument: Argument) -> Self {
+        ObjectInput::Argument(argument)
+    }
+}
+
+/// Adds the Move call of a generated binding to `builder`.
+pub fn move_call(
+    builder: &mut ProgrammableTransactionBuilder,
+    package: ObjectID,
+    module: &str,
+    function: &str,
+    type_arguments: Vec<TypeTag>,
+    arguments: Vec<Argument>,
+) -> CallResult {
+    Ok(builder.programmable_move_call(
+        package,
+        Identifier::new(module)?,
+        Identifier::new(function)?,
+        type_arguments,
+        arguments,
+    ))
+}
+
+/// Fetch the modules of `package` and generate its bindings.
+pub async fn generate_package_bindings(
+    read_api: &ReadApi,
+    package: ObjectID,
+) -> SuiRpcResult<String> {
+    let modules = read_api
+        .get_normalized_move_modules_by_package(package)
+        .await?;
+    Ok(generate_bindings(package, &modules))
+}
+
+/// Generate the Rust source of the bindings of `package`, with a module per Move module and a
+/// function per entry or public function.
+pub fn generate_bindings(
+    package: ObjectID,
+    modules: &BTreeMap<String, SuiMoveNormalizedModule>,
+) -> String {
+    let mut out = String::new();
+    write_bindings(&mut out, package, modules).expect("Writing to a String cannot fail");
+    out
+}
+
+fn write_bindings(
+    out: &mut String,
+    package: ObjectID,
+    modules: &BTreeMap<String, SuiMoveNormalizedModule>,
+) -> std::fmt::Result {
+    writeln!(
+        out,
+        "// Bindings of package {package}, generated by `sui_sdk::bindings`. Do not edit."
+    )?;
+    writeln!(out)?;
+    writeln!(
+        out,
+        "pub const PACKAGE_ID: ::sui_sdk::types::base_types::ObjectID =\n    \
+        ::sui_sdk::types::base_types::ObjectID::new({:?});",
+        AccountAddress::from(package).into_bytes()
+    )?;
+
+    let supported_structs = supported_structs(package, modules);
+    for (module_name, module) in modules {
+        let structs: Vec<_> = module
+            .structs
+            .iter()
+            .filter(|(struct_name, _)| {
+                supported_structs.contains(&(module_name.clone(), (*struct_name).clone()))
+            })
+            .collect();
+        let functions: Vec<_> = module
+            .exposed_functions
+            .iter()
+            .filter(|(_, function)| {
+                function.is_entry || matches!(function.visibility, SuiMoveVisibility::Public)
+            })
+            .collect();
+        if structs.is_empty() && functions.is_empty() {
+            continue;
+        }
+
+        writeln!(out)?;
+        writeln!(out, "pub mod {} {{", rust_identifier(module_name))?;
+        writeln!(
+            out,
+            "    #![allow(clippy::too_many_arguments, non_camel_case_types)]"
+        )?;
+        for (struct_name, struct_) in structs {
+            write_struct(
+                out,
+                package,
+                module_name,
+                struct_name,
+                struct_,
+                &supported_structs,
+            )?;
+        }
+        for (function_name, function) in functions {
+            let parameters: Vec<_> = function
+                .parameters
+                .iter()
+                .filter_map(Parameter::new)
+                .collect();
+
+            writeln!(out)?;
+            writeln!(
+                out,
+                "    /// `{}{}fun {module_name}::{function_name}({})`",
+                if matches!(function.visibility, SuiMoveVisibility::Public) {
+                    "public "
+                } else {
+                    ""
+                },
+                if function.is_entry { "entry " } else { "" },
+                function
+                    .parameters
+                    .iter()
+                    .map(move_type)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )?;
+            writeln!(out, "    pub fn {}(", rust_identifier(function_name))?;
+            writeln!(
+                out,
+                "        builder: &mut ::sui_sdk::types::programmable_transaction_builder::ProgrammableTransactionBuilder,"
+            )?;
+            if !function.type_parameters.is_empty() {
+                writeln!(
+                    out,
+                    "        type_arguments: [::sui_sdk::bindings::TypeTag; {}],",
+                    function.type_parameters.len()
+                )?;
+            }
+            for (i, parameter) in parameters.iter().enumerate() {
+                writeln!(out, "        arg{i}: {},", parameter.rust_type())?;
+            }
+            writeln!(out, "    ) -> ::sui_sdk::bindings::CallResult {{")?;
+            writeln!(out, "        let arguments = vec![")?;
+            for (i, parameter) in parameters.iter().enumerate() {
+                writeln!(
+                    out,
+                    "            {},",
+                    parameter.argument(&format!("arg{i}"))
+                )?;
+            }
+            writeln!(out, "        ];")?;
+            writeln!(out, "        ::sui_sdk::bindings::move_call(")?;
+            writeln!(out, "            builder,")?;
+            writeln!(out, "            super::PACKAGE_ID,")?;
+            writeln!(out, "            {module_name:?},")?;
+            writeln!(out, "            {function_name:?},")?;
+            if function.type_parameters.is_empty() {
+                writeln!(out, "            vec![],")?;
+            } else {
+                writeln!(out, "            type_arguments.to_vec(),")?;
+            }
+            writeln!(out, "            arguments,")?;
+            writeln!(out, "        )")?;
+            writeln!(out, "    }}")?;
+        }
+        writeln!(out, "}}")?;
+    }
+    Ok(())
+}
+
+fn write_struct(
+    out: &mut String,
+    package: ObjectID,
+    module_name: &str,
+    struct_name: &str,
+    struct_: &SuiMoveNormalizedStruct,
+    supported_structs: &BTreeSet<(String, String)>,
+) -> std::fmt::Result {
+    let type_parameters: Vec<_> = (0..struct_.type_parameters.len())
+        .map(|i| format!("T{i}"))
+        .collect();
+
+    writeln!(out)?;
+    writeln!(out, "    /// `struct {module_name}::{struct_name}`")?;
+    writeln!(
+        out,
+        "    #[derive(Debug, ::sui_sdk::bindings::serde::Deserialize, ::sui_sdk::MoveStruct)]"
+    )?;
+    writeln!(out, "    #[serde(crate = \"::sui_sdk::bindings::serde\")]")?;
+    writeln!(
+        out,
+        "    #[move_struct(address = \"{}\", module = \"{module_name}\", name = \"{struct_name}\")]",
+        package.to_hex_literal()
+    )?;
+    if type_parameters.is_empty() {
+        writeln!(out, "    pub struct {} {{", rust_identifier(struct_name))?;
+    } else {
+        writeln!(
+            out,
+            "    pub struct {}<{}> {{",
+            rust_identifier(struct_name),
+            type_parameters.join(", ")
+        )?;
+    }
+    for field in &struct_.fields {
+        let rust_type = field_type(&field.type_, package, supported_structs)
+            .expect("Only structs with supported field types are generated");
+        writeln!(
+            out,
+            "        pub {}: {rust_type},",
+            rust_identifier(&field.name)
+        )?;
+    }
+    // Rust rejects unused type parameters, they are marked as used with `PhantomData`, which has
+    // no BCS representation.
+    for (i, type_parameter) in type_parameters.iter().enumerate() {
+        if !struct_
+            .fields
+            .iter()
+            .any(|field| uses_type_parameter(&field.type_, i as u16))
+        {
+            writeln!(
+                out,
+                "        pub phantom_{}: ::std::marker::PhantomData<{type_parameter}>,",
+                type_parameter.to_lowercase()
+            )?;
+        }
+    }
+    writeln!(out, "    }}")
+}
+
+/// The structs of `package` that bindings are generated for: those whose fields all have a Rust
+/// counterpart, see [field_type].
+fn supported_structs(
+    package: ObjectID,
+    modules: &BTreeMap<String, SuiMoveNormalizedModule>,
+) -> BTreeSet<(String, String)> {
+    let mut supported: BTreeSet<_> = modules
+        .iter()
+        .flat_map(|(module_name, module)| {
+            module
+                .structs
+                .keys()
+                .map(move |struct_name| (module_name.clone(), struct_name.clone()))
+        })
+        .collect();
+    // Removing a struct can make the structs with a field of its type unsupported.
+    loop {
+        let unsupported: Vec<_> = supported
+            .iter()
+            .filter(|(module_name, struct_name)| {
+                modules[module_name].structs[struct_name]
+                    .fields
+                    .iter()
+                    .any(|field| field_type(&field.type_, package, &supported).is_none())
+            })
+            .cloned()
+            .collect();
+        if unsupported.is_empty() {
+            return supported;
+        }
+        for key in unsupported {
+            supported.remove(&key);
+        }
+    }
+}
+
+/// The Rust type of a struct field of type `type_`, which has the same BCS encoding and
+/// implements `MoveTypeTagTrait`. Structs of `package` map to their generated bindings.
+fn field_type(
+    type_: &SuiMoveNormalizedType,
+    package: ObjectID,
+    supported_structs: &BTreeSet<(String, String)>,
+) -> Option<String> {
+    Some(match type_ {
+        SuiMoveNormalizedType::Bool => "bool".to_string(),
+        SuiMoveNormalizedType::U8 => "u8".to_string(),
+        SuiMoveNormalizedType::U16 => "u16".to_string(),
+        SuiMoveNormalizedType::U32 => "u32".to_string(),
+        SuiMoveNormalizedType::U64 => "u64".to_string(),
+        SuiMoveNormalizedType::U128 => "u128".to_string(),
+        SuiMoveNormalizedType::U256 => "::sui_sdk::bindings::U256".to_string(),
+        SuiMoveNormalizedType::Address => "::sui_sdk::types::base_types::SuiAddress".to_string(),
+        SuiMoveNormalizedType::Vector(inner) => {
+            format!("Vec<{}>", field_type(inner, package, supported_structs)?)
+        }
+        SuiMoveNormalizedType::TypeParameter(index) => format!("T{index}"),
+        SuiMoveNormalizedType::Struct {
+            address,
+            module,
+            name,
+            type_arguments,
+        } => {
+            if is_struct(type_, MOVE_STDLIB_ADDRESS, "string", "String") {
+                "String".to_string()
+            } else if is_struct(type_, SUI_FRAMEWORK_ADDRESS, "object", "UID") {
+                "::sui_sdk::types::id::UID".to_string()
+            } else if is_struct(type_, SUI_FRAMEWORK_ADDRESS, "object", "ID") {
+                "::sui_sdk::types::id::ID".to_string()
+            } else if is_struct(type_, MOVE_STDLIB_ADDRESS, "option", "Option") {
+                format!(
+                    "Option<{}>",
+                    field_type(type_arguments.first()?, package, supported_structs)?
+                )
+            } else if AccountAddress::from_hex_literal(address).ok()
+                == Some(AccountAddress::from(package))
+                && supported_structs.contains(&(module.clone(), name.clone()))
+            {
+                let path = format!(
+                    "super::{}::{}",
+                    rust_identifier(module),
+                    rust_identifier(name)
+                );
+                if type_arguments.is_empty() {
+                    path
+                } else {
+                    let type_arguments = type_arguments
+                        .iter()
+                        .map(|type_| field_type(type_, package, supported_structs))
+                        .collect::<Option<Vec<_>>>()?;
+                    format!("{path}<{}>", type_arguments.join(", "))
+                }
+            } else {
+                return None;
+            }
+        }
+        _ => return None,
+    })
+}
+
+fn uses_type_parameter(type_: &SuiMoveNormalizedType, index: u16) -> bool {
+    match type_ {
+        SuiMoveNormalizedType::TypeParameter(i) => *i == index,
+        SuiMoveNormalizedType::Vector(inner)
+        | SuiMoveNormalizedType::Reference(inner)
+        | SuiMoveNormalizedType::MutableReference(inner) => uses_type_parameter(inner, index),
+        SuiMoveNormalizedType::Struct { type_arguments, .. } => type_arguments
+            .iter()
+            .any(|type_| uses_type_parameter(type_, index)),
+        _ => false,
+    }
+}
+
+/// How a Move parameter is passed to the generated function.
+enum Parameter {
+    /// A BCS encoded value of the given Rust type.
+    Pure(String),
+    /// An object, or the result of a previous command.
+    Object,
+    /// A vector of objects.
+    ObjectVec,
+    /// Parameters typed by a type parameter can be anything, they take the raw argument.
+    Argument,
+}
+
+impl Parameter {
+    /// Returns `None` for the `TxContext`, which is provided by the runtime.
+    fn new(type_: &SuiMoveNormalizedType) -> Option<Self> {
+        let type_ = match type_ {
+            SuiMoveNormalizedType::Reference(type_)
+            | SuiMoveNormalizedType::MutableReference(type_) => type_.as_ref(),
+            type_ => type_,
+        };
+        if is_struct(type_, SUI_FRAMEWORK_ADDRESS, "tx_context", "TxContext") {
+            return None;
+        }
+        Some(if let Some(rust_type) = pure_type(type_) {
+            Parameter::Pure(rust_type)
+        } else {
+            match type_ {
+                SuiMoveNormalizedType::Struct { .. } => Parameter::Object,
+                SuiMoveNormalizedType::Vector(inner)
+                    if matches!(**inner, SuiMoveNormalizedType::Struct { .. }) =>
+                {
+                    Parameter::ObjectVec
+                }
+                _ => Parameter::Argument,
+            }
+        })
+    }
+
+    fn rust_type(&self) -> String {
+        match self {
+            Parameter::Pure(rust_type) => rust_type.clone(),
+            Parameter::Object => "impl Into<::sui_sdk::bindings::ObjectInput>".to_string(),
+            Parameter::ObjectVec => "Vec<::sui_sdk::types::messages::ObjectArg>".to_string(),
+            Parameter::Argument => "::sui_sdk::types::messages::Argument".to_string(),
+        }
+    }
+
+    fn argument(&self, name: &str) -> String {
+        match self {
+            Parameter::Pure(_) => format!("builder.pure({name})?"),
+            Parameter::Object => format!("{name}.into().into_argument(builder)?"),
+            Parameter::ObjectVec => format!("builder.make_obj_vec({name})?"),
+            Parameter::Argument => name.to_string(),
+        }
+    }
+}
+
+/// The Rust type with the BCS encoding of a Move type that can be passed as a pure value.
+fn pure_type(type_: &SuiMoveNormalizedType) -> Option<String> {
+    Some(match type_ {
+        SuiMoveNormalizedType::Bool => "bool".to_string(),
+        SuiMoveNormalizedType::U8 => "u8".to_string(),
+        SuiMoveNormalizedType::U16 => "u16".to_string(),
+        SuiMoveNormalizedType::U32 => "u32".to_string(),
+        SuiMoveNormalizedType::U64 => "u64".to_string(),
+        SuiMoveNormalizedType::U128 => "u128".to_string(),
+        SuiMoveNormalizedType::U256 => "::sui_sdk::bindings::U256".to_string(),
+        SuiMoveNormalizedType::Address => "::sui_sdk::types::base_types::SuiAddress".to_string(),
+        SuiMoveNormalizedType::Vector(inner) => format!("Vec<{}>", pure_type(inner)?),
+        SuiMoveNormalizedType::Struct { type_arguments, .. } => {
+            if is_struct(type_, MOVE_STDLIB_ADDRESS, "string", "String")
+                || is_struct(type_, MOVE_STDLIB_ADDRESS, "ascii", "String")
+            {
+                "String".to_string()
+            } else if is_struct(type_, SUI_FRAMEWORK_ADDRESS, "object", "ID") {
+                "::sui_sdk::types::base_types::ObjectID".to_string()
+            } else if is_struct(type_, MOVE_STDLIB_ADDRESS, "option", "Option") {
+                format!("Option<{}>", pure_type(type_arguments.first()?)?)
+            } else {
+                return None;
+            }
+        }
+        _ => return None,
+    })
+}
+
+fn is_struct(
+    type_: &SuiMoveNormalizedType,
+    expected_address: AccountAddress,
+    expected_module: &str,
+    expected_name: &str,
+) -> bool {
+    match type_ {
+        SuiMoveNormalizedType::Struct {
+            address,
+            module,
+            name,
+            ..
+        } => {
+            AccountAddress::from_hex_literal(address).ok() == Some(expected_address)
+                && module == expected_module
+                && name == expected_name
+        }
+        _ => false,
+    }
+}
+
+/// Renders a normalized type the way it is written in Move, for the doc comments.
+fn move_type(type_: &SuiMoveNormalizedType) -> String {
+    match type_ {
+        SuiMoveNormalizedType::Bool => "bool".to_string(),
+        SuiMoveNormalizedType::U8 => "u8".to_string(),
+        SuiMoveNormalizedType::U16 => "u16".to_string(),
+        SuiMoveNormalizedType::U32 => "u32".to_string(),
+        SuiMoveNormalizedType::U64 => "u64".to_string(),
+        SuiMoveNormalizedType::U128 => "u128".to_string(),
+        SuiMoveNormalizedType::U256 => "u256".to_string(),
+        SuiMoveNormalizedType::Address => "address".to_string(),
+        SuiMoveNormalizedType::Signer => "signer".to_string(),
+        SuiMoveNormalizedType::Struct {
+            address,
+            module,
+            name,
+            type_arguments,
+        } => {
+            if type_arguments.is_empty() {
+                format!("{address}::{module}::{name}")
+            } else {
+                let type_arguments: Vec<_> = type_arguments.iter().map(move_type).collect();
+                format!("{address}::{module}::{name}<{}>", type_arguments.join(", "))
+            }
+        }
+        SuiMoveNormalizedType::Vector(inner) => format!("vector<{}>", move_type(inner)),
+        SuiMoveNormalizedType::TypeParameter(index) => format!("T{index}"),
+        SuiMoveNormalizedType::Reference(inner) => format!("&{}", move_type(inner)),
+        SuiMoveNormalizedType::MutableReference(inner) => format!("&mut {}", move_type(inner)),
+    }
+}
+
+/// Move identifiers that are Rust keywords are escaped as raw identifiers, or suffixed with an
+/// underscore for the keywords that cannot be raw.
+fn rust_identifier(identifier: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do",
+        "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in", "let",
+        "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+        "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+        "virtual", "where", "while", "yield",
+    ];
+    match identifier {
+        "self" | "Self" | "super" | "crate" | "_" => format!("{identifier}_"),
+        identifier if KEYWORDS.contains(&identifier) => format!("r#{identifier}"),
+        identifier => identifier.to_string(),
+    }
+}
@@ -1,7 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use sui_types::base_types::{SuiAddress, TransactionDigest};
+use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
 use sui_types::error::UserInputError;
 use thiserror::Error;
 
@@ -17,6 +17,8 @@ pub enum Error {
     UserInputError(#[from] UserInputError),
     #[error("Subscription error : {0}")]
     Subscription(String),
+    #[error("Subscription lagged behind, {0} items were dropped")]
+    SubscriptionLagged(u64),
     #[error("Encountered error when confirming tx status for {0:?}, err: {1:?}")]
     TransactionConfirmationError(TransactionDigest, jsonrpsee::core::Error),
     #[error("Failed to confirm tx status for {0:?} within {1} seconds.")]
@@ -30,4 +32,18 @@ pub enum Error {
     },
     #[error("Insufficient fund for address [{address}], requested amount: {amount}")]
     InsufficientFund { address: SuiAddress, amount: u128 },
+    #[error("Paying amount {amount} from address [{address}] takes more than {max_coins} coins")]
+    TooManyCoins {
+        address: SuiAddress,
+        amount: u128,
+        max_coins: usize,
+    },
+    #[error("Object [{object_id}] has type {actual}, expected {expected}")]
+    ObjectTypeMismatch {
+        object_id: ObjectID,
+        expected: String,
+        actual: String,
+    },
+    #[error("Rust type does not match the layout of Move struct {struct_tag}: {reason}")]
+    StructLayoutMismatch { struct_tag: String, reason: String },
 }
@@ -16,17 +16,25 @@ use crate::error::{Error, SuiRpcResult};
 use serde_json::Value;
 use sui_adapter::execution_mode::Normal;
 pub use sui_json as json;
+pub use sui_proc_macros::MoveStruct;
 
-use crate::apis::{CoinReadApi, EventApi, GovernanceApi, QuorumDriver, ReadApi};
+use crate::apis::{CoinReadApi, CoinSelection, EventApi, GovernanceApi, QuorumDriver, ReadApi};
+use shared_crypto::intent::Intent;
 use sui_json_rpc::{
     CLIENT_SDK_TYPE_HEADER, CLIENT_SDK_VERSION_HEADER, CLIENT_TARGET_API_VERSION_HEADER,
 };
 pub use sui_json_rpc_types as rpc_types;
-use sui_json_rpc_types::{SuiObjectDataOptions, SuiObjectInfo, SuiObjectResponse};
+use sui_json_rpc_types::{
+    Coin, DryRunTransactionResponse, SuiExecutionStatus, SuiObjectDataOptions, SuiObjectInfo,
+    SuiObjectResponse, SuiTransactionEffectsAPI, SuiTransactionResponseOptions,
+};
+use sui_keys::keystore::{AccountKeystore, Keystore};
 use sui_transaction_builder::{DataReader, TransactionBuilder};
 pub use sui_types as types;
 use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::messages::{ExecuteTransactionRequestType, Transaction, TransactionData};
 pub mod apis;
+pub mod bindings;
 pub mod error;
 pub const SUI_COIN_TYPE: &str = "0x2::sui::SUI";
 const WAIT_FOR_TX_TIMEOUT_SEC: u64 = 60;
@@ -243,6 +251,79 @@ impl SuiClient {
     pub fn governance_api(&self) -> &GovernanceApi {
         &self.governance_api
     }
+
+    /// Select coins like [CoinReadApi::select_coins_with_strategy]. When the amount takes more
+    /// than `selection.max_coins` coins, the coins are first merged in batches of at most
+    /// `max_coins`, with transactions signed by `keystore`, and selected again.
+    pub async fn select_coins_with_merge(
+        &self,
+        keystore: &Keystore,
+        address: SuiAddress,
+        coin_type: Option<String>,
+        amount: u128,
+        selection: &CoinSelection,
+    ) -> SuiRpcResult<Vec<Coin>> {
+        let max_coins = selection.max_coins.unwrap_or(usize::MAX).max(2);
+        let unbounded = CoinSelection {
+            max_coins: None,
+            ..selection.clone()
+        };
+        loop {
+            match self
+                .coin_read_api
+                .select_coins_with_strategy(address, coin_type.clone(), amount, selection)
+                .await
+            {
+                Err(Error::TooManyCoins { .. }) => {}
+                result => return result,
+            }
+
+            // Every round merges each batch into a single coin, so the selection shrinks.
+            let coins = self
+                .coin_read_api
+                .select_coins_with_strategy(address, coin_type.clone(), amount, &unbounded)
+                .await?;
+            for batch in coins.chunks(max_coins).filter(|batch| batch.len() > 1) {
+                let batch: Vec<_> = batch.iter().map(|coin| coin.coin_object_id).collect();
+                self.merge_coins_batch(keystore, address, batch).await?;
+            }
+        }
+    }
+
+    async fn merge_coins_batch(
+        &self,
+        keystore: &Keystore,
+        address: SuiAddress,
+        coins: Vec<ObjectID>,
+    ) -> SuiRpcResult<()> {
+        let builder = &self.transaction_builder;
+        let data = builder
+            .build_with_gas_budget(None, |gas_budget| {
+                builder.merge_coins_batch(address, coins.clone(), None, gas_budget)
+            })
+            .await
+            .map_err(|e| Error::DataError(e.to_string()))?;
+        let signature = keystore
+            .sign_secure(&address, &data, Intent::default())
+            .map_err(|e| Error::DataError(e.to_string()))?;
+        let transaction = Transaction::from_data(data, Intent::default(), vec![signature])
+            .verify()
+            .map_err(|e| Error::DataError(e.to_string()))?;
+        let response = self
+            .quorum_driver
+            .execute_transaction(
+                transaction,
+                SuiTransactionResponseOptions::new().with_effects(),
+                Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+            )
+            .await?;
+        match response.effects.as_ref().map(|effects| effects.status()) {
+            Some(SuiExecutionStatus::Success) => Ok(()),
+            status => Err(Error::DataError(format!(
+                "Merging coins {coins:?} failed with status {status:?}"
+            ))),
+        }
+    }
 }
 
 #[async_trait]
@@ -265,4 +346,11 @@ impl DataReader for ReadApi {
     async fn get_reference_gas_price(&self) -> Result<u64, anyhow::Error> {
         Ok(self.get_reference_gas_price().await?)
     }
+
+    async fn dry_run_transaction(
+        &self,
+        tx: TransactionData,
+    ) -> Result<DryRunTransactionResponse, anyhow::Error> {
+        Ok(self.dry_run_transaction(tx).await?)
+    }
 }
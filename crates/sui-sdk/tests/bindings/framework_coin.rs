@@ -0,0 +1,109 @@
+// Bindings of package 0x0000000000000000000000000000000000000000000000000000000000000002, generated by `sui_sdk::bindings`. Do not edit.
+
+pub const PACKAGE_ID: ::sui_sdk::types::base_types::ObjectID =
+    ::sui_sdk::types::base_types::ObjectID::new([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2]);
+
+pub mod balance {
+    #![allow(clippy::too_many_arguments, non_camel_case_types)]
+
+    /// `struct balance::Balance`
+    #[derive(Debug, ::sui_sdk::bindings::serde::Deserialize, ::sui_sdk::MoveStruct)]
+    #[serde(crate = "::sui_sdk::bindings::serde")]
+    #[move_struct(address = "0x2", module = "balance", name = "Balance")]
+    pub struct Balance<T0> {
+        pub value: u64,
+        pub phantom_t0: ::std::marker::PhantomData<T0>,
+    }
+
+    /// `struct balance::Supply`
+    #[derive(Debug, ::sui_sdk::bindings::serde::Deserialize, ::sui_sdk::MoveStruct)]
+    #[serde(crate = "::sui_sdk::bindings::serde")]
+    #[move_struct(address = "0x2", module = "balance", name = "Supply")]
+    pub struct Supply<T0> {
+        pub value: u64,
+        pub phantom_t0: ::std::marker::PhantomData<T0>,
+    }
+}
+
+pub mod coin {
+    #![allow(clippy::too_many_arguments, non_camel_case_types)]
+
+    /// `struct coin::Coin`
+    #[derive(Debug, ::sui_sdk::bindings::serde::Deserialize, ::sui_sdk::MoveStruct)]
+    #[serde(crate = "::sui_sdk::bindings::serde")]
+    #[move_struct(address = "0x2", module = "coin", name = "Coin")]
+    pub struct Coin<T0> {
+        pub id: ::sui_sdk::types::id::UID,
+        pub balance: super::balance::Balance<T0>,
+    }
+
+    /// `struct coin::CurrencyCreated`
+    #[derive(Debug, ::sui_sdk::bindings::serde::Deserialize, ::sui_sdk::MoveStruct)]
+    #[serde(crate = "::sui_sdk::bindings::serde")]
+    #[move_struct(address = "0x2", module = "coin", name = "CurrencyCreated")]
+    pub struct CurrencyCreated<T0> {
+        pub decimals: u8,
+        pub phantom_t0: ::std::marker::PhantomData<T0>,
+    }
+
+    /// `struct coin::TreasuryCap`
+    #[derive(Debug, ::sui_sdk::bindings::serde::Deserialize, ::sui_sdk::MoveStruct)]
+    #[serde(crate = "::sui_sdk::bindings::serde")]
+    #[move_struct(address = "0x2", module = "coin", name = "TreasuryCap")]
+    pub struct TreasuryCap<T0> {
+        pub id: ::sui_sdk::types::id::UID,
+        pub total_supply: super::balance::Supply<T0>,
+    }
+
+    /// `public fun coin::split(&mut 0x2::coin::Coin<T0>, u64, &mut 0x2::tx_context::TxContext)`
+    pub fn split(
+        builder: &mut ::sui_sdk::types::programmable_transaction_builder::ProgrammableTransactionBuilder,
+        type_arguments: [::sui_sdk::bindings::TypeTag; 1],
+        arg0: impl Into<::sui_sdk::bindings::ObjectInput>,
+        arg1: u64,
+    ) -> ::sui_sdk::bindings::CallResult {
+        let arguments = vec![
+            arg0.into().into_argument(builder)?,
+            builder.pure(arg1)?,
+        ];
+        ::sui_sdk::bindings::move_call(
+            builder,
+            super::PACKAGE_ID,
+            "coin",
+            "split",
+            type_arguments.to_vec(),
+            arguments,
+        )
+    }
+
+    /// `public fun coin::value(&0x2::coin::Coin<T0>)`
+    pub fn value(
+        builder: &mut ::sui_sdk::types::programmable_transaction_builder::ProgrammableTransactionBuilder,
+        type_arguments: [::sui_sdk::bindings::TypeTag; 1],
+        arg0: impl Into<::sui_sdk::bindings::ObjectInput>,
+    ) -> ::sui_sdk::bindings::CallResult {
+        let arguments = vec![
+            arg0.into().into_argument(builder)?,
+        ];
+        ::sui_sdk::bindings::move_call(
+            builder,
+            super::PACKAGE_ID,
+            "coin",
+            "value",
+            type_arguments.to_vec(),
+            arguments,
+        )
+    }
+}
+
+pub mod sui {
+    #![allow(clippy::too_many_arguments, non_camel_case_types)]
+
+    /// `struct sui::SUI`
+    #[derive(Debug, ::sui_sdk::bindings::serde::Deserialize, ::sui_sdk::MoveStruct)]
+    #[serde(crate = "::sui_sdk::bindings::serde")]
+    #[move_struct(address = "0x2", module = "sui", name = "SUI")]
+    pub struct SUI {
+        pub dummy_field: bool,
+    }
+}
@@ -0,0 +1,152 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use move_binary_format::normalized;
+use shared_crypto::intent::Intent;
+use std::collections::BTreeMap;
+use sui_keys::keystore::AccountKeystore;
+use sui_sdk::bindings::generate_bindings;
+use sui_sdk::rpc_types::{
+    SuiMoveNormalizedModule, SuiTransactionEffectsAPI, SuiTransactionResponseOptions,
+};
+use sui_sdk::types::gas_coin::GAS;
+use sui_sdk::types::messages::{ExecuteTransactionRequestType, ObjectArg, Transaction};
+use sui_sdk::types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_sdk::types::SUI_FRAMEWORK_OBJECT_ID;
+use sui_sdk::{SuiClientBuilder, SUI_COIN_TYPE};
+use test_utils::network::TestClusterBuilder;
+
+/// The bindings of the coin types of the framework, as generated from [coin_modules].
+#[allow(dead_code)]
+mod framework {
+    include!("bindings/framework_coin.rs");
+}
+
+fn framework_modules() -> BTreeMap<String, SuiMoveNormalizedModule> {
+    sui_framework::get_sui_framework()
+        .iter()
+        .map(|module| {
+            let module = normalized::Module::new(module);
+            (module.name.to_string(), module.into())
+        })
+        .collect()
+}
+
+/// The modules defining `Coin<SUI>`, with only the `coin::split` and `coin::value` functions.
+fn coin_modules() -> BTreeMap<String, SuiMoveNormalizedModule> {
+    let mut modules = framework_modules();
+    modules.retain(|name, _| ["balance", "coin", "sui"].contains(&name.as_str()));
+    for (name, module) in &mut modules {
+        module.exposed_functions.retain(|function, _| {
+            name == "coin" && ["split", "value"].contains(&function.as_str())
+        });
+    }
+    modules
+}
+
+#[test]
+fn test_generate_framework_bindings() {
+    let bindings = generate_bindings(SUI_FRAMEWORK_OBJECT_ID, &framework_modules());
+
+    assert!(bindings.contains("pub mod coin {"));
+    // Object and pure parameters are typed, the TxContext is left out.
+    assert!(bindings.contains(
+        "    /// `public fun coin::split(&mut 0x2::coin::Coin<T0>, u64, &mut 0x2::tx_context::TxContext)`
+    pub fn split(
+        builder: &mut ::sui_sdk::types::programmable_transaction_builder::ProgrammableTransactionBuilder,
+        type_arguments: [::sui_sdk::bindings::TypeTag; 1],
+        arg0: impl Into<::sui_sdk::bindings::ObjectInput>,
+        arg1: u64,
+    ) -> ::sui_sdk::bindings::CallResult {
+        let arguments = vec![
+            arg0.into().into_argument(builder)?,
+            builder.pure(arg1)?,
+        ];
+        ::sui_sdk::bindings::move_call(
+            builder,
+            super::PACKAGE_ID,
+            \"coin\",
+            \"split\",
+            type_arguments.to_vec(),
+            arguments,
+        )
+    }"
+    ));
+    // Parameters typed by a type parameter take the raw argument.
+    assert!(bindings.contains(
+        "        arg0: ::sui_sdk::types::messages::Argument,
+        arg1: ::sui_sdk::types::base_types::SuiAddress,"
+    ));
+    // Friend functions cannot be called from a transaction.
+    assert!(!bindings.contains("pub fn create_staking_rewards("));
+}
+
+#[test]
+fn test_generated_bindings_fixture_is_up_to_date() {
+    assert_eq!(
+        generate_bindings(SUI_FRAMEWORK_OBJECT_ID, &coin_modules()),
+        include_str!("bindings/framework_coin.rs"),
+        "tests/bindings/framework_coin.rs must be regenerated from coin_modules()"
+    );
+}
+
+#[tokio::test]
+async fn test_generated_bindings_call_and_decode() -> Result<(), anyhow::Error> {
+    let test_cluster = TestClusterBuilder::new().build().await?;
+    let address = test_cluster.get_address_0();
+    let client = SuiClientBuilder::default()
+        .build(test_cluster.rpc_url())
+        .await?;
+    let coins = client
+        .coin_read_api()
+        .get_coins(address, Some(SUI_COIN_TYPE.to_string()), None, None)
+        .await?;
+    let coin = &coins.data[0];
+
+    // A real coin decodes into the generated struct, including the struct of another module.
+    let decoded = client
+        .read_api()
+        .get_typed_object::<framework::coin::Coin<framework::sui::SUI>>(coin.coin_object_id)
+        .await?;
+    assert_eq!(*decoded.id.object_id(), coin.coin_object_id);
+    assert_eq!(decoded.balance.value, coin.balance);
+
+    // The generated functions add their Move calls to a programmable transaction.
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let split = framework::coin::split(
+        &mut builder,
+        [GAS::type_tag()],
+        ObjectArg::ImmOrOwnedObject(coin.object_ref()),
+        1000,
+    )?;
+    builder.transfer_arg(address, split);
+    let data = client
+        .transaction_builder()
+        .programmable_transaction(address, builder.finish(), None, 10_000)
+        .await?;
+    let signature =
+        test_cluster
+            .wallet
+            .config
+            .keystore
+            .sign_secure(&address, &data, Intent::default())?;
+    let response = client
+        .quorum_driver()
+        .execute_transaction(
+            Transaction::from_data(data, Intent::default(), vec![signature]).verify()?,
+            SuiTransactionResponseOptions::new().with_effects(),
+            Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+        )
+        .await?;
+    let effects = response.effects.unwrap();
+    assert!(effects.status().is_ok());
+
+    let split_coin = client
+        .read_api()
+        .get_typed_object::<framework::coin::Coin<framework::sui::SUI>>(
+            effects.created()[0].reference.object_id,
+        )
+        .await?;
+    assert_eq!(split_coin.balance.value, 1000);
+    Ok(())
+}
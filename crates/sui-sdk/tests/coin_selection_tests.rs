@@ -0,0 +1,76 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_sdk::apis::{CoinSelection, CoinSelectionStrategy};
+use sui_sdk::error::Error;
+use sui_sdk::{SuiClientBuilder, SUI_COIN_TYPE};
+use test_utils::network::TestClusterBuilder;
+
+#[tokio::test]
+async fn test_select_coins_with_strategy() -> Result<(), anyhow::Error> {
+    let test_cluster = TestClusterBuilder::new().build().await?;
+    let address = test_cluster.get_address_0();
+    let rpc_url = test_cluster.rpc_url();
+
+    let client = SuiClientBuilder::default().build(rpc_url).await?;
+    let coin_type = Some(SUI_COIN_TYPE.to_string());
+    let coins = client
+        .coin_read_api()
+        .get_coins(address, coin_type.clone(), None, None)
+        .await?
+        .data;
+    let balance = coins[0].balance as u128;
+
+    // A single coin covers less than one coin's balance.
+    let selection = CoinSelection {
+        strategy: CoinSelectionStrategy::MinimizeInputCount,
+        ..Default::default()
+    };
+    let selected = client
+        .coin_read_api()
+        .select_coins_with_strategy(address, coin_type.clone(), balance / 2, &selection)
+        .await?;
+    assert_eq!(1, selected.len());
+
+    // Excluded coins are never selected.
+    let selection = CoinSelection {
+        strategy: CoinSelectionStrategy::SmallestFirst,
+        exclude: vec![coins[0].coin_object_id],
+        ..Default::default()
+    };
+    let selected = client
+        .coin_read_api()
+        .select_coins_with_strategy(address, coin_type.clone(), balance * 2, &selection)
+        .await?;
+    assert_eq!(2, selected.len());
+    assert!(selected
+        .iter()
+        .all(|coin| coin.coin_object_id != coins[0].coin_object_id));
+
+    // Two and a half coins do not fit in two coins until they are merged.
+    let amount = balance * 5 / 2;
+    let selection = CoinSelection {
+        strategy: CoinSelectionStrategy::LargestFirst,
+        max_coins: Some(2),
+        ..Default::default()
+    };
+    let result = client
+        .coin_read_api()
+        .select_coins_with_strategy(address, coin_type.clone(), amount, &selection)
+        .await;
+    assert!(matches!(result, Err(Error::TooManyCoins { .. })));
+
+    let selected = client
+        .select_coins_with_merge(
+            &test_cluster.wallet.config.keystore,
+            address,
+            coin_type,
+            amount,
+            &selection,
+        )
+        .await?;
+    assert!(selected.len() <= 2);
+    let total: u128 = selected.iter().map(|coin| coin.balance as u128).sum();
+    assert!(total >= amount);
+    Ok(())
+}
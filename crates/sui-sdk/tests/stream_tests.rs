@@ -3,8 +3,10 @@
 
 use futures::StreamExt;
 use std::future;
+use std::time::Duration;
 use sui::client_commands::SuiClientCommands;
 use sui_json_rpc_types::SuiTransactionResponseQuery;
+use sui_sdk::apis::StreamOptions;
 use sui_sdk::{SuiClientBuilder, SUI_COIN_TYPE};
 use sui_types::event::EventType;
 use sui_types::query::EventQuery;
@@ -170,3 +172,71 @@ async fn test_coins_stream() -> Result<(), anyhow::Error> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_checkpoints_stream() -> Result<(), anyhow::Error> {
+    let test_cluster = TestClusterBuilder::new().build().await?;
+    let rpc_url = test_cluster.rpc_url();
+
+    let client = SuiClientBuilder::default().build(rpc_url).await?;
+    let checkpoints = client
+        .read_api()
+        .get_checkpoints_stream(None, false, StreamOptions::default().with_page_size(2))
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()?;
+
+    assert!(!checkpoints.is_empty());
+    for (i, checkpoint) in checkpoints.iter().enumerate() {
+        assert_eq!(i as u64, checkpoint.sequence_number);
+    }
+
+    // Follow mode keeps yielding checkpoints past the tip seen by the first stream.
+    let tip = checkpoints.last().unwrap().sequence_number;
+    let followed = tokio::time::timeout(
+        Duration::from_secs(60),
+        client
+            .read_api()
+            .get_checkpoints_stream(
+                Some(tip),
+                false,
+                StreamOptions::default().follow(Duration::from_millis(100)),
+            )
+            .take(3)
+            .collect::<Vec<_>>(),
+    )
+    .await?
+    .into_iter()
+    .collect::<Result<Vec<_>, _>>()?;
+
+    let sequence_numbers = followed
+        .iter()
+        .map(|checkpoint| checkpoint.sequence_number)
+        .collect::<Vec<_>>();
+    assert_eq!(vec![tip + 1, tip + 2, tip + 3], sequence_numbers);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_owned_objects_stream() -> Result<(), anyhow::Error> {
+    let test_cluster = TestClusterBuilder::new().build().await?;
+    let address = test_cluster.get_address_0();
+    let rpc_url = test_cluster.rpc_url();
+
+    let client = SuiClientBuilder::default().build(rpc_url).await?;
+    let objects = client
+        .read_api()
+        .get_owned_objects_stream(address, StreamOptions::default())
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let expected = client
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?;
+    assert_eq!(expected.len(), objects.len());
+    Ok(())
+}
@@ -0,0 +1,197 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use move_core_types::identifier::Identifier;
+use serde::Deserialize;
+use shared_crypto::intent::Intent;
+use std::marker::PhantomData;
+use sui_keys::keystore::AccountKeystore;
+use sui_sdk::error::Error;
+use sui_sdk::rpc_types::{SuiTransactionEffectsAPI, SuiTransactionResponseOptions};
+use sui_sdk::types::base_types::ObjectID;
+use sui_sdk::types::id::UID;
+use sui_sdk::types::messages::{Argument, Command, ExecuteTransactionRequestType, Transaction};
+use sui_sdk::types::object::Owner;
+use sui_sdk::types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_sdk::types::{MoveTypeTagTrait, SUI_FRAMEWORK_OBJECT_ID};
+use sui_sdk::{MoveStruct, SuiClient, SuiClientBuilder, SUI_COIN_TYPE};
+use test_utils::network::{TestCluster, TestClusterBuilder};
+
+#[derive(Deserialize, MoveStruct)]
+#[allow(dead_code)]
+#[move_struct(address = "0x2", module = "sui", name = "SUI")]
+struct Sui {
+    dummy_field: bool,
+}
+
+#[derive(Deserialize, MoveStruct)]
+#[allow(dead_code)]
+#[move_struct(address = "0x2", module = "balance")]
+struct Balance<T> {
+    value: u64,
+    coin_type: PhantomData<T>,
+}
+
+#[derive(Deserialize, MoveStruct)]
+#[move_struct(address = "0x2", module = "coin")]
+struct Coin<T> {
+    id: UID,
+    balance: Balance<T>,
+}
+
+#[derive(Deserialize, MoveStruct)]
+#[allow(dead_code)]
+#[move_struct(address = "0x2", module = "coin", name = "Coin")]
+struct FlatCoin<T> {
+    id: UID,
+    value: u64,
+    coin_type: PhantomData<T>,
+}
+
+#[tokio::test]
+async fn test_get_typed_object() -> Result<(), anyhow::Error> {
+    let test_cluster = TestClusterBuilder::new().build().await?;
+    let address = test_cluster.get_address_0();
+    let rpc_url = test_cluster.rpc_url();
+
+    let client = SuiClientBuilder::default().build(rpc_url).await?;
+    let coins = client
+        .coin_read_api()
+        .get_coins(address, Some(SUI_COIN_TYPE.to_string()), None, None)
+        .await?;
+    let gas = &coins.data[0];
+
+    let coin = client
+        .read_api()
+        .get_typed_object::<Coin<Sui>>(gas.coin_object_id)
+        .await?;
+    assert_eq!(gas.coin_object_id, *coin.id.object_id());
+    assert_eq!(gas.balance, coin.balance.value);
+
+    // Same struct tag, but the fields do not match the on-chain struct.
+    let result = client
+        .read_api()
+        .get_typed_object::<FlatCoin<Sui>>(gas.coin_object_id)
+        .await;
+    assert!(matches!(result, Err(Error::StructLayoutMismatch { .. })));
+
+    let result = client
+        .read_api()
+        .get_typed_object::<Balance<Sui>>(gas.coin_object_id)
+        .await;
+    assert!(matches!(result, Err(Error::ObjectTypeMismatch { .. })));
+    Ok(())
+}
+
+/// Creates a `module::Bag` owned by the active address, holding a coin of `value` MIST under the
+/// name `0u64`. `module` is `bag` or `object_bag`, which add the coin as a dynamic field or as a
+/// dynamic object field.
+async fn create_bag_with_coin(
+    test_cluster: &TestCluster,
+    client: &SuiClient,
+    module: &str,
+    value: u64,
+) -> Result<ObjectID, anyhow::Error> {
+    let address = test_cluster.get_address_0();
+    let module = Identifier::new(module)?;
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let amount = builder.pure(value)?;
+    let coin = builder.command(Command::SplitCoin(Argument::GasCoin, amount));
+    let bag = builder.programmable_move_call(
+        SUI_FRAMEWORK_OBJECT_ID,
+        module.clone(),
+        Identifier::new("new")?,
+        vec![],
+        vec![],
+    );
+    let name = builder.pure(0u64)?;
+    builder.programmable_move_call(
+        SUI_FRAMEWORK_OBJECT_ID,
+        module,
+        Identifier::new("add")?,
+        vec![u64::get_type_tag(), Coin::<Sui>::get_type_tag()],
+        vec![bag, name, coin],
+    );
+    builder.transfer_arg(address, bag);
+
+    let data = client
+        .transaction_builder()
+        .programmable_transaction(address, builder.finish(), None, 10_000)
+        .await?;
+    let signature =
+        test_cluster
+            .wallet
+            .config
+            .keystore
+            .sign_secure(&address, &data, Intent::default())?;
+    let response = client
+        .quorum_driver()
+        .execute_transaction(
+            Transaction::from_data(data, Intent::default(), vec![signature]).verify()?,
+            SuiTransactionResponseOptions::new().with_effects(),
+            Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+        )
+        .await?;
+    let effects = response.effects.unwrap();
+    assert!(effects.status().is_ok());
+    // The fields are owned by the bag, only the bag is owned by the address.
+    Ok(effects
+        .created()
+        .iter()
+        .find(|object| object.owner == Owner::AddressOwner(address))
+        .unwrap()
+        .reference
+        .object_id)
+}
+
+#[tokio::test]
+async fn test_get_typed_dynamic_field() -> Result<(), anyhow::Error> {
+    let test_cluster = TestClusterBuilder::new().build().await?;
+    let client = SuiClientBuilder::default()
+        .build(test_cluster.rpc_url())
+        .await?;
+    let bag = create_bag_with_coin(&test_cluster, &client, "bag", 1000).await?;
+
+    let coin = client
+        .read_api()
+        .get_typed_dynamic_field::<u64, Coin<Sui>>(bag, &0)
+        .await?;
+    assert_eq!(1000, coin.balance.value);
+
+    // No field of that name.
+    assert!(client
+        .read_api()
+        .get_typed_dynamic_field::<u64, Coin<Sui>>(bag, &1)
+        .await
+        .is_err());
+    // The coin is stored in the field itself, not as a dynamic object field.
+    assert!(client
+        .read_api()
+        .get_typed_dynamic_object_field::<u64, Coin<Sui>>(bag, &0)
+        .await
+        .is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_typed_dynamic_object_field() -> Result<(), anyhow::Error> {
+    let test_cluster = TestClusterBuilder::new().build().await?;
+    let client = SuiClientBuilder::default()
+        .build(test_cluster.rpc_url())
+        .await?;
+    let bag = create_bag_with_coin(&test_cluster, &client, "object_bag", 1000).await?;
+
+    let coin = client
+        .read_api()
+        .get_typed_dynamic_object_field::<u64, Coin<Sui>>(bag, &0)
+        .await?;
+    assert_eq!(1000, coin.balance.value);
+
+    // Dynamic object fields are named by a wrapper of the name, there is no plain field.
+    assert!(client
+        .read_api()
+        .get_typed_dynamic_field::<u64, Coin<Sui>>(bag, &0)
+        .await
+        .is_err());
+    Ok(())
+}
@@ -5,7 +5,9 @@
 //! The main user of this data is the explorer.
 
 use std::cmp::{max, min};
+use std::iter::Peekable;
 use std::path::PathBuf;
+use std::str::FromStr;
 use std::sync::atomic::{AtomicU64, Ordering};
 
 use anyhow::anyhow;
@@ -23,7 +25,8 @@ use sui_types::event::EventType;
 use sui_types::fp_ensure;
 use sui_types::messages::TransactionEvents;
 use sui_types::object::Owner;
-use sui_types::query::TransactionFilter;
+use sui_types::parse_sui_struct_tag;
+use sui_types::query::{EventQuery, TransactionFilter};
 use typed_store::rocks::DBOptions;
 use typed_store::rocks::{DBMap, MetricConf};
 use typed_store::traits::Map;
@@ -34,8 +37,10 @@ use crate::default_db_options;
 
 type OwnerIndexKey = (SuiAddress, ObjectID);
 type DynamicFieldKey = (ObjectID, ObjectID);
-type EventId = (TxSequenceNumber, usize);
-type EventIndex = (TransactionEventsDigest, TransactionDigest, u64);
+pub type EventId = (TxSequenceNumber, usize);
+pub type EventIndex = (TransactionEventsDigest, TransactionDigest, u64);
+
+type EventIndexIterator<'a> = Box<dyn Iterator<Item = SuiResult<(EventId, EventIndex)>> + 'a>;
 
 pub const MAX_TX_RANGE_SIZE: u64 = 4096;
 
@@ -354,6 +359,24 @@ impl IndexStore {
         self.next_sequence_number.load(Ordering::SeqCst) + 1
     }
 
+    /// The first sequence number in `from..to` allocated by `index_tx` whose transaction is not
+    /// committed to the store yet. Transactions are committed after their sequence number is
+    /// allocated, and not necessarily in sequence order.
+    pub fn first_uncommitted_sequence_number(
+        &self,
+        from: TxSequenceNumber,
+        to: TxSequenceNumber,
+    ) -> SuiResult<Option<TxSequenceNumber>> {
+        let mut expected = from;
+        for (seq, _) in self.tables.transaction_order.iter().skip_to(&from)? {
+            if expected >= to || seq != expected {
+                break;
+            }
+            expected += 1;
+        }
+        Ok((expected < to).then_some(expected))
+    }
+
     pub fn get_transactions(
         &self,
         filter: Option<TransactionFilter>,
@@ -689,6 +712,24 @@ impl IndexStore {
         })
     }
 
+    /// Returns up to `limit` events strictly after the (tx_seq, event_seq) position in index order,
+    /// keyed by their position. Used to replay events to subscribers resuming from a cursor.
+    pub fn events_after(
+        &self,
+        tx_seq: TxSequenceNumber,
+        event_seq: usize,
+        limit: usize,
+    ) -> SuiResult<Vec<(EventId, EventIndex)>> {
+        Ok(self
+            .tables
+            .event_order
+            .iter()
+            .skip_to(&(tx_seq, event_seq))?
+            .skip_while(|(id, _)| id == &(tx_seq, event_seq))
+            .take(limit)
+            .collect())
+    }
+
     pub fn events_by_transaction(
         &self,
         digest: &TransactionDigest,
@@ -901,6 +942,285 @@ impl IndexStore {
         })
     }
 
+    /// Returns up to `limit` events matching the query, starting from the (tx_seq, event_seq)
+    /// position inclusive. Composed queries are driven by an indexed criterion and the remaining
+    /// criteria are checked with point lookups. Results are in event order, or in timestamp order
+    /// if the query is driven by a `TimeRange`. Queries without an indexed criterion are rejected.
+    pub fn events_by_query(
+        &self,
+        query: &EventQuery,
+        tx_seq: TxSequenceNumber,
+        event_seq: usize,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<(TransactionEventsDigest, TransactionDigest, usize, u64)>, anyhow::Error> {
+        let query = self.resolve_event_query(query)?;
+        Ok(self
+            .event_query_iter(&query, (tx_seq, event_seq), descending)?
+            .take(limit)
+            .map(|item| {
+                item.map(|((_, event_seq), (digest, tx_digest, time))| {
+                    (digest, tx_digest, event_seq, time)
+                })
+            })
+            .collect::<SuiResult<_>>()?)
+    }
+
+    fn resolve_event_query(&self, query: &EventQuery) -> Result<ResolvedEventQuery, anyhow::Error> {
+        Ok(match query {
+            EventQuery::All => ResolvedEventQuery::All,
+            EventQuery::Transaction(digest) => ResolvedEventQuery::Transaction(
+                self.get_transaction_seq(digest)?
+                    .ok_or(SuiError::TransactionNotFound { digest: *digest })?,
+            ),
+            EventQuery::MoveModule { package, module } => ResolvedEventQuery::MoveModule(
+                ModuleId::new((*package).into(), Identifier::from_str(module)?),
+            ),
+            EventQuery::MoveEvent(struct_name) => {
+                ResolvedEventQuery::MoveEvent(parse_sui_struct_tag(struct_name)?)
+            }
+            EventQuery::EventType(event_type) => ResolvedEventQuery::EventType(*event_type),
+            EventQuery::Sender(sender) => ResolvedEventQuery::Sender(*sender),
+            EventQuery::Recipient(recipient) => ResolvedEventQuery::Recipient(*recipient),
+            EventQuery::Object(object) => ResolvedEventQuery::Object(*object),
+            EventQuery::TimeRange {
+                start_time,
+                end_time,
+            } => ResolvedEventQuery::TimeRange {
+                start_time: *start_time,
+                end_time: *end_time,
+            },
+            EventQuery::And(queries) => ResolvedEventQuery::And(
+                queries
+                    .iter()
+                    .map(|q| self.resolve_event_query(q))
+                    .collect::<Result<_, _>>()?,
+            ),
+            EventQuery::Or(queries) => ResolvedEventQuery::Or(
+                queries
+                    .iter()
+                    .map(|q| self.resolve_event_query(q))
+                    .collect::<Result<_, _>>()?,
+            ),
+            EventQuery::Not(query) => {
+                ResolvedEventQuery::Not(Box::new(self.resolve_event_query(query)?))
+            }
+        })
+    }
+
+    fn event_query_iter<'a>(
+        &'a self,
+        query: &'a ResolvedEventQuery,
+        position: EventId,
+        descending: bool,
+    ) -> SuiResult<EventIndexIterator<'a>> {
+        Ok(match query {
+            ResolvedEventQuery::Transaction(seq) => {
+                let seq = *seq;
+                if descending {
+                    Box::new(
+                        self.tables
+                            .event_order
+                            .iter()
+                            .skip_prior_to(&min(position, (seq, usize::MAX)))?
+                            .reverse()
+                            .take_while(move |((tx, _), _)| tx == &seq)
+                            .map(Ok),
+                    )
+                } else {
+                    Box::new(
+                        self.tables
+                            .event_order
+                            .iter()
+                            .skip_to(&max(position, (seq, 0)))?
+                            .take_while(move |((tx, _), _)| tx == &seq)
+                            .map(Ok),
+                    )
+                }
+            }
+            ResolvedEventQuery::MoveModule(module) => Self::event_index_iter(
+                &self.tables.event_by_move_module,
+                module,
+                position,
+                descending,
+            )?,
+            ResolvedEventQuery::MoveEvent(struct_name) => Self::event_index_iter(
+                &self.tables.event_by_move_event,
+                struct_name,
+                position,
+                descending,
+            )?,
+            ResolvedEventQuery::EventType(event_type) => Self::event_index_iter(
+                &self.tables.event_by_event_type,
+                event_type,
+                position,
+                descending,
+            )?,
+            ResolvedEventQuery::Sender(sender) => {
+                Self::event_index_iter(&self.tables.event_by_sender, sender, position, descending)?
+            }
+            ResolvedEventQuery::Recipient(recipient) => Self::event_index_iter(
+                &self.tables.event_by_recipient,
+                recipient,
+                position,
+                descending,
+            )?,
+            ResolvedEventQuery::Object(object) => {
+                Self::event_index_iter(&self.tables.event_by_object, object, position, descending)?
+            }
+            ResolvedEventQuery::TimeRange {
+                start_time,
+                end_time,
+            } => {
+                let (start_time, end_time) = (*start_time, *end_time);
+                // The index is ordered by timestamp first, so resume from the timestamp of the
+                // event at the position, if there is one.
+                let resume_time = self
+                    .tables
+                    .event_order
+                    .get(&position)?
+                    .map(|(_, _, time)| time);
+                if descending {
+                    let from = match resume_time {
+                        Some(time) if time <= end_time => (time, position),
+                        _ => (end_time, (TxSequenceNumber::MAX, usize::MAX)),
+                    };
+                    Box::new(
+                        self.tables
+                            .event_by_time
+                            .iter()
+                            .skip_prior_to(&from)?
+                            .reverse()
+                            .take_while(move |((time, _), _)| time >= &start_time)
+                            .map(|((_, id), index)| Ok((id, index))),
+                    )
+                } else {
+                    let from = match resume_time {
+                        Some(time) if time >= start_time => (time, position),
+                        _ => (start_time, (0, 0)),
+                    };
+                    Box::new(
+                        self.tables
+                            .event_by_time
+                            .iter()
+                            .skip_to(&from)?
+                            .take_while(move |((time, _), _)| time <= &end_time)
+                            .map(|((_, id), index)| Ok((id, index))),
+                    )
+                }
+            }
+            ResolvedEventQuery::And(queries) => match ResolvedEventQuery::driver(queries) {
+                Some(driver) => {
+                    let iter = self.event_query_iter(&queries[driver], position, descending)?;
+                    Box::new(iter.filter_map(move |item| {
+                        item.and_then(|(id, index)| {
+                            for (i, q) in queries.iter().enumerate() {
+                                if i != driver && !self.event_matches(q, &id, &index)? {
+                                    return Ok(None);
+                                }
+                            }
+                            Ok(Some((id, index)))
+                        })
+                        .transpose()
+                    }))
+                }
+                None => return Err(unindexed_event_query()),
+            },
+            ResolvedEventQuery::Or(_) if !query.is_indexed() => return Err(unindexed_event_query()),
+            ResolvedEventQuery::Or(queries) => Box::new(MergedEventIterator {
+                iters: queries
+                    .iter()
+                    .map(|q| Ok(self.event_query_iter(q, position, descending)?.peekable()))
+                    .collect::<SuiResult<_>>()?,
+                descending,
+            }),
+            ResolvedEventQuery::All | ResolvedEventQuery::Not(_) => {
+                return Err(unindexed_event_query())
+            }
+        })
+    }
+
+    fn event_index_iter<'a, KeyT: Clone + PartialEq + Serialize + DeserializeOwned>(
+        index: &'a DBMap<(KeyT, EventId), EventIndex>,
+        key: &'a KeyT,
+        position: EventId,
+        descending: bool,
+    ) -> SuiResult<EventIndexIterator<'a>> {
+        Ok(if descending {
+            Box::new(
+                index
+                    .iter()
+                    .skip_prior_to(&(key.clone(), position))?
+                    .reverse()
+                    .take_while(move |((m, _), _)| m == key)
+                    .map(|((_, id), event)| Ok((id, event))),
+            )
+        } else {
+            Box::new(
+                index
+                    .iter()
+                    .skip_to(&(key.clone(), position))?
+                    .take_while(move |((m, _), _)| m == key)
+                    .map(|((_, id), event)| Ok((id, event))),
+            )
+        })
+    }
+
+    fn event_matches(
+        &self,
+        query: &ResolvedEventQuery,
+        id: &EventId,
+        index: &EventIndex,
+    ) -> SuiResult<bool> {
+        Ok(match query {
+            ResolvedEventQuery::All => true,
+            ResolvedEventQuery::Transaction(seq) => &id.0 == seq,
+            ResolvedEventQuery::MoveModule(module) => self
+                .tables
+                .event_by_move_module
+                .contains_key(&(module.clone(), *id))?,
+            ResolvedEventQuery::MoveEvent(struct_name) => self
+                .tables
+                .event_by_move_event
+                .contains_key(&(struct_name.clone(), *id))?,
+            ResolvedEventQuery::EventType(event_type) => self
+                .tables
+                .event_by_event_type
+                .contains_key(&(*event_type, *id))?,
+            ResolvedEventQuery::Sender(sender) => {
+                self.tables.event_by_sender.contains_key(&(*sender, *id))?
+            }
+            ResolvedEventQuery::Recipient(recipient) => self
+                .tables
+                .event_by_recipient
+                .contains_key(&(*recipient, *id))?,
+            ResolvedEventQuery::Object(object) => {
+                self.tables.event_by_object.contains_key(&(*object, *id))?
+            }
+            ResolvedEventQuery::TimeRange {
+                start_time,
+                end_time,
+            } => *start_time <= index.2 && index.2 <= *end_time,
+            ResolvedEventQuery::And(queries) => {
+                for q in queries {
+                    if !self.event_matches(q, id, index)? {
+                        return Ok(false);
+                    }
+                }
+                true
+            }
+            ResolvedEventQuery::Or(queries) => {
+                for q in queries {
+                    if self.event_matches(q, id, index)? {
+                        return Ok(true);
+                    }
+                }
+                false
+            }
+            ResolvedEventQuery::Not(query) => !self.event_matches(query, id, index)?,
+        })
+    }
+
     pub fn get_dynamic_fields_iterator(
         &self,
         object: ObjectID,
@@ -984,3 +1304,150 @@ impl IndexStore {
         self.tables.owner_index.is_empty()
     }
 }
+
+/// An [EventQuery] with its criteria parsed and resolved against the index tables.
+enum ResolvedEventQuery {
+    All,
+    Transaction(TxSequenceNumber),
+    MoveModule(ModuleId),
+    MoveEvent(StructTag),
+    EventType(EventType),
+    Sender(SuiAddress),
+    Recipient(Owner),
+    Object(ObjectID),
+    TimeRange { start_time: u64, end_time: u64 },
+    And(Vec<ResolvedEventQuery>),
+    Or(Vec<ResolvedEventQuery>),
+    Not(Box<ResolvedEventQuery>),
+}
+
+impl ResolvedEventQuery {
+    /// Whether the matching events can be iterated without scanning all events.
+    fn is_indexed(&self) -> bool {
+        match self {
+            ResolvedEventQuery::All | ResolvedEventQuery::Not(_) => false,
+            ResolvedEventQuery::And(queries) => queries.iter().any(|q| q.is_indexed()),
+            // Merging needs all criteria iterated in event order.
+            ResolvedEventQuery::Or(queries) => queries
+                .iter()
+                .all(|q| q.is_indexed() && !q.is_time_ordered()),
+            _ => true,
+        }
+    }
+
+    /// Whether the matching events are iterated in timestamp order rather than event order.
+    fn is_time_ordered(&self) -> bool {
+        match self {
+            ResolvedEventQuery::TimeRange { .. } => true,
+            ResolvedEventQuery::And(queries) => {
+                Self::driver(queries).map_or(false, |driver| queries[driver].is_time_ordered())
+            }
+            _ => false,
+        }
+    }
+
+    /// The criterion iterated for an `And` query, criteria in event order are preferred.
+    fn driver(queries: &[ResolvedEventQuery]) -> Option<usize> {
+        queries
+            .iter()
+            .position(|q| q.is_indexed() && !q.is_time_ordered())
+            .or_else(|| queries.iter().position(|q| q.is_indexed()))
+    }
+}
+
+fn unindexed_event_query() -> SuiError {
+    SuiError::UnsupportedFeatureError {
+        error: "Event queries must include an indexed criterion other than All and Not, and Or \
+            queries cannot include TimeRange criteria"
+            .to_string(),
+    }
+}
+
+/// Merges event iterators sorted in the same order into a single sorted iterator without duplicates.
+struct MergedEventIterator<'a> {
+    iters: Vec<Peekable<EventIndexIterator<'a>>>,
+    descending: bool,
+}
+
+impl Iterator for MergedEventIterator<'_> {
+    type Item = SuiResult<(EventId, EventIndex)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let descending = self.descending;
+        let mut next: Option<EventId> = None;
+        for iter in self.iters.iter_mut() {
+            match iter.peek() {
+                Some(Ok((id, _))) => {
+                    if next.map_or(true, |n| if descending { *id > n } else { *id < n }) {
+                        next = Some(*id);
+                    }
+                }
+                Some(Err(_)) => return iter.next(),
+                None => {}
+            }
+        }
+        let next = next?;
+        let mut item = None;
+        for iter in self.iters.iter_mut() {
+            if matches!(iter.peek(), Some(Ok((id, _))) if id == &next) {
+                item = iter.next();
+            }
+        }
+        item
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_uncommitted_sequence_number() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = IndexStore::new(dir.path().to_path_buf());
+        let index = |store: &IndexStore| {
+            store
+                .index_tx(
+                    SuiAddress::random_for_testing_only(),
+                    std::iter::empty(),
+                    std::iter::empty(),
+                    std::iter::empty(),
+                    &TransactionEvents::default(),
+                    ObjectIndexChanges {
+                        deleted_owners: vec![],
+                        deleted_dynamic_fields: vec![],
+                        new_owners: vec![],
+                        new_dynamic_fields: vec![],
+                    },
+                    &TransactionDigest::random(),
+                    0,
+                )
+                .unwrap()
+        };
+
+        assert_eq!(0, index(&store));
+        assert_eq!(None, store.first_uncommitted_sequence_number(0, 1).unwrap());
+
+        // Sequence number 1 is allocated, but its transaction is still being written while the
+        // transaction with sequence number 2 is committed.
+        let in_flight = store.next_sequence_number.fetch_add(1, Ordering::SeqCst);
+        assert_eq!(2, index(&store));
+        assert_eq!(
+            Some(in_flight),
+            store.first_uncommitted_sequence_number(0, 3).unwrap()
+        );
+        assert_eq!(None, store.first_uncommitted_sequence_number(2, 3).unwrap());
+
+        store
+            .tables
+            .transaction_order
+            .insert(&in_flight, &TransactionDigest::random())
+            .unwrap();
+        assert_eq!(None, store.first_uncommitted_sequence_number(0, 3).unwrap());
+        // Sequence numbers that are not allocated yet are not committed either.
+        assert_eq!(
+            Some(3),
+            store.first_uncommitted_sequence_number(0, 4).unwrap()
+        );
+    }
+}
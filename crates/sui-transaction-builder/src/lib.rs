@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::collections::BTreeMap;
+use std::future::Future;
 use std::marker::PhantomData;
 use std::str::FromStr;
 use std::sync::Arc;
@@ -16,20 +17,25 @@ use move_core_types::language_storage::TypeTag;
 
 use sui_adapter::adapter::{resolve_and_type_check, CheckCallArg};
 use sui_adapter::execution_mode::ExecutionMode;
-use sui_json::{resolve_move_function_args, SuiJsonCallArg, SuiJsonValue};
+use sui_json::{
+    resolve_move_function_args, resolve_programmable_move_call_args, SuiJsonCallArg, SuiJsonValue,
+};
 use sui_json_rpc_types::{
-    RPCTransactionRequestParams, SuiData, SuiObjectDataOptions, SuiObjectInfo, SuiObjectResponse,
-    SuiTypeTag,
+    DryRunTransactionResponse, RPCTransactionRequestParams, SuiData, SuiExecutionStatus,
+    SuiObjectDataOptions, SuiObjectInfo, SuiObjectResponse, SuiTransactionEffectsAPI, SuiTypeTag,
 };
 use sui_protocol_config::ProtocolConfig;
 use sui_types::base_types::{ObjectID, ObjectRef, ObjectType, SuiAddress};
+use sui_types::committee::EpochId;
 use sui_types::error::UserInputError;
+use sui_types::gas::SuiCostTable;
 use sui_types::gas_coin::GasCoin;
 use sui_types::governance::{ADD_STAKE_MUL_COIN_FUN_NAME, WITHDRAW_STAKE_FUN_NAME};
 use sui_types::messages::{
-    Argument, CallArg, Command, InputObjectKind, ObjectArg, TransactionData, TransactionKind,
+    Argument, CallArg, Command, GasData, InputObjectKind, ObjectArg, ProgrammableTransaction,
+    TransactionData, TransactionDataAPI, TransactionExpiration, TransactionKind,
 };
-use sui_types::move_package::MovePackage;
+use sui_types::move_package::{MovePackage, UpgradeCap};
 use sui_types::object::{Object, Owner};
 use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
 use sui_types::sui_system_state::SUI_SYSTEM_MODULE_NAME;
@@ -52,17 +58,126 @@ pub trait DataReader {
     ) -> Result<SuiObjectResponse, anyhow::Error>;
 
     async fn get_reference_gas_price(&self) -> Result<u64, anyhow::Error>;
+
+    async fn dry_run_transaction(
+        &self,
+        tx: TransactionData,
+    ) -> Result<DryRunTransactionResponse, anyhow::Error>;
+}
+
+/// The percentage added on top of the dry run gas costs when estimating a gas budget.
+pub const DEFAULT_GAS_BUDGET_MARGIN: u64 = 10;
+
+/// An argument of a command added to a programmable transaction by the [TransactionBuilder].
+#[derive(Debug, Clone)]
+pub enum ProgrammableArg {
+    /// A value resolved against the expected type of the argument, like the args of a Move call.
+    Json(SuiJsonValue),
+    /// The gas coin, an input or the result of an earlier command.
+    Argument(Argument),
 }
 
 #[derive(Clone)]
 pub struct TransactionBuilder<Mode: ExecutionMode>(
     Arc<dyn DataReader + Sync + Send>,
     PhantomData<Mode>,
+    /// The expiration of the transactions built.
+    TransactionExpiration,
+    /// The margin, in percent, of the estimated gas budgets.
+    u64,
 );
 
 impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
     pub fn new(data_reader: Arc<dyn DataReader + Sync + Send>) -> Self {
-        Self(data_reader, PhantomData)
+        Self(
+            data_reader,
+            PhantomData,
+            TransactionExpiration::None,
+            DEFAULT_GAS_BUDGET_MARGIN,
+        )
+    }
+
+    /// Returns a builder of transactions that validators only sign up to the end of
+    /// `expiration_epoch`, or that never expire if it is `None`.
+    pub fn with_expiration(&self, expiration_epoch: Option<EpochId>) -> Self {
+        Self(
+            self.0.clone(),
+            PhantomData,
+            expiration_epoch.map_or(TransactionExpiration::None, TransactionExpiration::Epoch),
+            self.3,
+        )
+    }
+
+    /// Returns a builder adding `margin` percent to the dry run gas costs of the transactions
+    /// whose gas budget it estimates.
+    pub fn with_gas_budget_margin(&self, margin: u64) -> Self {
+        Self(self.0.clone(), PhantomData, self.2, margin)
+    }
+
+    /// Estimate the gas budget of `tx_data` by dry running it: the computation and storage costs
+    /// of its effects, plus the builder's margin. The dry run pays with the gas payment of
+    /// `tx_data`, which also funds the SUI paid by `PaySui` and `TransferSui`, with the largest
+    /// budget the payment coins cover. The estimate is capped at that budget.
+    pub async fn estimate_gas_budget(&self, tx_data: &TransactionData) -> anyhow::Result<u64> {
+        let mut dry_run_data = tx_data.clone();
+        let gas_data = dry_run_data.gas_data_mut();
+        let mut balance = 0u128;
+        for (id, _, _) in &gas_data.payment {
+            balance += self.get_gas_coin(*id).await?.1.value() as u128;
+        }
+        let gas_price = gas_data.price.max(1) as u128;
+        // The budget times the price has to be covered by the payment, and the budget cannot
+        // exceed the maximum transaction gas times the price.
+        let max_tx_budget =
+            (ProtocolConfig::get_for_min_version().max_tx_gas() as u128) * gas_price;
+        gas_data.budget = (balance / gas_price)
+            .min(max_tx_budget)
+            .min(u64::MAX as u128) as u64;
+        let max_budget = gas_data.budget;
+
+        let response = self.0.dry_run_transaction(dry_run_data).await?;
+        if let SuiExecutionStatus::Failure { error } = response.effects.status() {
+            return Err(anyhow!(
+                "Cannot estimate the gas budget, the dry run of the transaction failed: {error}"
+            ));
+        }
+        let gas_used = response.effects.gas_used();
+        let cost = gas_used
+            .computation_cost
+            .saturating_add(gas_used.storage_cost);
+        Ok(cost
+            .saturating_add(cost.saturating_mul(self.3) / 100)
+            .min(max_budget))
+    }
+
+    /// Build a transaction with `build` and `gas_budget`, or with an estimated gas budget if
+    /// `gas_budget` is `None`. In the latter case the transaction is built with the minimum gas
+    /// budget, so a gas coin is selected if needed, and its budget is then set to the estimate
+    /// of [Self::estimate_gas_budget], which is covered by the gas payment.
+    pub async fn build_with_gas_budget<F, Fut>(
+        &self,
+        gas_budget: Option<u64>,
+        build: F,
+    ) -> anyhow::Result<TransactionData>
+    where
+        F: Fn(u64) -> Fut,
+        Fut: Future<Output = anyhow::Result<TransactionData>>,
+    {
+        match gas_budget {
+            Some(gas_budget) => build(gas_budget).await,
+            None => {
+                let min_gas_budget = SuiCostTable::new(&ProtocolConfig::get_for_min_version())
+                    .min_gas_budget_external();
+                let mut tx_data = build(min_gas_budget).await?;
+                let gas_budget = self.estimate_gas_budget(&tx_data).await?;
+                tx_data.gas_data_mut().budget = gas_budget;
+                Ok(tx_data)
+            }
+        }
+    }
+
+    fn apply_expiration(&self, data: TransactionData) -> TransactionData {
+        data.with_expiration(self.2)
     }
 
     async fn select_gas(
@@ -83,29 +198,34 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
             let required_gas_amount = (budget as u128) * (gas_price as u128);
 
             for obj in gas_objs {
-                let response = self
-                    .0
-                    .get_object_with_options(obj.object_id, SuiObjectDataOptions::new().with_bcs())
-                    .await?;
-                let obj = response.object()?;
-                let gas: GasCoin = bcs::from_bytes(
-                    &obj.bcs
-                        .as_ref()
-                        .ok_or_else(|| anyhow!("bcs field is unexpectedly empty"))?
-                        .try_as_move()
-                        .ok_or_else(|| anyhow!("Cannot parse move object to gas object"))?
-                        .bcs_bytes,
-                )?;
-                if !input_objects.contains(&obj.object_id)
+                let (obj_ref, gas) = self.get_gas_coin(obj.object_id).await?;
+                if !input_objects.contains(&obj_ref.0)
                     && (gas.value() as u128) >= required_gas_amount
                 {
-                    return Ok(obj.object_ref());
+                    return Ok(obj_ref);
                 }
             }
             Err(anyhow!("Cannot find gas coin for signer address [{signer}] with amount sufficient for the required gas amount [{required_gas_amount}]."))
         }
     }
 
+    async fn get_gas_coin(&self, object_id: ObjectID) -> anyhow::Result<(ObjectRef, GasCoin)> {
+        let response = self
+            .0
+            .get_object_with_options(object_id, SuiObjectDataOptions::new().with_bcs())
+            .await?;
+        let obj = response.object()?;
+        let gas: GasCoin = bcs::from_bytes(
+            &obj.bcs
+                .as_ref()
+                .ok_or_else(|| anyhow!("bcs field is unexpectedly empty"))?
+                .try_as_move()
+                .ok_or_else(|| anyhow!("Cannot parse move object to gas object"))?
+                .bcs_bytes,
+        )?;
+        Ok((obj.object_ref(), gas))
+    }
+
     pub async fn transfer_object(
         &self,
         signer: SuiAddress,
@@ -122,13 +242,13 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
             .select_gas(signer, gas, gas_budget, vec![object_id], gas_price)
             .await?;
 
-        Ok(TransactionData::new(
+        Ok(self.apply_expiration(TransactionData::new(
             TransactionKind::programmable(builder.finish()),
             signer,
             gas,
             gas_budget,
             gas_price,
-        ))
+        )))
     }
 
     async fn single_transfer_object(
@@ -151,9 +271,9 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
     ) -> anyhow::Result<TransactionData> {
         let object = self.get_object_ref(sui_object_id).await?;
         let gas_price = self.0.get_reference_gas_price().await?;
-        Ok(TransactionData::new_transfer_sui(
+        Ok(self.apply_expiration(TransactionData::new_transfer_sui(
             recipient, signer, amount, object, gas_budget, gas_price,
-        ))
+        )))
     }
 
     pub async fn pay(
@@ -187,6 +307,7 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
         TransactionData::new_pay(
             signer, coin_refs, recipients, amounts, gas, gas_budget, gas_price,
         )
+        .map(|data| self.apply_expiration(data))
     }
 
     pub async fn pay_sui(
@@ -222,6 +343,7 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
             gas_budget,
             gas_price,
         )
+        .map(|data| self.apply_expiration(data))
     }
 
     pub async fn pay_all_sui(
@@ -248,14 +370,14 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
         // [0] is safe because input_coins is non-empty and coins are of same length as input_coins.
         let gas_object_ref = coin_refs.remove(0);
         let gas_price = self.0.get_reference_gas_price().await?;
-        Ok(TransactionData::new_pay_all_sui(
+        Ok(self.apply_expiration(TransactionData::new_pay_all_sui(
             signer,
             coin_refs,
             recipient,
             gas_object_ref,
             gas_budget,
             gas_price,
-        ))
+        )))
     }
 
     pub async fn move_call(
@@ -293,13 +415,13 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
             .select_gas(signer, gas, gas_budget, input_objects, gas_price)
             .await?;
 
-        Ok(TransactionData::new(
+        Ok(self.apply_expiration(TransactionData::new(
             TransactionKind::programmable(pt),
             signer,
             gas,
             gas_budget,
             gas_price,
-        ))
+        )))
     }
 
     pub async fn single_move_call(
@@ -335,7 +457,7 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
         &self,
         id: ObjectID,
         objects: &mut BTreeMap<ObjectID, Object>,
-        expected_type: SignatureToken,
+        is_mutable_ref: bool,
     ) -> Result<ObjectArg, anyhow::Error> {
         let response = self
             .0
@@ -352,7 +474,7 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
             } => ObjectArg::SharedObject {
                 id,
                 initial_shared_version,
-                mutable: matches!(expected_type, SignatureToken::MutableReference(_)),
+                mutable: is_mutable_ref,
             },
             Owner::AddressOwner(_) | Owner::ObjectOwner(_) | Owner::Immutable => {
                 ObjectArg::ImmOrOwnedObject(obj_ref)
@@ -400,15 +522,24 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
         for (arg, expected_type) in json_args_and_tokens {
             check_args.push(match arg {
                 SuiJsonCallArg::Object(id) => CheckCallArg::Object(
-                    self.get_object_arg(id, &mut objects, expected_type).await?,
+                    self.get_object_arg(
+                        id,
+                        &mut objects,
+                        matches!(expected_type, SignatureToken::MutableReference(_)),
+                    )
+                    .await?,
                 ),
                 SuiJsonCallArg::Pure(p) => CheckCallArg::Pure(p),
                 SuiJsonCallArg::ObjVec(v) => {
                     let mut object_ids = vec![];
                     for id in v {
                         object_ids.push(
-                            self.get_object_arg(id, &mut objects, expected_type.clone())
-                                .await?,
+                            self.get_object_arg(
+                                id,
+                                &mut objects,
+                                matches!(expected_type, SignatureToken::MutableReference(_)),
+                            )
+                            .await?,
                         );
                     }
                     CheckCallArg::ObjVec(object_ids)
@@ -437,6 +568,135 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
         Ok(args)
     }
 
+    /// Add a Move call to a programmable transaction. Each argument is either a JSON value
+    /// resolved against the parameter types of the function, or an [Argument] referring to the
+    /// gas coin or to the result of an earlier command. Unlike [Self::move_call], public functions
+    /// can be called, and the arguments are only type checked when the transaction is executed.
+    pub async fn programmable_move_call(
+        &self,
+        builder: &mut ProgrammableTransactionBuilder,
+        package: ObjectID,
+        module: &str,
+        function: &str,
+        type_args: Vec<SuiTypeTag>,
+        call_args: Vec<ProgrammableArg>,
+    ) -> anyhow::Result<Argument> {
+        let module = Identifier::from_str(module)?;
+        let function = Identifier::from_str(function)?;
+        let type_args = type_args
+            .into_iter()
+            .map(|ty| ty.try_into())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let package_object = self
+            .0
+            .get_object_with_options(package, SuiObjectDataOptions::bcs_lossless())
+            .await?
+            .into_object()?;
+        let move_package = package_object
+            .bcs
+            .ok_or_else(|| anyhow!("Bcs field in object [{}] is missing.", package))?
+            .try_as_package()
+            .cloned()
+            .ok_or_else(|| anyhow!("Object [{}] is not a move package.", package))?;
+        let move_package = MovePackage::new(
+            move_package.id,
+            package_object.version,
+            &move_package.module_map,
+            ProtocolConfig::get_for_min_version().max_move_package_size(),
+        )?;
+
+        let json_args = call_args
+            .iter()
+            .map(|arg| match arg {
+                ProgrammableArg::Json(value) => Some(value.clone()),
+                ProgrammableArg::Argument(_) => None,
+            })
+            .collect();
+        let resolved = resolve_programmable_move_call_args(
+            &move_package,
+            module.clone(),
+            function.clone(),
+            &type_args,
+            json_args,
+        )?;
+
+        let mut objects = BTreeMap::new();
+        let mut args = Vec::with_capacity(call_args.len());
+        for (call_arg, (resolved, expected_type)) in call_args.into_iter().zip(resolved) {
+            let is_mutable_ref = matches!(expected_type, SignatureToken::MutableReference(_));
+            args.push(match (call_arg, resolved) {
+                (ProgrammableArg::Argument(arg), _) => arg,
+                (_, Some(SuiJsonCallArg::Pure(bytes))) => builder.input(CallArg::Pure(bytes))?,
+                (_, Some(SuiJsonCallArg::Object(id))) => builder.obj(
+                    self.get_object_arg(id, &mut objects, is_mutable_ref)
+                        .await?,
+                )?,
+                (_, Some(SuiJsonCallArg::ObjVec(ids))) => {
+                    let mut objs = vec![];
+                    for id in ids {
+                        objs.push(
+                            self.get_object_arg(id, &mut objects, is_mutable_ref)
+                                .await?,
+                        );
+                    }
+                    builder.make_obj_vec(objs)?
+                }
+                (ProgrammableArg::Json(_), None) => {
+                    unreachable!("JSON arguments are always resolved")
+                }
+            });
+        }
+        Ok(builder.command(Command::move_call(
+            package, module, function, type_args, args,
+        )))
+    }
+
+    /// Add the object `id` as an input of a programmable transaction. Shared objects are taken
+    /// by mutable reference if `mutable`, otherwise by immutable reference unless the transaction
+    /// already uses them mutably.
+    pub async fn input_object(
+        &self,
+        builder: &mut ProgrammableTransactionBuilder,
+        id: ObjectID,
+        mutable: bool,
+    ) -> anyhow::Result<Argument> {
+        let obj_arg = self
+            .get_object_arg(id, &mut BTreeMap::new(), mutable)
+            .await?;
+        builder.obj(obj_arg)
+    }
+
+    /// Build a transaction from a programmable transaction. If `gas` is not provided, a gas coin
+    /// that is not an input of the transaction is selected.
+    pub async fn programmable_transaction(
+        &self,
+        sender: SuiAddress,
+        pt: ProgrammableTransaction,
+        gas: Option<ObjectID>,
+        gas_budget: u64,
+    ) -> anyhow::Result<TransactionData> {
+        let inputs = pt
+            .input_objects()?
+            .iter()
+            .flat_map(|obj| match obj {
+                InputObjectKind::ImmOrOwnedMoveObject((id, _, _)) => Some(*id),
+                _ => None,
+            })
+            .collect();
+        let gas_price = self.0.get_reference_gas_price().await?;
+        let gas = self
+            .select_gas(sender, gas, gas_budget, inputs, gas_price)
+            .await?;
+        Ok(self.apply_expiration(TransactionData::new(
+            TransactionKind::programmable(pt),
+            sender,
+            gas,
+            gas_budget,
+            gas_price,
+        )))
+    }
+
     pub async fn publish(
         &self,
         sender: SuiAddress,
@@ -448,13 +708,70 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
         let gas = self
             .select_gas(sender, gas, gas_budget, vec![], gas_price)
             .await?;
-        Ok(TransactionData::new_module(
+        Ok(self.apply_expiration(TransactionData::new_module(
+            sender,
+            gas,
+            compiled_modules,
+            gas_budget,
+            gas_price,
+        )))
+    }
+
+    /// Upgrade the package governed by the `UpgradeCap` object `upgrade_capability` to
+    /// `compiled_modules`, linked against the packages in `dep_ids`.
+    pub async fn upgrade(
+        &self,
+        sender: SuiAddress,
+        compiled_modules: Vec<Vec<u8>>,
+        dep_ids: Vec<ObjectID>,
+        upgrade_capability: ObjectID,
+        upgrade_policy: u8,
+        gas: Option<ObjectID>,
+        gas_budget: u64,
+    ) -> anyhow::Result<TransactionData> {
+        let gas_price = self.0.get_reference_gas_price().await?;
+        let gas = self
+            .select_gas(sender, gas, gas_budget, vec![upgrade_capability], gas_price)
+            .await?;
+
+        let upgrade_cap: Object = self
+            .0
+            .get_object_with_options(upgrade_capability, SuiObjectDataOptions::bcs_lossless())
+            .await?
+            .into_object()?
+            .try_into()?;
+        let cap_type = upgrade_cap.struct_tag();
+        ensure!(
+            cap_type == Some(UpgradeCap::type_()),
+            "Object [{upgrade_capability}] is not an UpgradeCap, found type {cap_type:?}"
+        );
+        let upgrade_cap = match upgrade_cap.owner {
+            Owner::Shared {
+                initial_shared_version,
+            } => ObjectArg::SharedObject {
+                id: upgrade_capability,
+                initial_shared_version,
+                mutable: true,
+            },
+            Owner::AddressOwner(_) | Owner::ObjectOwner(_) | Owner::Immutable => {
+                ObjectArg::ImmOrOwnedObject(upgrade_cap.compute_object_reference())
+            }
+        };
+
+        let digest =
+            MovePackage::compute_digest_for_modules_and_deps(&compiled_modules, &dep_ids).to_vec();
+        TransactionData::new_upgrade(
             sender,
             gas,
+            upgrade_cap,
+            upgrade_policy,
+            digest,
+            dep_ids,
             compiled_modules,
             gas_budget,
             gas_price,
-        ))
+        )
+        .map(|data| self.apply_expiration(data))
     }
 
     // TODO: consolidate this with Pay transactions
@@ -493,6 +810,7 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
             gas_budget,
             gas_price,
         )
+        .map(|data| self.apply_expiration(data))
     }
 
     // TODO: consolidate this with Pay transactions
@@ -531,6 +849,7 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
             gas_budget,
             gas_price,
         )
+        .map(|data| self.apply_expiration(data))
     }
 
     // TODO: consolidate this with Pay transactions
@@ -576,6 +895,38 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
             gas_budget,
             gas_price,
         )
+        .map(|data| self.apply_expiration(data))
+    }
+
+    /// Merge all `coins` into the first of them. Without a `gas` coin, SUI coins pay for their own
+    /// merge, while other coins are merged with a gas coin selected from the signer's coins.
+    pub async fn merge_coins_batch(
+        &self,
+        signer: SuiAddress,
+        coins: Vec<ObjectID>,
+        gas: Option<ObjectID>,
+        gas_budget: u64,
+    ) -> anyhow::Result<TransactionData> {
+        fp_ensure!(!coins.is_empty(), UserInputError::EmptyInputCoins.into());
+        let primary_coin = self
+            .0
+            .get_object_with_options(coins[0], SuiObjectDataOptions::new().with_type())
+            .await?
+            .into_object()?;
+        if gas.is_none() && primary_coin.object_type()?.is_gas_coin() {
+            return self.pay_all_sui(signer, coins, signer, gas_budget).await;
+        }
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let mut arguments = vec![];
+        for coin in coins {
+            let coin_ref = self.get_object_ref(coin).await?;
+            arguments.push(builder.obj(ObjectArg::ImmOrOwnedObject(coin_ref))?);
+        }
+        let primary_coin = arguments.remove(0);
+        builder.command(Command::MergeCoins(primary_coin, arguments));
+        self.programmable_transaction(signer, builder.finish(), gas, gas_budget)
+            .await
     }
 
     pub async fn batch_transaction(
@@ -626,13 +977,13 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
             .select_gas(signer, gas, gas_budget, inputs, gas_price)
             .await?;
 
-        Ok(TransactionData::new(
+        Ok(self.apply_expiration(TransactionData::new(
             TransactionKind::programmable(pt),
             signer,
             gas,
             gas_budget,
             gas_price,
-        ))
+        )))
     }
 
     pub async fn request_add_stake(
@@ -700,13 +1051,13 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
             ));
             builder.finish()
         };
-        Ok(TransactionData::new_programmable(
+        Ok(self.apply_expiration(TransactionData::new_programmable(
             signer,
             vec![gas],
             pt,
             gas_budget,
             gas_price,
-        ))
+        )))
     }
 
     pub async fn request_withdraw_stake(
@@ -740,6 +1091,79 @@ impl<Mode: ExecutionMode> TransactionBuilder<Mode> {
             gas_budget,
             gas_price,
         )
+        .map(|data| self.apply_expiration(data))
+    }
+
+    /// Build a transaction of `kind` sent by `sender`, with the gas paid by `sponsor`. If
+    /// `sponsor_gas` is not provided, a gas coin is selected from the coins owned by the sponsor.
+    pub async fn sponsored_transaction(
+        &self,
+        kind: TransactionKind,
+        sender: SuiAddress,
+        sponsor: SuiAddress,
+        sponsor_gas: Option<ObjectID>,
+        gas_budget: u64,
+    ) -> anyhow::Result<TransactionData> {
+        let pt = match &kind {
+            TransactionKind::ProgrammableTransaction(pt) => pt,
+            _ => return Err(UserInputError::UnsupportedSponsoredTransactionKind.into()),
+        };
+        ensure!(
+            !pt.uses_gas_coin(),
+            "Transaction uses the gas coin, which would be the sponsor's coin"
+        );
+        let inputs = pt
+            .input_objects()?
+            .iter()
+            .flat_map(|obj| match obj {
+                InputObjectKind::ImmOrOwnedMoveObject((id, _, _)) => Some(*id),
+                _ => None,
+            })
+            .collect();
+        let gas_price = self.0.get_reference_gas_price().await?;
+        let gas = self
+            .select_gas(sponsor, sponsor_gas, gas_budget, inputs, gas_price)
+            .await?;
+
+        Ok(self.apply_expiration(TransactionData::new_with_gas_data(
+            kind,
+            sender,
+            GasData {
+                payment: vec![gas],
+                owner: sponsor,
+                price: gas_price,
+                budget: gas_budget,
+            },
+        )))
+    }
+
+    /// Replace the gas payment of `tx_data` with a gas coin owned by `sponsor`, keeping the
+    /// sender and the transaction kind. The gas budget of `tx_data` is kept if `gas_budget` is
+    /// not provided.
+    pub async fn sponsor_transaction(
+        &self,
+        tx_data: TransactionData,
+        sponsor: SuiAddress,
+        sponsor_gas: Option<ObjectID>,
+        gas_budget: Option<u64>,
+    ) -> anyhow::Result<TransactionData> {
+        let sender = tx_data.sender();
+        let gas_budget = gas_budget.unwrap_or_else(|| tx_data.gas_budget());
+        let expiration = *tx_data.expiration();
+        let data = self
+            .sponsored_transaction(
+                tx_data.into_kind(),
+                sender,
+                sponsor,
+                sponsor_gas,
+                gas_budget,
+            )
+            .await?;
+        // Keep the expiration of the sender's transaction, unless this builder sets one.
+        Ok(match self.2 {
+            TransactionExpiration::None => data.with_expiration(expiration),
+            TransactionExpiration::Epoch(_) => data,
+        })
     }
 
     // TODO: we should add retrial to reduce the transaction building error rate
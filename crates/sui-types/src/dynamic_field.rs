@@ -10,6 +10,8 @@ use crate::sui_serde::Readable;
 use crate::{MoveTypeTagTrait, ObjectID, SequenceNumber, SUI_FRAMEWORK_ADDRESS};
 use fastcrypto::encoding::Base58;
 use fastcrypto::hash::HashFunction;
+use move_core_types::ident_str;
+use move_core_types::identifier::IdentStr;
 use move_core_types::language_storage::{StructTag, TypeTag};
 use move_core_types::value::{MoveStruct, MoveValue};
 use schemars::JsonSchema;
@@ -29,6 +31,32 @@ pub struct Field<N, V> {
     pub value: V,
 }
 
+const DYNAMIC_FIELD_MODULE_NAME: &IdentStr = ident_str!("dynamic_field");
+const DYNAMIC_FIELD_FIELD_STRUCT_NAME: &IdentStr = ident_str!("Field");
+const DYNAMIC_OBJECT_FIELD_MODULE_NAME: &IdentStr = ident_str!("dynamic_object_field");
+const DYNAMIC_OBJECT_FIELD_WRAPPER_STRUCT_NAME: &IdentStr = ident_str!("Wrapper");
+
+/// The type of the object storing a dynamic field, `sui::dynamic_field::Field<name, value>`.
+pub fn dynamic_field_type(name: TypeTag, value: TypeTag) -> StructTag {
+    StructTag {
+        address: SUI_FRAMEWORK_ADDRESS,
+        module: DYNAMIC_FIELD_MODULE_NAME.to_owned(),
+        name: DYNAMIC_FIELD_FIELD_STRUCT_NAME.to_owned(),
+        type_params: vec![name, value],
+    }
+}
+
+/// The name of a dynamic object field is wrapped in `sui::dynamic_object_field::Wrapper<name>`,
+/// which has the same BCS encoding as the name itself.
+pub fn dynamic_object_field_name_type(name: TypeTag) -> TypeTag {
+    TypeTag::Struct(Box::new(StructTag {
+        address: SUI_FRAMEWORK_ADDRESS,
+        module: DYNAMIC_OBJECT_FIELD_MODULE_NAME.to_owned(),
+        name: DYNAMIC_OBJECT_FIELD_WRAPPER_STRUCT_NAME.to_owned(),
+        type_params: vec![name],
+    }))
+}
+
 #[serde_as]
 #[derive(Clone, Serialize, Deserialize, JsonSchema, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -180,6 +180,14 @@ pub enum UserInputError {
 
     #[error("Feature is not yet supported: {0}")]
     Unsupported(String),
+
+    #[error(
+        "Transaction expired at the end of epoch {expiration_epoch}, the current epoch is {current_epoch}"
+    )]
+    TransactionExpired {
+        expiration_epoch: EpochId,
+        current_epoch: EpochId,
+    },
 }
 
 /// Custom error type for Sui.
@@ -394,9 +402,6 @@ pub enum SuiError {
     #[error("Error when advancing epoch: {:?}", error)]
     AdvanceEpochError { error: String },
 
-    #[error("Transaction Expired")]
-    TransactionExpired,
-
     // These are errors that occur when an RPC fails and is simply the utf8 message sent in a
     // Tonic::Status
     #[error("{1} - {0}")]
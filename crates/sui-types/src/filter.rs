@@ -8,7 +8,9 @@ use serde_json::Value;
 use crate::base_types::SuiAddress;
 use crate::event::EventType;
 use crate::event::{Event, EventEnvelope};
+use crate::messages::{TransactionDataAPI, TransactionEffectsAPI, TransactionEnvelope};
 use crate::object::Owner;
+use crate::query::{ObjectChangeFilter, TransactionFilter};
 use crate::ObjectID;
 
 #[cfg(test)]
@@ -74,6 +76,70 @@ impl Filter<EventEnvelope> for EventFilter {
     }
 }
 
+impl Filter<TransactionEnvelope> for TransactionFilter {
+    fn matches(&self, item: &TransactionEnvelope) -> bool {
+        let data = &item.transaction.intent_message().value;
+        match self {
+            TransactionFilter::MoveFunction {
+                package,
+                module,
+                function,
+            } => data.move_calls().into_iter().any(|(p, m, f)| {
+                p == package
+                    && module.as_ref().map_or(true, |module| m.as_str() == module)
+                    && function
+                        .as_ref()
+                        .map_or(true, |function| f.as_str() == function)
+            }),
+            TransactionFilter::InputObject(object_id) => data
+                .input_objects()
+                .map(|inputs| inputs.iter().any(|o| &o.object_id() == object_id))
+                .unwrap_or_default(),
+            TransactionFilter::ChangedObject(object_id) => item
+                .effects
+                .all_changed_objects()
+                .into_iter()
+                .any(|(oref, _, _)| &oref.0 == object_id),
+            TransactionFilter::FromAddress(address) => &data.sender() == address,
+            TransactionFilter::ToAddress(address) => {
+                item.effects.all_changed_objects().into_iter().any(
+                    |(_, owner, _)| matches!(owner.get_owner_address(), Ok(a) if &a == address),
+                )
+            }
+        }
+    }
+}
+
+/// Matches the transactions which may change objects matching the filter. The exact match happens
+/// once the object changes of the transaction are resolved against the object store.
+impl Filter<TransactionEnvelope> for ObjectChangeFilter {
+    fn matches(&self, item: &TransactionEnvelope) -> bool {
+        let effects = &item.effects;
+        match self {
+            ObjectChangeFilter::ObjectId(object_id) => {
+                effects
+                    .all_changed_objects()
+                    .into_iter()
+                    .any(|(oref, _, _)| &oref.0 == object_id)
+                    || effects
+                        .all_deleted()
+                        .into_iter()
+                        .any(|(oref, _)| &oref.0 == object_id)
+            }
+            // Only the sender and the gas owner can use their owned objects in a transaction.
+            ObjectChangeFilter::Owner(address) => {
+                let data = &item.transaction.intent_message().value;
+                &data.sender() == address
+                    || &data.gas_owner() == address
+                    || effects.all_changed_objects().into_iter().any(
+                        |(_, owner, _)| matches!(owner, Owner::AddressOwner(a) if a == address),
+                    )
+            }
+            ObjectChangeFilter::StructType(_) => true,
+        }
+    }
+}
+
 pub trait Filter<T> {
     fn matches(&self, item: &T) -> bool;
 }
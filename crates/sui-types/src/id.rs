@@ -91,6 +91,12 @@ impl ID {
     }
 }
 
+impl MoveTypeTagTrait for UID {
+    fn get_type_tag() -> TypeTag {
+        TypeTag::Struct(Box::new(Self::type_()))
+    }
+}
+
 impl MoveTypeTagTrait for ID {
     fn get_type_tag() -> TypeTag {
         TypeTag::Struct(Box::new(Self::type_()))
@@ -7,12 +7,15 @@
     rust_2021_compatibility
 )]
 
-use base_types::SequenceNumber;
+use base_types::{SequenceNumber, SuiAddress};
 use move_core_types::{
     account_address::AccountAddress,
+    ident_str,
     language_storage::{StructTag, TypeTag},
+    u256::U256,
 };
 use object::OBJECT_START_VERSION;
+use serde::de::DeserializeOwned;
 
 use base_types::ObjectID;
 
@@ -111,8 +114,72 @@ pub trait MoveTypeTagTrait {
     fn get_type_tag() -> TypeTag;
 }
 
-impl MoveTypeTagTrait for u64 {
+/// A Rust type mirroring a Move struct, so the BCS contents of Move objects of that type can be
+/// decoded into it. Implemented with `#[derive(MoveStruct)]` from `sui-proc-macros`.
+pub trait MoveStructType: MoveTypeTagTrait + DeserializeOwned {
+    /// The struct tag, with the type parameters resolved.
+    fn struct_tag() -> StructTag;
+
+    /// Names and types of the Move fields, in the order they are BCS encoded.
+    fn fields() -> Vec<(&'static str, TypeTag)>;
+}
+
+/// Paths used by the code generated by `#[derive(MoveStruct)]`.
+#[doc(hidden)]
+pub mod __private {
+    pub use move_core_types::account_address::AccountAddress;
+    pub use move_core_types::identifier::Identifier;
+    pub use move_core_types::language_storage::{StructTag, TypeTag};
+    pub use serde::de::DeserializeOwned;
+}
+
+macro_rules! impl_primitive_move_type_tag {
+    ($($ty:ty => $tag:ident),* $(,)?) => {
+        $(impl MoveTypeTagTrait for $ty {
+            fn get_type_tag() -> TypeTag {
+                TypeTag::$tag
+            }
+        })*
+    };
+}
+
+impl_primitive_move_type_tag!(
+    bool => Bool,
+    u8 => U8,
+    u16 => U16,
+    u32 => U32,
+    u64 => U64,
+    u128 => U128,
+    U256 => U256,
+    SuiAddress => Address,
+);
+
+impl<T: MoveTypeTagTrait> MoveTypeTagTrait for Vec<T> {
+    fn get_type_tag() -> TypeTag {
+        TypeTag::Vector(Box::new(T::get_type_tag()))
+    }
+}
+
+/// `std::option::Option` is a vector of at most one element, which has the same BCS encoding as
+/// a Rust `Option`.
+impl<T: MoveTypeTagTrait> MoveTypeTagTrait for Option<T> {
+    fn get_type_tag() -> TypeTag {
+        TypeTag::Struct(Box::new(StructTag {
+            address: MOVE_STDLIB_ADDRESS,
+            module: ident_str!("option").to_owned(),
+            name: ident_str!("Option").to_owned(),
+            type_params: vec![T::get_type_tag()],
+        }))
+    }
+}
+
+impl MoveTypeTagTrait for String {
     fn get_type_tag() -> TypeTag {
-        TypeTag::U64
+        TypeTag::Struct(Box::new(StructTag {
+            address: MOVE_STDLIB_ADDRESS,
+            module: ident_str!("string").to_owned(),
+            name: ident_str!("String").to_owned(),
+            type_params: vec![],
+        }))
     }
 }
@@ -593,6 +593,25 @@ impl ProgrammableTransaction {
         Ok(())
     }
 
+    /// Whether any command takes the gas coin as an argument. The gas coin of a sponsored
+    /// transaction belongs to the sponsor, so the sponsor should not sign such transactions.
+    pub fn uses_gas_coin(&self) -> bool {
+        self.commands.iter().any(|command| {
+            let args: Vec<&Argument> = match command {
+                Command::MoveCall(m) => m.arguments.iter().collect(),
+                Command::TransferObjects(objs, recipient) => {
+                    objs.iter().chain(std::iter::once(recipient)).collect()
+                }
+                Command::SplitCoin(coin, amount) => vec![coin, amount],
+                Command::MergeCoins(coin, coins) => std::iter::once(coin).chain(coins).collect(),
+                Command::Publish(_) => vec![],
+                Command::MakeMoveVec(_, args) => args.iter().collect(),
+                Command::Upgrade(_, _, ticket) => vec![ticket],
+            };
+            args.into_iter().any(|arg| matches!(arg, Argument::GasCoin))
+        })
+    }
+
     fn shared_input_objects(&self) -> impl Iterator<Item = SharedInputObject> + '_ {
         self.inputs
             .iter()
@@ -1017,6 +1036,12 @@ impl TransactionData {
         })
     }
 
+    /// Returns this transaction with `expiration` in place of its current expiration.
+    pub fn with_expiration(mut self, expiration: TransactionExpiration) -> Self {
+        *self.expiration_mut() = expiration;
+        self
+    }
+
     pub fn new_move_call_with_dummy_gas_price(
         sender: SuiAddress,
         package: ObjectID,
@@ -1279,6 +1304,31 @@ impl TransactionData {
         Self::new_programmable(sender, vec![gas_payment], pt, gas_budget, gas_price)
     }
 
+    pub fn new_upgrade(
+        sender: SuiAddress,
+        gas_payment: ObjectRef,
+        upgrade_cap: ObjectArg,
+        policy: u8,
+        digest: Vec<u8>,
+        dep_ids: Vec<ObjectID>,
+        modules: Vec<Vec<u8>>,
+        gas_budget: u64,
+        gas_price: u64,
+    ) -> anyhow::Result<Self> {
+        let pt = {
+            let mut builder = ProgrammableTransactionBuilder::new();
+            builder.upgrade_package(upgrade_cap, policy, digest, dep_ids, modules)?;
+            builder.finish()
+        };
+        Ok(Self::new_programmable(
+            sender,
+            vec![gas_payment],
+            pt,
+            gas_budget,
+            gas_price,
+        ))
+    }
+
     pub fn new_programmable_with_dummy_gas_price(
         sender: SuiAddress,
         gas_payment: Vec<ObjectRef>,
@@ -2718,6 +2768,17 @@ impl TransactionEvents {
     }
 }
 
+/// An executed transaction together with its effects and events, streamed to the subscribers of
+/// transactions matching a `TransactionFilter`.
+#[derive(Clone, Debug)]
+pub struct TransactionEnvelope {
+    /// UTC timestamp in milliseconds since epoch (1/1/1970)
+    pub timestamp_ms: u64,
+    pub transaction: SenderSignedData,
+    pub effects: TransactionEffects,
+    pub events: TransactionEvents,
+}
+
 impl Message for TransactionEffects {
     type DigestType = TransactionEffectsDigest;
     const SCOPE: IntentScope = IntentScope::TransactionEffects;
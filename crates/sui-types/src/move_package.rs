@@ -3,10 +3,12 @@
 
 use crate::{
     base_types::{ObjectID, SequenceNumber},
+    crypto::InternalHash,
     error::{ExecutionError, ExecutionErrorKind, SuiError, SuiResult},
     id::{ID, UID},
     SUI_FRAMEWORK_ADDRESS,
 };
+use fastcrypto::hash::HashFunction;
 use move_binary_format::access::ModuleAccess;
 use move_binary_format::binary_views::BinaryIndexedView;
 use move_binary_format::file_format::CompiledModule;
@@ -74,12 +76,9 @@ pub struct MovePackage {
 }
 
 /// Rust representation of upgrade policy constants in `sui::package`.
-#[allow(dead_code)]
-const UPGRADE_POLICY_COMPATIBLE: u8 = 0;
-#[allow(dead_code)]
-const UPGRADE_POLICY_ADDITIVE: u8 = 128;
-#[allow(dead_code)]
-const UPGRADE_POLICY_DEP_ONLY: u8 = 192;
+pub const UPGRADE_POLICY_COMPATIBLE: u8 = 0;
+pub const UPGRADE_POLICY_ADDITIVE: u8 = 128;
+pub const UPGRADE_POLICY_DEP_ONLY: u8 = 192;
 
 /// Rust representation of `sui::package::UpgradeCap`.
 #[derive(Debug, Serialize, Deserialize)]
@@ -208,6 +207,27 @@ impl MovePackage {
     pub fn normalize(&self) -> SuiResult<BTreeMap<String, normalized::Module>> {
         normalize_modules(self.module_map.values())
     }
+
+    /// The digest of a package's module bytes and dependency IDs, as committed to by
+    /// `sui::package::authorize_upgrade`. The digest does not depend on the order of the modules
+    /// or of the dependencies.
+    pub fn compute_digest_for_modules_and_deps<'a>(
+        modules: impl IntoIterator<Item = &'a Vec<u8>>,
+        dep_ids: impl IntoIterator<Item = &'a ObjectID>,
+    ) -> [u8; 32] {
+        let mut bytes: Vec<&[u8]> = modules
+            .into_iter()
+            .map(|module| module.as_slice())
+            .chain(dep_ids.into_iter().map(|id| id.as_ref()))
+            .collect();
+        bytes.sort();
+
+        let mut hasher = InternalHash::default();
+        for b in bytes {
+            hasher.update(b);
+        }
+        hasher.finalize().digest
+    }
 }
 
 impl UpgradeCap {
@@ -204,6 +204,38 @@ impl ProgrammableTransactionBuilder {
         self.command(Command::Upgrade(modules, transitive_deps, upgrade_ticket))
     }
 
+    /// Authorizes an upgrade with the `upgrade_cap`, upgrades the package to `modules` and commits
+    /// the upgrade back to the cap. `digest` must match the digest of `modules` and
+    /// `transitive_deps`.
+    pub fn upgrade_package(
+        &mut self,
+        upgrade_cap: ObjectArg,
+        policy: u8,
+        digest: Vec<u8>,
+        transitive_deps: Vec<ObjectID>,
+        modules: Vec<Vec<u8>>,
+    ) -> anyhow::Result<()> {
+        let cap = self.obj(upgrade_cap)?;
+        let policy = self.pure(policy)?;
+        let digest = self.pure(digest)?;
+        let ticket = self.programmable_move_call(
+            SUI_FRAMEWORK_OBJECT_ID,
+            PACKAGE_MODULE_NAME.to_owned(),
+            ident_str!("authorize_upgrade").to_owned(),
+            vec![],
+            vec![cap, policy, digest],
+        );
+        let receipt = self.upgrade(ticket, transitive_deps, modules);
+        self.programmable_move_call(
+            SUI_FRAMEWORK_OBJECT_ID,
+            PACKAGE_MODULE_NAME.to_owned(),
+            ident_str!("commit_upgrade").to_owned(),
+            vec![],
+            vec![cap, receipt],
+        );
+        Ok(())
+    }
+
     pub fn transfer_arg(&mut self, recipient: SuiAddress, arg: Argument) {
         self.transfer_args(recipient, vec![arg])
     }
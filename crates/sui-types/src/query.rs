@@ -1,9 +1,11 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use move_core_types::language_storage::StructTag;
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
+use serde_with::{serde_as, DisplayFromStr};
 
 use crate::base_types::{SuiAddress, TransactionDigest};
 use crate::event::EventType;
@@ -28,6 +30,21 @@ pub enum TransactionFilter {
     ToAddress(SuiAddress),
 }
 
+#[serde_as]
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize)]
+pub enum ObjectChangeFilter {
+    /// Changes of the given object.
+    ObjectId(ObjectID),
+    /// Changes of the objects owned by the given address, before or after the change.
+    Owner(SuiAddress),
+    /// Changes of the objects of the given struct type, e.g. `0x2::coin::Coin<0x2::sui::SUI>`.
+    StructType(
+        #[schemars(with = "String")]
+        #[serde_as(as = "DisplayFromStr")]
+        StructTag,
+    ),
+}
+
 #[derive(Clone, Debug, JsonSchema, Serialize, Deserialize)]
 pub enum EventQuery {
     /// Return all events.
@@ -64,4 +81,10 @@ pub enum EventQuery {
         /// right endpoint of time interval, milliseconds since epoch, exclusive
         end_time: u64,
     },
+    /// Return events matching all of the given queries
+    And(Vec<EventQuery>),
+    /// Return events matching any of the given queries
+    Or(Vec<EventQuery>),
+    /// Return events not matching the given query
+    Not(Box<EventQuery>),
 }
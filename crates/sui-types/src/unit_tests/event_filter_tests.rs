@@ -7,12 +7,19 @@ use move_core_types::ident_str;
 use move_core_types::identifier::Identifier;
 use serde_json::json;
 
-use crate::base_types::{ObjectDigest, SuiAddress, TransactionDigest};
+use crate::base_types::{random_object_ref, ObjectDigest, SuiAddress, TransactionDigest};
+use crate::crypto::{get_key_pair, AccountKeyPair};
 use crate::event::EventType;
 use crate::event::{Event, EventEnvelope};
 use crate::filter::{EventFilter, Filter};
 use crate::gas_coin::GasCoin;
+use crate::messages::{
+    TransactionData, TransactionEffects, TransactionEffectsV1, TransactionEnvelope,
+    TransactionEvents,
+};
 use crate::object::{Owner, OBJECT_START_VERSION};
+use crate::query::TransactionFilter;
+use crate::utils::to_sender_signed_transaction;
 use crate::{ObjectID, MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS};
 
 #[test]
@@ -268,3 +275,55 @@ fn test_checkpoint_filter() {
     };
     assert!(EventFilter::EventType(EventType::Checkpoint).matches(&envelope))
 }
+
+#[test]
+fn test_transaction_filter() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let recipient = SuiAddress::random_for_testing_only();
+    let object_ref = random_object_ref();
+    let data =
+        TransactionData::new_transfer(recipient, object_ref, sender, random_object_ref(), 10000, 1);
+    let transaction = to_sender_signed_transaction(data, &sender_key).into_message();
+    let effects = TransactionEffects::V1(TransactionEffectsV1 {
+        mutated: vec![(object_ref, Owner::AddressOwner(recipient))],
+        ..Default::default()
+    });
+    let envelope = TransactionEnvelope {
+        timestamp_ms: 0,
+        transaction,
+        effects,
+        events: TransactionEvents::default(),
+    };
+
+    let filters = vec![
+        TransactionFilter::FromAddress(sender),
+        TransactionFilter::ToAddress(recipient),
+        TransactionFilter::InputObject(object_ref.0),
+        TransactionFilter::ChangedObject(object_ref.0),
+    ];
+    for filter in &filters {
+        assert!(
+            filter.matches(&envelope),
+            "filter = {:?} should match",
+            filter
+        )
+    }
+
+    let false_filters = vec![
+        TransactionFilter::FromAddress(recipient),
+        TransactionFilter::ToAddress(sender),
+        TransactionFilter::ChangedObject(ObjectID::random()),
+        TransactionFilter::MoveFunction {
+            package: ObjectID::from(SUI_FRAMEWORK_ADDRESS),
+            module: None,
+            function: None,
+        },
+    ];
+    for filter in &false_filters {
+        assert!(
+            !filter.matches(&envelope),
+            "filter = {:?} should not match",
+            filter
+        )
+    }
+}
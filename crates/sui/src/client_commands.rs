@@ -10,6 +10,7 @@ use std::{
     time::Instant,
 };
 
+use crate::client_ptb::{build_ptb, parse_ptb_commands, PtbResponse};
 use crate::config::{Config, PersistedConfig, SuiClientConfig, SuiEnv};
 use anyhow::{anyhow, ensure};
 use bip32::DerivationPath;
@@ -30,26 +31,33 @@ use sui_move::build::resolve_lock_file_path;
 use sui_source_validation::{BytecodeSourceVerifier, SourceMode};
 use sui_types::error::SuiError;
 
-use shared_crypto::intent::Intent;
+use shared_crypto::intent::{Intent, IntentMessage};
 use sui_framework_build::compiled_package::{
     build_from_resolution_graph, check_invalid_dependencies, check_unpublished_dependencies,
     gather_dependencies, BuildConfig,
 };
 use sui_json::SuiJsonValue;
 use sui_json_rpc_types::{
-    DynamicFieldPage, SuiObjectData, SuiObjectInfo, SuiObjectResponse, SuiRawData,
-    SuiTransactionEffectsAPI, SuiTransactionResponse, SuiTransactionResponseOptions,
+    DynamicFieldPage, SuiCommand, SuiObjectData, SuiObjectInfo, SuiObjectResponse, SuiRawData,
+    SuiTransactionData, SuiTransactionEffectsAPI, SuiTransactionResponse,
+    SuiTransactionResponseOptions, SuiTypeTag,
 };
 use sui_json_rpc_types::{SuiExecutionStatus, SuiObjectDataOptions};
+use sui_keys::key_identity::KeyIdentity;
 use sui_keys::keystore::AccountKeystore;
 use sui_sdk::SuiClient;
-use sui_types::crypto::SignatureScheme;
+use sui_types::crypto::{EncodeDecodeBase64, SignatureScheme};
 use sui_types::dynamic_field::DynamicFieldType;
-use sui_types::signature::GenericSignature;
+use sui_types::signature::{AuthenticatorTrait, GenericSignature};
 use sui_types::{
     base_types::{ObjectID, ObjectRef, SuiAddress},
+    committee::EpochId,
+    error::UserInputError,
     gas_coin::GasCoin,
-    messages::{Transaction, VerifiedTransaction},
+    messages::{
+        Transaction, TransactionData, TransactionDataAPI, TransactionKind, VerifiedTransaction,
+    },
+    move_package::{UPGRADE_POLICY_ADDITIVE, UPGRADE_POLICY_COMPATIBLE, UPGRADE_POLICY_DEP_ONLY},
     object::Owner,
     parse_sui_type_tag, SUI_FRAMEWORK_ADDRESS,
 };
@@ -67,10 +75,10 @@ pub enum SuiClientCommands {
     /// Switch active address and network(e.g., devnet, local rpc server)
     #[clap(name = "switch")]
     Switch {
-        /// An Sui address to be used as the active address for subsequent
+        /// An Sui address or its alias to be used as the active address for subsequent
         /// commands.
         #[clap(long)]
-        address: Option<SuiAddress>,
+        address: Option<KeyIdentity>,
         /// The RPC server URL (e.g., local rpc server, devnet rpc server, etc) to be
         /// used for subsequent commands.
         #[clap(long)]
@@ -130,9 +138,9 @@ pub enum SuiClientCommands {
         #[clap(long)]
         gas: Option<ObjectID>,
 
-        /// Gas budget for running module initializers
+        /// Gas budget for running module initializers, estimated by dry running the transaction if not provided
         #[clap(long)]
-        gas_budget: u64,
+        gas_budget: Option<u64>,
 
         /// Publish the package without checking whether compiling dependencies from source results
         /// in bytecode matching the dependencies found on-chain.
@@ -144,6 +152,50 @@ pub enum SuiClientCommands {
         with_unpublished_dependencies: bool,
     },
 
+    /// Upgrade a published Move package, authorized by the package's UpgradeCap
+    #[clap(name = "upgrade")]
+    Upgrade {
+        /// Path to directory containing the upgraded Move package
+        #[clap(
+            name = "package_path",
+            global = true,
+            parse(from_os_str),
+            default_value = "."
+        )]
+        package_path: PathBuf,
+
+        /// ID of the UpgradeCap of the package being upgraded
+        #[clap(long)]
+        upgrade_capability: ObjectID,
+
+        /// The upgrade policy to authorize the upgrade with, which cannot be more permissive than
+        /// the policy of the UpgradeCap
+        #[clap(long, arg_enum, default_value = "compatible")]
+        policy: UpgradePolicy,
+
+        /// Package build options
+        #[clap(flatten)]
+        build_config: MoveBuildConfig,
+
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+
+        /// Gas budget for running the upgrade, estimated by dry running the transaction if not provided
+        #[clap(long)]
+        gas_budget: Option<u64>,
+
+        /// Upgrade the package without checking whether compiling dependencies from source results
+        /// in bytecode matching the dependencies found on-chain.
+        #[clap(long)]
+        skip_dependency_verification: bool,
+
+        /// Also publish transitive dependencies that have not already been published.
+        #[clap(long)]
+        with_unpublished_dependencies: bool,
+    },
+
     /// Verify local Move packages against on-chain packages, and optionally their dependencies.
     #[clap(name = "verify-source")]
     VerifySource {
@@ -203,17 +255,17 @@ pub enum SuiClientCommands {
         /// If not provided, a gas object with at least gas_budget value will be selected
         #[clap(long)]
         gas: Option<ObjectID>,
-        /// Gas budget for this call
+        /// Gas budget for this call, estimated by dry running the transaction if not provided
         #[clap(long)]
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     },
 
     /// Transfer object
     #[clap(name = "transfer")]
     Transfer {
-        /// Recipient address
+        /// Recipient address or alias
         #[clap(long)]
-        to: SuiAddress,
+        to: KeyIdentity,
 
         /// Object to transfer, in 20 bytes Hex string
         #[clap(long)]
@@ -224,26 +276,26 @@ pub enum SuiClientCommands {
         #[clap(long)]
         gas: Option<ObjectID>,
 
-        /// Gas budget for this transfer
+        /// Gas budget for this transfer, estimated by dry running the transaction if not provided
         #[clap(long)]
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     },
     /// Transfer SUI, and pay gas with the same SUI coin object.
     /// If amount is specified, only the amount is transferred; otherwise the entire object
     /// is transferred.
     #[clap(name = "transfer-sui")]
     TransferSui {
-        /// Recipient address
+        /// Recipient address or alias
         #[clap(long)]
-        to: SuiAddress,
+        to: KeyIdentity,
 
         /// Sui coin object to transfer, ID in 20 bytes Hex string. This is also the gas object.
         #[clap(long)]
         sui_coin_object_id: ObjectID,
 
-        /// Gas budget for this transfer
+        /// Gas budget for this transfer, estimated by dry running the transaction if not provided
         #[clap(long)]
-        gas_budget: u64,
+        gas_budget: Option<u64>,
 
         /// The amount to transfer, if not specified, the entire coin object will be transferred.
         #[clap(long)]
@@ -259,7 +311,7 @@ pub enum SuiClientCommands {
 
         /// The recipient addresses, must be of same length as amounts
         #[clap(long, multiple_occurrences = false, multiple_values = true)]
-        recipients: Vec<SuiAddress>,
+        recipients: Vec<KeyIdentity>,
 
         /// The amounts to be paid, following the order of recipients.
         #[clap(long, multiple_occurrences = false, multiple_values = true)]
@@ -270,9 +322,9 @@ pub enum SuiClientCommands {
         #[clap(long)]
         gas: Option<ObjectID>,
 
-        /// Gas budget for this transaction
+        /// Gas budget for this transaction, estimated by dry running the transaction if not provided
         #[clap(long)]
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     },
 
     /// Pay SUI coins to recipients following following specified amounts, with input coins.
@@ -286,15 +338,15 @@ pub enum SuiClientCommands {
 
         /// The recipient addresses, must be of same length as amounts.
         #[clap(long, multiple_occurrences = false, multiple_values = true)]
-        recipients: Vec<SuiAddress>,
+        recipients: Vec<KeyIdentity>,
 
         /// The amounts to be paid, following the order of recipients.
         #[clap(long, multiple_occurrences = false, multiple_values = true)]
         amounts: Vec<u64>,
 
-        /// Gas budget for this transaction
+        /// Gas budget for this transaction, estimated by dry running the transaction if not provided
         #[clap(long)]
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     },
 
     /// Pay all residual SUI coins to the recipient with input coins, after deducting the gas cost.
@@ -305,13 +357,13 @@ pub enum SuiClientCommands {
         #[clap(long, multiple_occurrences = false, multiple_values = true)]
         input_coins: Vec<ObjectID>,
 
-        /// The recipient address.
+        /// The recipient address or alias.
         #[clap(long, multiple_occurrences = false)]
-        recipient: SuiAddress,
+        recipient: KeyIdentity,
 
-        /// Gas budget for this transaction
+        /// Gas budget for this transaction, estimated by dry running the transaction if not provided
         #[clap(long)]
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     },
 
     /// Obtain the Addresses managed by the client.
@@ -324,23 +376,26 @@ pub enum SuiClientCommands {
     NewAddress {
         key_scheme: SignatureScheme,
         derivation_path: Option<DerivationPath>,
+        /// A human readable alias for the new address
+        #[clap(long)]
+        alias: Option<String>,
     },
 
     /// Obtain all objects owned by the address
     #[clap(name = "objects")]
     Objects {
-        /// Address owning the objects
+        /// Address or alias owning the objects
         /// Shows all objects owned by `sui client active-address` if no argument is passed
         #[clap(name = "owner_address")]
-        address: Option<SuiAddress>,
+        address: Option<KeyIdentity>,
     },
 
     /// Obtain all gas objects owned by the address.
     #[clap(name = "gas")]
     Gas {
-        /// Address owning the objects
+        /// Address or alias owning the objects
         #[clap(name = "owner_address")]
-        address: Option<SuiAddress>,
+        address: Option<KeyIdentity>,
     },
 
     /// Query a dynamic field by its address.
@@ -373,9 +428,9 @@ pub enum SuiClientCommands {
         /// If not provided, a gas object with at least gas_budget value will be selected
         #[clap(long)]
         gas: Option<ObjectID>,
-        /// Gas budget for this call
+        /// Gas budget for this call, estimated by dry running the transaction if not provided
         #[clap(long)]
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     },
 
     /// Merge two coin objects into one coin
@@ -390,9 +445,9 @@ pub enum SuiClientCommands {
         /// If not provided, a gas object with at least gas_budget value will be selected
         #[clap(long)]
         gas: Option<ObjectID>,
-        /// Gas budget for this call
+        /// Gas budget for this call, estimated by dry running the transaction if not provided
         #[clap(long)]
-        gas_budget: u64,
+        gas_budget: Option<u64>,
     },
 
     /// Create an example NFT
@@ -415,7 +470,7 @@ pub enum SuiClientCommands {
         #[clap(long)]
         gas: Option<ObjectID>,
 
-        /// Gas budget for this transfer
+        /// Gas budget for this transfer, estimated by dry running the transaction if not provided
         #[clap(long)]
         gas_budget: Option<u64>,
     },
@@ -423,17 +478,17 @@ pub enum SuiClientCommands {
     /// Serialize a transfer that can be signed. This is useful when user prefers to take the data to sign elsewhere.
     #[clap(name = "serialize-transfer-sui")]
     SerializeTransferSui {
-        /// Recipient address
+        /// Recipient address or alias
         #[clap(long)]
-        to: SuiAddress,
+        to: KeyIdentity,
 
         /// Sui coin object to transfer, ID in 20 bytes Hex string. This is also the gas object.
         #[clap(long)]
         sui_coin_object_id: ObjectID,
 
-        /// Gas budget for this transfer
+        /// Gas budget for this transfer, estimated by dry running the transaction if not provided
         #[clap(long)]
-        gas_budget: u64,
+        gas_budget: Option<u64>,
 
         /// The amount to transfer, if not specified, the entire coin object will be transferred.
         #[clap(long)]
@@ -446,10 +501,85 @@ pub enum SuiClientCommands {
         #[clap(long)]
         tx_bytes: String,
 
-        /// A list of Base64 encoded signatures `flag || signature || pubkey`.
-        #[clap(long)]
+        /// A list of Base64 encoded signatures `flag || signature || pubkey`, or multisigs as
+        /// combined by `sui keytool multi-sig-combine-partial-sig`. A sponsored transaction needs
+        /// the signatures of both the sender and the sponsor.
+        #[clap(long, multiple_occurrences = true, multiple_values = true)]
         signatures: Vec<String>,
     },
+
+    /// Build, sign and execute a programmable transaction running several commands atomically.
+    /// Commands are JSON objects, read from a file or from the arguments. A command can use the
+    /// gas coin as argument with `"gas"`, and the results of earlier commands with `"$i"` for the
+    /// result of the i-th command or `"$i.j"` for the j-th value it returns, e.g.
+    /// '{"splitCoin":{"coin":"gas","amount":1000}}' '{"transferObjects":{"objects":["$0"],"recipient":"0x.."}}'.
+    /// Other supported commands are `moveCall` (package, module, function, typeArguments,
+    /// arguments), `mergeCoins` (coin, coins) and `makeMoveVec` (type, elements).
+    #[clap(name = "ptb")]
+    Ptb {
+        /// Path to a JSON file containing an array of commands, run before the commands passed
+        /// as arguments
+        #[clap(long)]
+        file: Option<PathBuf>,
+
+        /// Commands as JSON objects
+        commands: Vec<String>,
+
+        /// ID of the gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+
+        /// Gas budget for this transaction, estimated by dry running the transaction if not provided
+        #[clap(long)]
+        gas_budget: Option<u64>,
+
+        /// Run the transaction without executing it, printing the values returned by each
+        /// command
+        #[clap(long)]
+        dry_run: bool,
+    },
+
+    /// Pay the gas of another address's transaction. The gas payment of the sender's unsigned
+    /// transaction is replaced with a gas coin of the sponsor, the sender then signs the returned
+    /// transaction and passes it back to the sponsor for `sponsor-sign`.
+    #[clap(name = "sponsor-tx")]
+    SponsorTx {
+        /// BCS serialized transaction data bytes without its type tag, as base-64 encoded string.
+        #[clap(long)]
+        tx_bytes: String,
+
+        /// Sponsor address or alias, defaults to the active address.
+        #[clap(long)]
+        sponsor: Option<KeyIdentity>,
+
+        /// ID of the sponsor's gas object for gas payment, in 20 bytes Hex string
+        /// If not provided, a gas object with at least gas_budget value will be selected
+        #[clap(long)]
+        gas: Option<ObjectID>,
+
+        /// Gas budget for the transaction, defaults to the budget of the sender's transaction.
+        #[clap(long)]
+        gas_budget: Option<u64>,
+    },
+
+    /// Review a sponsored transaction signed by its sender, and co-sign it with the key of the gas
+    /// owner. The sponsor signature is printed, or the transaction is executed with both signatures
+    /// if `--execute` is set.
+    #[clap(name = "sponsor-sign")]
+    SponsorSign {
+        /// BCS serialized transaction data bytes without its type tag, as base-64 encoded string.
+        #[clap(long)]
+        tx_bytes: String,
+
+        /// The sender's Base64 encoded signature `flag || signature || pubkey`.
+        #[clap(long)]
+        sender_signature: String,
+
+        /// Execute the transaction after signing it.
+        #[clap(long)]
+        execute: bool,
+    },
 }
 
 impl SuiClientCommands {
@@ -469,67 +599,84 @@ impl SuiClientCommands {
                 let sender = context.try_get_object_owner(&gas).await?;
                 let sender = sender.unwrap_or(context.active_address()?);
 
-                let config = resolve_lock_file_path(build_config, Some(package_path.clone()))?;
-                let run_bytecode_verifier = true;
-                let print_diags_to_stderr = true;
-
-                let config = BuildConfig {
-                    config,
-                    run_bytecode_verifier,
-                    print_diags_to_stderr,
-                };
-
-                let resolution_graph = config.resolution_graph(&package_path)?;
-                let dependencies = gather_dependencies(&resolution_graph);
-
-                check_invalid_dependencies(dependencies.invalid)?;
-
-                if !with_unpublished_dependencies {
-                    check_unpublished_dependencies(dependencies.unpublished)?;
-                };
-
-                let compiled_package = build_from_resolution_graph(
+                let client = context.get_client().await?;
+                let (_, compiled_modules) = compile_package(
+                    &client,
+                    build_config,
                     package_path,
-                    resolution_graph,
-                    run_bytecode_verifier,
-                    print_diags_to_stderr,
-                )?;
+                    with_unpublished_dependencies,
+                    skip_dependency_verification,
+                )
+                .await?;
 
-                if !compiled_package.is_framework() {
-                    if let Some(already_published) = compiled_package.published_root_module() {
-                        return Err(SuiError::ModulePublishFailure {
-                            error: format!(
-                                "Modules must all have 0x0 as their addresses. \
-                                 Violated by module {:?}",
-                                already_published.self_id(),
-                            ),
-                        }
-                        .into());
-                    }
+                let builder = client
+                    .transaction_builder()
+                    .with_expiration(context.expiration_epoch);
+                let data = builder
+                    .build_with_gas_budget(gas_budget, |gas_budget| {
+                        builder.publish(sender, compiled_modules.clone(), gas, gas_budget)
+                    })
+                    .await?;
+                if context.serialize_unsigned {
+                    return serialize_unsigned_transaction(&data);
                 }
+                let signature =
+                    context
+                        .config
+                        .keystore
+                        .sign_secure(&sender, &data, Intent::default())?;
+                let response = context
+                    .execute_transaction(
+                        Transaction::from_data(data, Intent::default(), vec![signature])
+                            .verify()?,
+                    )
+                    .await?;
 
-                let client = context.get_client().await?;
-                let compiled_modules =
-                    compiled_package.get_package_bytes(with_unpublished_dependencies);
+                SuiClientCommandResult::Publish(response)
+            }
 
-                if !skip_dependency_verification {
-                    BytecodeSourceVerifier::new(client.read_api(), false)
-                        .verify_package_deps(&compiled_package.package)
-                        .await?;
-                    eprintln!(
-                        "{}",
-                        "Successfully verified dependencies on-chain against source."
-                            .bold()
-                            .green(),
-                    );
-                } else {
-                    eprintln!("{}", "Skipping dependency verification".bold().yellow());
-                }
+            SuiClientCommands::Upgrade {
+                package_path,
+                upgrade_capability,
+                policy,
+                build_config,
+                gas,
+                gas_budget,
+                skip_dependency_verification,
+                with_unpublished_dependencies,
+            } => {
+                let sender = context.try_get_object_owner(&gas).await?;
+                let sender = sender.unwrap_or(context.active_address()?);
 
-                let data = client
+                let client = context.get_client().await?;
+                let (dep_ids, compiled_modules) = compile_package(
+                    &client,
+                    build_config,
+                    package_path,
+                    with_unpublished_dependencies,
+                    skip_dependency_verification,
+                )
+                .await?;
+
+                let builder = client
                     .transaction_builder()
-                    .publish(sender, compiled_modules, gas, gas_budget)
+                    .with_expiration(context.expiration_epoch);
+                let data = builder
+                    .build_with_gas_budget(gas_budget, |gas_budget| {
+                        builder.upgrade(
+                            sender,
+                            compiled_modules.clone(),
+                            dep_ids.clone(),
+                            upgrade_capability,
+                            policy.into(),
+                            gas,
+                            gas_budget,
+                        )
+                    })
                     .await?;
+                if context.serialize_unsigned {
+                    return serialize_unsigned_transaction(&data);
+                }
                 let signature =
                     context
                         .config
@@ -542,7 +689,7 @@ impl SuiClientCommands {
                     )
                     .await?;
 
-                SuiClientCommandResult::Publish(response)
+                SuiClientCommandResult::Upgrade(response)
             }
 
             SuiClientCommands::Object { id, bcs } => {
@@ -581,10 +728,14 @@ impl SuiClientCommands {
                 gas_budget,
                 args,
             } => {
-                let response = call_move(
+                let data = move_call_transaction(
                     package, &module, &function, type_args, gas, gas_budget, args, context,
                 )
                 .await?;
+                if context.serialize_unsigned {
+                    return serialize_unsigned_transaction(&data);
+                }
+                let response = execute_move_call(data, context).await?;
                 SuiClientCommandResult::Call(response)
             }
 
@@ -594,14 +745,22 @@ impl SuiClientCommands {
                 gas,
                 gas_budget,
             } => {
+                let to = context.get_identity_address(Some(to))?;
                 let from = context.get_object_owner(&object_id).await?;
                 let time_start = Instant::now();
 
                 let client = context.get_client().await?;
-                let data = client
+                let builder = client
                     .transaction_builder()
-                    .transfer_object(from, object_id, gas, gas_budget, to)
+                    .with_expiration(context.expiration_epoch);
+                let data = builder
+                    .build_with_gas_budget(gas_budget, |gas_budget| {
+                        builder.transfer_object(from, object_id, gas, gas_budget, to)
+                    })
                     .await?;
+                if context.serialize_unsigned {
+                    return serialize_unsigned_transaction(&data);
+                }
                 let signature =
                     context
                         .config
@@ -632,13 +791,21 @@ impl SuiClientCommands {
                 gas_budget,
                 amount,
             } => {
+                let to = context.get_identity_address(Some(to))?;
                 let from = context.get_object_owner(&object_id).await?;
 
                 let client = context.get_client().await?;
-                let data = client
+                let builder = client
                     .transaction_builder()
-                    .transfer_sui(from, object_id, gas_budget, to, amount)
+                    .with_expiration(context.expiration_epoch);
+                let data = builder
+                    .build_with_gas_budget(gas_budget, |gas_budget| {
+                        builder.transfer_sui(from, object_id, gas_budget, to, amount)
+                    })
                     .await?;
+                if context.serialize_unsigned {
+                    return serialize_unsigned_transaction(&data);
+                }
                 let signature =
                     context
                         .config
@@ -666,6 +833,7 @@ impl SuiClientCommands {
                 gas,
                 gas_budget,
             } => {
+                let recipients = context.get_identity_addresses(recipients)?;
                 ensure!(
                     !input_coins.is_empty(),
                     "Pay transaction requires a non-empty list of input coins"
@@ -684,10 +852,24 @@ impl SuiClientCommands {
                 );
                 let from = context.get_object_owner(&input_coins[0]).await?;
                 let client = context.get_client().await?;
-                let data = client
+                let builder = client
                     .transaction_builder()
-                    .pay(from, input_coins, recipients, amounts, gas, gas_budget)
+                    .with_expiration(context.expiration_epoch);
+                let data = builder
+                    .build_with_gas_budget(gas_budget, |gas_budget| {
+                        builder.pay(
+                            from,
+                            input_coins.clone(),
+                            recipients.clone(),
+                            amounts.clone(),
+                            gas,
+                            gas_budget,
+                        )
+                    })
                     .await?;
+                if context.serialize_unsigned {
+                    return serialize_unsigned_transaction(&data);
+                }
                 let signature =
                     context
                         .config
@@ -717,6 +899,7 @@ impl SuiClientCommands {
                 amounts,
                 gas_budget,
             } => {
+                let recipients = context.get_identity_addresses(recipients)?;
                 ensure!(
                     !input_coins.is_empty(),
                     "PaySui transaction requires a non-empty list of input coins"
@@ -735,10 +918,23 @@ impl SuiClientCommands {
                 );
                 let signer = context.get_object_owner(&input_coins[0]).await?;
                 let client = context.get_client().await?;
-                let data = client
+                let builder = client
                     .transaction_builder()
-                    .pay_sui(signer, input_coins, recipients, amounts, gas_budget)
+                    .with_expiration(context.expiration_epoch);
+                let data = builder
+                    .build_with_gas_budget(gas_budget, |gas_budget| {
+                        builder.pay_sui(
+                            signer,
+                            input_coins.clone(),
+                            recipients.clone(),
+                            amounts.clone(),
+                            gas_budget,
+                        )
+                    })
                     .await?;
+                if context.serialize_unsigned {
+                    return serialize_unsigned_transaction(&data);
+                }
                 let signature =
                     context
                         .config
@@ -767,17 +963,25 @@ impl SuiClientCommands {
                 recipient,
                 gas_budget,
             } => {
+                let recipient = context.get_identity_address(Some(recipient))?;
                 ensure!(
                     !input_coins.is_empty(),
                     "PayAllSui transaction requires a non-empty list of input coins"
                 );
                 let signer = context.get_object_owner(&input_coins[0]).await?;
                 let client = context.get_client().await?;
-                let data = client
+                let builder = client
                     .transaction_builder()
-                    .pay_all_sui(signer, input_coins, recipient, gas_budget)
+                    .with_expiration(context.expiration_epoch);
+                let data = builder
+                    .build_with_gas_budget(gas_budget, |gas_budget| {
+                        builder.pay_all_sui(signer, input_coins.clone(), recipient, gas_budget)
+                    })
                     .await?;
 
+                if context.serialize_unsigned {
+                    return serialize_unsigned_transaction(&data);
+                }
                 let signature =
                     context
                         .config
@@ -802,12 +1006,21 @@ impl SuiClientCommands {
             }
 
             SuiClientCommands::Addresses => SuiClientCommandResult::Addresses(
-                context.config.keystore.addresses(),
+                context
+                    .config
+                    .keystore
+                    .addresses()
+                    .into_iter()
+                    .map(|address| {
+                        let alias = context.config.keystore.get_alias(&address);
+                        (address, alias.map(str::to_string))
+                    })
+                    .collect(),
                 context.active_address().ok(),
             ),
 
             SuiClientCommands::Objects { address } => {
-                let address = address.unwrap_or(context.active_address()?);
+                let address = context.get_identity_address(address)?;
                 let client = context.get_client().await?;
                 let address_object = client
                     .read_api()
@@ -819,15 +1032,19 @@ impl SuiClientCommands {
             SuiClientCommands::NewAddress {
                 key_scheme,
                 derivation_path,
+                alias,
             } => {
                 let (address, phrase, scheme) = context
                     .config
                     .keystore
                     .generate_and_add_new_key(key_scheme, derivation_path)?;
+                if let Some(alias) = alias {
+                    context.config.keystore.set_alias(&address, alias)?;
+                }
                 SuiClientCommandResult::NewAddress((address, phrase, scheme))
             }
             SuiClientCommands::Gas { address } => {
-                let address = address.unwrap_or(context.active_address()?);
+                let address = context.get_identity_address(address)?;
                 let coins = context
                     .gas_objects(address)
                     .await?
@@ -848,24 +1065,41 @@ impl SuiClientCommands {
                 let client = context.get_client().await?;
                 let data = match (amounts, count) {
                     (Some(amounts), None) => {
-                        client
+                        let builder = client
                             .transaction_builder()
-                            .split_coin(signer, coin_id, amounts, gas, gas_budget)
+                            .with_expiration(context.expiration_epoch);
+                        builder
+                            .build_with_gas_budget(gas_budget, |gas_budget| {
+                                builder.split_coin(
+                                    signer,
+                                    coin_id,
+                                    amounts.clone(),
+                                    gas,
+                                    gas_budget,
+                                )
+                            })
                             .await?
                     }
                     (None, Some(count)) => {
                         if count == 0 {
                             return Err(anyhow!("Coin split count must be greater than 0"));
                         }
-                        client
+                        let builder = client
                             .transaction_builder()
-                            .split_coin_equal(signer, coin_id, count, gas, gas_budget)
+                            .with_expiration(context.expiration_epoch);
+                        builder
+                            .build_with_gas_budget(gas_budget, |gas_budget| {
+                                builder.split_coin_equal(signer, coin_id, count, gas, gas_budget)
+                            })
                             .await?
                     }
                     _ => {
                         return Err(anyhow!("Exactly one of `count` and `amounts` must be present for split-coin command."));
                     }
                 };
+                if context.serialize_unsigned {
+                    return serialize_unsigned_transaction(&data);
+                }
                 let signature =
                     context
                         .config
@@ -887,10 +1121,17 @@ impl SuiClientCommands {
             } => {
                 let client = context.get_client().await?;
                 let signer = context.get_object_owner(&primary_coin).await?;
-                let data = client
+                let builder = client
                     .transaction_builder()
-                    .merge_coins(signer, primary_coin, coin_to_merge, gas, gas_budget)
+                    .with_expiration(context.expiration_epoch);
+                let data = builder
+                    .build_with_gas_budget(gas_budget, |gas_budget| {
+                        builder.merge_coins(signer, primary_coin, coin_to_merge, gas, gas_budget)
+                    })
                     .await?;
+                if context.serialize_unsigned {
+                    return serialize_unsigned_transaction(&data);
+                }
                 let signature =
                     context
                         .config
@@ -906,6 +1147,9 @@ impl SuiClientCommands {
                 SuiClientCommandResult::MergeCoin(response)
             }
             SuiClientCommands::Switch { address, env } => {
+                let address = address
+                    .map(|address| context.config.keystore.get_address(&address))
+                    .transpose()?;
                 match (address, &env) {
                     (None, Some(env)) => {
                         Self::switch_env(&mut context.config, env)?;
@@ -940,17 +1184,21 @@ impl SuiClientCommands {
                 for a in args_json.as_array().unwrap() {
                     args.push(SuiJsonValue::new(a.clone()).unwrap());
                 }
-                let response = call_move(
+                let data = move_call_transaction(
                     ObjectID::from(SUI_FRAMEWORK_ADDRESS),
                     "devnet_nft",
                     "mint",
                     vec![],
                     gas,
-                    gas_budget.unwrap_or(100_000),
+                    gas_budget,
                     args,
                     context,
                 )
                 .await?;
+                if context.serialize_unsigned {
+                    return serialize_unsigned_transaction(&data);
+                }
+                let response = execute_move_call(data, context).await?;
                 let nft_id = response
                     .effects
                     .ok_or_else(|| anyhow!("Failed to fetch transaction effects"))?
@@ -973,11 +1221,16 @@ impl SuiClientCommands {
                 gas_budget,
                 amount,
             } => {
+                let to = context.get_identity_address(Some(to))?;
                 let from = context.get_object_owner(&object_id).await?;
                 let client = context.get_client().await?;
-                let data = client
+                let builder = client
                     .transaction_builder()
-                    .transfer_sui(from, object_id, gas_budget, to, amount)
+                    .with_expiration(context.expiration_epoch);
+                let data = builder
+                    .build_with_gas_budget(gas_budget, |gas_budget| {
+                        builder.transfer_sui(from, object_id, gas_budget, to, amount)
+                    })
                     .await?;
                 SuiClientCommandResult::SerializeTransferSui(Base64::encode(
                     bcs::to_bytes(&data).unwrap(),
@@ -1013,6 +1266,166 @@ impl SuiClientCommands {
                 let response = context.execute_transaction(verified).await?;
                 SuiClientCommandResult::ExecuteSignedTx(response)
             }
+            SuiClientCommands::Ptb {
+                file,
+                commands,
+                gas,
+                gas_budget,
+                dry_run,
+            } => {
+                ensure!(
+                    !(dry_run && context.serialize_unsigned),
+                    "--dry-run cannot be combined with --serialize-unsigned"
+                );
+                let commands = parse_ptb_commands(file.as_deref(), &commands)?;
+                let sender = context.try_get_object_owner(&gas).await?;
+                let sender = sender.unwrap_or(context.active_address()?);
+                let client = context.get_client().await?;
+                let (pt, command_indices) = build_ptb(&client, commands).await?;
+                let sui_commands = pt.commands.iter().cloned().map(SuiCommand::from).collect();
+                let builder = client
+                    .transaction_builder()
+                    .with_expiration(context.expiration_epoch);
+                let data = builder
+                    .build_with_gas_budget(gas_budget, |gas_budget| {
+                        builder.programmable_transaction(sender, pt.clone(), gas, gas_budget)
+                    })
+                    .await?;
+                if context.serialize_unsigned {
+                    return serialize_unsigned_transaction(&data);
+                }
+
+                let (dry_run, dev_inspect, response) = if dry_run {
+                    let dev_inspect = client
+                        .read_api()
+                        .dev_inspect_transaction(
+                            sender,
+                            data.kind().clone(),
+                            Some(data.gas_price()),
+                        )
+                        .await?;
+                    let dry_run = client.read_api().dry_run_transaction(data).await?;
+                    (Some(dry_run), Some(dev_inspect), None)
+                } else {
+                    let signature =
+                        context
+                            .config
+                            .keystore
+                            .sign_secure(&sender, &data, Intent::default())?;
+                    let transaction =
+                        Transaction::from_data(data, Intent::default(), vec![signature])
+                            .verify()?;
+                    (
+                        None,
+                        None,
+                        Some(context.execute_transaction(transaction).await?),
+                    )
+                };
+                SuiClientCommandResult::Ptb(PtbResponse {
+                    commands: sui_commands,
+                    command_indices,
+                    dry_run,
+                    dev_inspect,
+                    response,
+                })
+            }
+
+            SuiClientCommands::SponsorTx {
+                tx_bytes,
+                sponsor,
+                gas,
+                gas_budget,
+            } => {
+                let data: TransactionData = bcs::from_bytes(
+                    &Base64::try_from(tx_bytes)
+                        .map_err(|e| anyhow!(e))?
+                        .to_vec()
+                        .map_err(|e| anyhow!(e))?,
+                )?;
+                let sponsor = context.get_identity_address(sponsor)?;
+                ensure!(
+                    data.sender() != sponsor,
+                    "Sponsor [{sponsor}] is the sender of the transaction"
+                );
+                let client = context.get_client().await?;
+                let data = client
+                    .transaction_builder()
+                    .with_expiration(context.expiration_epoch)
+                    .sponsor_transaction(data, sponsor, gas, gas_budget)
+                    .await?;
+                if context.serialize_unsigned {
+                    return serialize_unsigned_transaction(&data);
+                }
+                SuiClientCommandResult::SponsorTx(Base64::encode(bcs::to_bytes(&data)?))
+            }
+
+            SuiClientCommands::SponsorSign {
+                tx_bytes,
+                sender_signature,
+                execute,
+            } => {
+                let data: TransactionData = bcs::from_bytes(
+                    &Base64::try_from(tx_bytes)
+                        .map_err(|e| anyhow!(e))?
+                        .to_vec()
+                        .map_err(|e| anyhow!(e))?,
+                )?;
+                let sender_signature = GenericSignature::from_bytes(
+                    &Base64::try_from(sender_signature)
+                        .map_err(|e| anyhow!(e))?
+                        .to_vec()
+                        .map_err(|e| anyhow!(e))?,
+                )
+                .map_err(|e| anyhow!(e))?;
+
+                let sponsor = data.gas_owner();
+                ensure!(
+                    sponsor != data.sender(),
+                    "Transaction is not sponsored, the sender owns the gas"
+                );
+                match data.kind() {
+                    TransactionKind::ProgrammableTransaction(pt) => ensure!(
+                        !pt.uses_gas_coin(),
+                        "Transaction uses the gas coin, signing it hands the sponsor's coin to the sender"
+                    ),
+                    _ => return Err(UserInputError::UnsupportedSponsoredTransactionKind.into()),
+                }
+                let intent_msg = IntentMessage::new(Intent::default(), data.clone());
+                sender_signature
+                    .verify_secure_generic(&intent_msg, data.sender())
+                    .map_err(|e| anyhow!("Invalid sender signature: {e}"))?;
+
+                // Show what is about to be paid for before the sponsor's key signs it.
+                let transaction = SuiTransactionData::try_from(data.clone())?;
+                eprintln!("{}", "----- Transaction to sponsor ----".bold());
+                eprintln!("{}", transaction);
+                if context.serialize_unsigned {
+                    return serialize_unsigned_transaction(&data);
+                }
+
+                let sponsor_signature =
+                    context
+                        .config
+                        .keystore
+                        .sign_secure(&sponsor, &data, Intent::default())?;
+                let encoded_signature = sponsor_signature.encode_base64();
+                let response = if execute {
+                    let verified = Transaction::from_generic_sig_data(
+                        data,
+                        Intent::default(),
+                        vec![sender_signature, sponsor_signature.into()],
+                    )
+                    .verify()?;
+                    Some(context.execute_transaction(verified).await?)
+                } else {
+                    None
+                };
+                SuiClientCommandResult::SponsorSign(SponsorSignResponse {
+                    transaction,
+                    sponsor_signature: encoded_signature,
+                    response,
+                })
+            }
             SuiClientCommands::NewEnv { alias, rpc, ws } => {
                 if context.config.envs.iter().any(|env| env.alias == alias) {
                     return Err(anyhow!(
@@ -1089,6 +1502,11 @@ impl SuiClientCommands {
 
 pub struct WalletContext {
     pub config: PersistedConfig<SuiClientConfig>,
+    /// Transactions built by the client commands expire after the end of this epoch.
+    pub expiration_epoch: Option<EpochId>,
+    /// The client commands print their transaction data for offline signing, instead of signing
+    /// and executing it.
+    pub serialize_unsigned: bool,
     request_timeout: Option<std::time::Duration>,
     client: Arc<RwLock<Option<SuiClient>>>,
 }
@@ -1108,6 +1526,8 @@ impl WalletContext {
         let config = config.persisted(config_path);
         let context = Self {
             config,
+            expiration_epoch: None,
+            serialize_unsigned: false,
             request_timeout,
             client: Default::default(),
         };
@@ -1135,6 +1555,27 @@ impl WalletContext {
         })
     }
 
+    /// Resolves an address or a keystore alias, defaulting to the active address.
+    pub fn get_identity_address(
+        &mut self,
+        identity: Option<KeyIdentity>,
+    ) -> Result<SuiAddress, anyhow::Error> {
+        match identity {
+            Some(identity) => self.config.keystore.get_address(&identity),
+            None => self.active_address(),
+        }
+    }
+
+    pub fn get_identity_addresses(
+        &self,
+        identities: Vec<KeyIdentity>,
+    ) -> Result<Vec<SuiAddress>, anyhow::Error> {
+        identities
+            .iter()
+            .map(|identity| self.config.keystore.get_address(identity))
+            .collect()
+    }
+
     pub fn active_address(&mut self) -> Result<SuiAddress, anyhow::Error> {
         if self.config.keystore.addresses().is_empty() {
             return Err(anyhow!(
@@ -1266,7 +1707,8 @@ impl Display for SuiClientCommandResult {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let mut writer = String::new();
         match self {
-            SuiClientCommandResult::Publish(response) => {
+            SuiClientCommandResult::Publish(response)
+            | SuiClientCommandResult::Upgrade(response) => {
                 write!(writer, "{}", write_transaction_response(response)?)?;
             }
             SuiClientCommandResult::Object(object_read) => {
@@ -1315,11 +1757,15 @@ impl Display for SuiClientCommandResult {
             }
             SuiClientCommandResult::Addresses(addresses, active_address) => {
                 writeln!(writer, "Showing {} results.", addresses.len())?;
-                for address in addresses {
+                for (address, alias) in addresses {
+                    let alias = alias
+                        .as_ref()
+                        .map(|alias| format!(" ({alias})"))
+                        .unwrap_or_default();
                     if *active_address == Some(*address) {
-                        writeln!(writer, "{} <=", address)?;
+                        writeln!(writer, "{}{} <=", address, alias)?;
                     } else {
-                        writeln!(writer, "{}", address)?;
+                        writeln!(writer, "{}{}", address, alias)?;
                     }
                 }
             }
@@ -1428,6 +1874,18 @@ impl Display for SuiClientCommandResult {
             SuiClientCommandResult::SerializeTransferSui(data) => {
                 writeln!(writer, "Raw tx_bytes to execute: {}", data)?;
             }
+            SuiClientCommandResult::SerializeUnsigned(data) => {
+                writeln!(writer, "Raw tx_bytes to sign: {}", data)?;
+            }
+            SuiClientCommandResult::Ptb(response) => {
+                write!(writer, "{}", response)?;
+            }
+            SuiClientCommandResult::SponsorTx(data) => {
+                writeln!(writer, "Raw tx_bytes for the sender to sign: {}", data)?;
+            }
+            SuiClientCommandResult::SponsorSign(response) => {
+                write!(writer, "{}", response)?;
+            }
             SuiClientCommandResult::ActiveEnv(env) => {
                 write!(writer, "{}", env.as_deref().unwrap_or("None"))?;
             }
@@ -1457,12 +1915,30 @@ pub async fn call_move(
     function: &str,
     type_args: Vec<TypeTag>,
     gas: Option<ObjectID>,
-    gas_budget: u64,
+    gas_budget: Option<u64>,
     args: Vec<SuiJsonValue>,
     context: &mut WalletContext,
 ) -> Result<SuiTransactionResponse, anyhow::Error> {
+    let data = move_call_transaction(
+        package, module, function, type_args, gas, gas_budget, args, context,
+    )
+    .await?;
+    execute_move_call(data, context).await
+}
+
+/// Build the transaction calling `function`, sent by the owner of `gas` or by the active address.
+pub async fn move_call_transaction(
+    package: ObjectID,
+    module: &str,
+    function: &str,
+    type_args: Vec<TypeTag>,
+    gas: Option<ObjectID>,
+    gas_budget: Option<u64>,
+    args: Vec<SuiJsonValue>,
+    context: &mut WalletContext,
+) -> Result<TransactionData, anyhow::Error> {
     // Convert all numeric input to String, this will allow number input from the CLI without failing SuiJSON's checks.
-    let args = args
+    let args: Vec<_> = args
         .into_iter()
         .map(|value| SuiJsonValue::new(convert_number_to_string(value.to_json_value())))
         .collect::<Result<_, _>>()?;
@@ -1470,27 +1946,40 @@ pub async fn call_move(
     let gas_owner = context.try_get_object_owner(&gas).await?;
     let sender = gas_owner.unwrap_or(context.active_address()?);
 
+    let type_args = type_args
+        .into_iter()
+        .map(|arg| arg.try_into())
+        .collect::<Result<Vec<SuiTypeTag>, _>>()?;
+
     let client = context.get_client().await?;
-    let data = client
+    let builder = client
         .transaction_builder()
-        .move_call(
-            sender,
-            package,
-            module,
-            function,
-            type_args
-                .into_iter()
-                .map(|arg| arg.try_into())
-                .collect::<Result<Vec<_>, _>>()?,
-            args,
-            gas,
-            gas_budget,
-        )
-        .await?;
-    let signature = context
-        .config
-        .keystore
-        .sign_secure(&sender, &data, Intent::default())?;
+        .with_expiration(context.expiration_epoch);
+    builder
+        .build_with_gas_budget(gas_budget, |gas_budget| {
+            builder.move_call(
+                sender,
+                package,
+                module,
+                function,
+                type_args.clone(),
+                args.clone(),
+                gas,
+                gas_budget,
+            )
+        })
+        .await
+}
+
+async fn execute_move_call(
+    data: TransactionData,
+    context: &mut WalletContext,
+) -> Result<SuiTransactionResponse, anyhow::Error> {
+    let signature =
+        context
+            .config
+            .keystore
+            .sign_secure(&data.sender(), &data, Intent::default())?;
     let transaction = Transaction::from_data(data, Intent::default(), vec![signature]).verify()?;
 
     let response = context.execute_transaction(transaction).await?;
@@ -1504,7 +1993,108 @@ pub async fn call_move(
     Ok(response)
 }
 
-fn convert_number_to_string(value: Value) -> Value {
+/// The result of a command run with `--serialize-unsigned`: its transaction data, to be signed
+/// offline with `sui keytool sign` and executed with `sui client execute-signed-tx`.
+fn serialize_unsigned_transaction(
+    data: &TransactionData,
+) -> Result<SuiClientCommandResult, anyhow::Error> {
+    Ok(SuiClientCommandResult::SerializeUnsigned(Base64::encode(
+        bcs::to_bytes(data)?,
+    )))
+}
+
+/// The upgrade policies of `sui::package`, from the most to the least permissive.
+#[derive(ArgEnum, Clone, Copy, Debug)]
+pub enum UpgradePolicy {
+    /// Any compatible change to the package
+    Compatible,
+    /// Only adding new code to the package
+    Additive,
+    /// Only changing the package's dependencies
+    DepOnly,
+}
+
+impl From<UpgradePolicy> for u8 {
+    fn from(policy: UpgradePolicy) -> Self {
+        match policy {
+            UpgradePolicy::Compatible => UPGRADE_POLICY_COMPATIBLE,
+            UpgradePolicy::Additive => UPGRADE_POLICY_ADDITIVE,
+            UpgradePolicy::DepOnly => UPGRADE_POLICY_DEP_ONLY,
+        }
+    }
+}
+
+/// Builds the package at `package_path` for publishing, returning the IDs of its published
+/// dependencies, as resolved from `Move.lock`, and its serialized modules.
+async fn compile_package(
+    client: &SuiClient,
+    build_config: MoveBuildConfig,
+    package_path: PathBuf,
+    with_unpublished_dependencies: bool,
+    skip_dependency_verification: bool,
+) -> Result<(Vec<ObjectID>, Vec<Vec<u8>>), anyhow::Error> {
+    let config = resolve_lock_file_path(build_config, Some(package_path.clone()))?;
+    let run_bytecode_verifier = true;
+    let print_diags_to_stderr = true;
+
+    let config = BuildConfig {
+        config,
+        run_bytecode_verifier,
+        print_diags_to_stderr,
+    };
+
+    let resolution_graph = config.resolution_graph(&package_path)?;
+    let dependencies = gather_dependencies(&resolution_graph);
+
+    check_invalid_dependencies(dependencies.invalid)?;
+
+    if !with_unpublished_dependencies {
+        check_unpublished_dependencies(dependencies.unpublished)?;
+    };
+
+    let compiled_package = build_from_resolution_graph(
+        package_path,
+        resolution_graph,
+        run_bytecode_verifier,
+        print_diags_to_stderr,
+    )?;
+
+    if !compiled_package.is_framework() {
+        if let Some(already_published) = compiled_package.published_root_module() {
+            return Err(SuiError::ModulePublishFailure {
+                error: format!(
+                    "Modules must all have 0x0 as their addresses. \
+                     Violated by module {:?}",
+                    already_published.self_id(),
+                ),
+            }
+            .into());
+        }
+    }
+
+    let compiled_modules = compiled_package.get_package_bytes(with_unpublished_dependencies);
+
+    if !skip_dependency_verification {
+        BytecodeSourceVerifier::new(client.read_api(), false)
+            .verify_package_deps(&compiled_package.package)
+            .await?;
+        eprintln!(
+            "{}",
+            "Successfully verified dependencies on-chain against source."
+                .bold()
+                .green(),
+        );
+    } else {
+        eprintln!("{}", "Skipping dependency verification".bold().yellow());
+    }
+
+    Ok((
+        dependencies.published.into_values().collect(),
+        compiled_modules,
+    ))
+}
+
+pub(crate) fn convert_number_to_string(value: Value) -> Value {
     match value {
         Value::Number(n) => Value::String(n.to_string()),
         Value::Array(a) => Value::Array(a.into_iter().map(convert_number_to_string).collect()),
@@ -1524,7 +2114,9 @@ fn unwrap_or<'a>(val: &'a Option<String>, default: &'a str) -> &'a str {
     }
 }
 
-fn write_transaction_response(response: &SuiTransactionResponse) -> Result<String, fmt::Error> {
+pub(crate) fn write_transaction_response(
+    response: &SuiTransactionResponse,
+) -> Result<String, fmt::Error> {
     let mut writer = String::new();
     writeln!(writer, "{}", "----- Transaction Data ----".bold())?;
     if let Some(t) = &response.transaction {
@@ -1582,6 +2174,7 @@ impl SuiClientCommandResult {
 #[serde(untagged)]
 pub enum SuiClientCommandResult {
     Publish(SuiTransactionResponse),
+    Upgrade(SuiTransactionResponse),
     VerifySource,
     Object(SuiObjectResponse),
     RawObject(SuiObjectResponse),
@@ -1595,7 +2188,7 @@ pub enum SuiClientCommandResult {
     Pay(SuiTransactionResponse),
     PaySui(SuiTransactionResponse),
     PayAllSui(SuiTransactionResponse),
-    Addresses(Vec<SuiAddress>, Option<SuiAddress>),
+    Addresses(Vec<(SuiAddress, Option<String>)>, Option<SuiAddress>),
     Objects(Vec<SuiObjectInfo>),
     DynamicFieldQuery(DynamicFieldPage),
     SyncClientState,
@@ -1609,10 +2202,41 @@ pub enum SuiClientCommandResult {
     Envs(Vec<SuiEnv>, Option<String>),
     CreateExampleNFT(SuiObjectResponse),
     SerializeTransferSui(String),
+    SerializeUnsigned(String),
     ExecuteSignedTx(SuiTransactionResponse),
+    Ptb(PtbResponse),
+    SponsorTx(String),
+    SponsorSign(SponsorSignResponse),
     NewEnv(SuiEnv),
 }
 
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SponsorSignResponse {
+    /// The sponsored transaction, as reviewed before signing
+    pub transaction: SuiTransactionData,
+    /// Base64 encoded sponsor signature `flag || signature || pubkey`
+    pub sponsor_signature: String,
+    /// The execution result if the transaction was executed
+    pub response: Option<SuiTransactionResponse>,
+}
+
+impl Display for SponsorSignResponse {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // The transaction itself is printed before it is signed.
+        let mut writer = String::new();
+        writeln!(
+            writer,
+            "Sponsor signature (`flag || sig || pk` in Base64): {}",
+            self.sponsor_signature
+        )?;
+        if let Some(response) = &self.response {
+            write!(writer, "{}", write_transaction_response(response)?)?;
+        }
+        write!(f, "{}", writer)
+    }
+}
+
 #[derive(Serialize, Clone, Debug)]
 pub struct SwitchResponse {
     /// Active address
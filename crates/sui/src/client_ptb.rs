@@ -0,0 +1,367 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Commands of a programmable transaction composed with `sui client ptb`. Commands are JSON
+//! objects, for example:
+//!
+//! ```json
+//! [
+//!   {"splitCoin": {"coin": "gas", "amount": 1000}},
+//!   {"moveCall": {"package": "0x2", "module": "pay", "function": "join", "typeArguments": ["0x2::sui::SUI"], "arguments": ["0x..", "$0"]}},
+//!   {"transferObjects": {"objects": ["0x.."], "recipient": "0x.."}}
+//! ]
+//! ```
+//!
+//! An argument is either a JSON value, resolved like the arguments of `sui client call`, `"gas"`
+//! for the gas coin, `"$i"` for the result of the `i`-th command, or `"$i.j"` for the `j`-th value
+//! returned by the `i`-th command.
+
+use std::fmt::{self, Display, Formatter, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, ensure};
+use colored::Colorize;
+use fastcrypto::encoding::{Base64, Encoding};
+use move_core_types::value::MoveTypeLayout;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sui_json::SuiJsonValue;
+use sui_json_rpc_types::{
+    DevInspectResults, DryRunTransactionResponse, SuiCommand, SuiTransactionResponse, SuiTypeTag,
+};
+use sui_sdk::SuiClient;
+use sui_transaction_builder::ProgrammableArg;
+use sui_types::base_types::ObjectID;
+use sui_types::messages::{Argument, CallArg, Command, ProgrammableTransaction};
+use sui_types::parse_sui_type_tag;
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+
+use crate::client_commands::{convert_number_to_string, write_transaction_response};
+
+const GAS_COIN_ARG: &str = "gas";
+const RESULT_ARG_PREFIX: char = '$';
+
+/// A command of a programmable transaction, referring to the results of earlier commands by their
+/// position in the list of commands.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PtbCommand {
+    #[serde(rename_all = "camelCase")]
+    MoveCall {
+        package: ObjectID,
+        module: String,
+        function: String,
+        #[serde(default)]
+        type_arguments: Vec<String>,
+        #[serde(default)]
+        arguments: Vec<PtbArg>,
+    },
+    TransferObjects {
+        objects: Vec<PtbArg>,
+        recipient: PtbArg,
+    },
+    SplitCoin {
+        coin: PtbArg,
+        amount: PtbArg,
+    },
+    MergeCoins {
+        coin: PtbArg,
+        coins: Vec<PtbArg>,
+    },
+    /// Objects or results gathered in a vector, `type` is required if the vector is empty.
+    MakeMoveVec {
+        #[serde(default, rename = "type")]
+        type_: Option<String>,
+        elements: Vec<PtbArg>,
+    },
+}
+
+/// An argument of a [PtbCommand].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "Value", into = "Value")]
+pub enum PtbArg {
+    GasCoin,
+    Result(u16),
+    NestedResult(u16, u16),
+    Value(SuiJsonValue),
+}
+
+impl TryFrom<Value> for PtbArg {
+    type Error = anyhow::Error;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        let s = match &value {
+            Value::String(s) => s,
+            // Numbers are passed as strings, like the arguments of `sui client call`.
+            _ => {
+                return Ok(Self::Value(SuiJsonValue::new(convert_number_to_string(
+                    value,
+                ))?))
+            }
+        };
+        if s == GAS_COIN_ARG {
+            return Ok(Self::GasCoin);
+        }
+        let result = match s.strip_prefix(RESULT_ARG_PREFIX) {
+            Some(result) => result,
+            None => return Ok(Self::Value(SuiJsonValue::new(value)?)),
+        };
+        let parse =
+            |i: &str| u16::from_str(i).map_err(|e| anyhow!("Invalid result argument [{s}]: {e}"));
+        Ok(match result.split_once('.') {
+            Some((command, value)) => Self::NestedResult(parse(command)?, parse(value)?),
+            None => Self::Result(parse(result)?),
+        })
+    }
+}
+
+impl From<PtbArg> for Value {
+    fn from(arg: PtbArg) -> Self {
+        match arg {
+            PtbArg::GasCoin => Value::String(GAS_COIN_ARG.to_string()),
+            PtbArg::Result(i) => Value::String(format!("{RESULT_ARG_PREFIX}{i}")),
+            PtbArg::NestedResult(i, j) => Value::String(format!("{RESULT_ARG_PREFIX}{i}.{j}")),
+            PtbArg::Value(value) => value.to_json_value(),
+        }
+    }
+}
+
+/// Reads the commands from `file`, a JSON array of commands, followed by `commands`, each a JSON
+/// command.
+pub fn parse_ptb_commands(
+    file: Option<&Path>,
+    commands: &[String],
+) -> Result<Vec<PtbCommand>, anyhow::Error> {
+    let mut parsed: Vec<PtbCommand> = match file {
+        Some(file) => serde_json::from_str(&std::fs::read_to_string(file)?)
+            .map_err(|e| anyhow!("Cannot parse commands in {}: {e}", file.display()))?,
+        None => vec![],
+    };
+    for command in commands {
+        parsed.push(
+            serde_json::from_str(command)
+                .map_err(|e| anyhow!("Cannot parse command [{command}]: {e}"))?,
+        );
+    }
+    ensure!(!parsed.is_empty(), "No commands to execute");
+    Ok(parsed)
+}
+
+/// Builds the programmable transaction running `commands` in order. Also returns the position in
+/// the transaction of the command added for each of `commands`, as some commands are preceded by
+/// the commands building their arguments.
+pub async fn build_ptb(
+    client: &SuiClient,
+    commands: Vec<PtbCommand>,
+) -> Result<(ProgrammableTransaction, Vec<u16>), anyhow::Error> {
+    let mut builder = ProgrammableTransactionBuilder::new();
+    // A command can add several commands to the transaction, e.g. a vector of objects passed to a
+    // Move call, so results are looked up by the position of the command in `commands`.
+    let mut results = Vec::with_capacity(commands.len());
+    for (i, command) in commands.into_iter().enumerate() {
+        let mut resolver = ArgResolver {
+            client,
+            builder: &mut builder,
+            results: &results,
+        };
+        let result = match command {
+            PtbCommand::MoveCall {
+                package,
+                module,
+                function,
+                type_arguments,
+                arguments,
+            } => {
+                let type_args = type_arguments
+                    .iter()
+                    .map(|t| parse_sui_type_tag(t).map(SuiTypeTag::from))
+                    .collect::<Result<_, _>>()?;
+                let mut args = Vec::with_capacity(arguments.len());
+                for arg in arguments {
+                    args.push(match resolver.argument(arg)? {
+                        Ok(arg) => ProgrammableArg::Argument(arg),
+                        Err(value) => ProgrammableArg::Json(value),
+                    });
+                }
+                client
+                    .transaction_builder()
+                    .programmable_move_call(
+                        &mut builder,
+                        package,
+                        &module,
+                        &function,
+                        type_args,
+                        args,
+                    )
+                    .await?
+            }
+            PtbCommand::TransferObjects { objects, recipient } => {
+                let objects = resolver.objects(objects).await?;
+                let recipient = match resolver.argument(recipient)? {
+                    Ok(arg) => arg,
+                    Err(value) => resolver.builder.pure(value.to_sui_address()?)?,
+                };
+                builder.command(Command::TransferObjects(objects, recipient))
+            }
+            PtbCommand::SplitCoin { coin, amount } => {
+                let coin = resolver.object(coin).await?;
+                let amount = resolver.pure(amount, &MoveTypeLayout::U64)?;
+                builder.command(Command::SplitCoin(coin, amount))
+            }
+            PtbCommand::MergeCoins { coin, coins } => {
+                let coin = resolver.object(coin).await?;
+                let coins = resolver.objects(coins).await?;
+                builder.command(Command::MergeCoins(coin, coins))
+            }
+            PtbCommand::MakeMoveVec { type_, elements } => {
+                let type_ = type_.as_deref().map(parse_sui_type_tag).transpose()?;
+                ensure!(
+                    type_.is_some() || !elements.is_empty(),
+                    "Command {i}: the type of an empty vector is required"
+                );
+                let elements = resolver.objects(elements).await?;
+                builder.command(Command::MakeMoveVec(type_, elements))
+            }
+        };
+        results.push(result);
+    }
+    let command_indices = results
+        .into_iter()
+        .map(|result| match result {
+            Argument::Result(i) => i,
+            _ => unreachable!("Commands always return a result"),
+        })
+        .collect();
+    Ok((builder.finish(), command_indices))
+}
+
+struct ArgResolver<'a> {
+    client: &'a SuiClient,
+    builder: &'a mut ProgrammableTransactionBuilder,
+    /// The result of each command added so far.
+    results: &'a [Argument],
+}
+
+impl<'a> ArgResolver<'a> {
+    /// Returns the [Argument] for the gas coin or a result, or the value to be resolved otherwise.
+    fn argument(&self, arg: PtbArg) -> Result<Result<Argument, SuiJsonValue>, anyhow::Error> {
+        let result = |i: u16| {
+            self.results.get(i as usize).copied().ok_or_else(|| {
+                anyhow!(
+                    "Command {} cannot use the result of command {i}, only of earlier commands",
+                    self.results.len()
+                )
+            })
+        };
+        Ok(Ok(match arg {
+            PtbArg::GasCoin => Argument::GasCoin,
+            PtbArg::Result(i) => result(i)?,
+            PtbArg::NestedResult(i, j) => match result(i)? {
+                Argument::Result(command) => Argument::NestedResult(command, j),
+                _ => bail!("Command {i} does not return multiple values"),
+            },
+            PtbArg::Value(value) => return Ok(Err(value)),
+        }))
+    }
+
+    async fn object(&mut self, arg: PtbArg) -> Result<Argument, anyhow::Error> {
+        match self.argument(arg)? {
+            Ok(arg) => Ok(arg),
+            Err(value) => {
+                let id = ObjectID::from_str(
+                    value
+                        .to_json_value()
+                        .as_str()
+                        .ok_or_else(|| anyhow!("Expected an object ID, found {:?}", value))?,
+                )?;
+                // Objects passed to commands other than Move calls are taken by value or mutated.
+                self.client
+                    .transaction_builder()
+                    .input_object(self.builder, id, true)
+                    .await
+            }
+        }
+    }
+
+    async fn objects(&mut self, args: Vec<PtbArg>) -> Result<Vec<Argument>, anyhow::Error> {
+        let mut objects = Vec::with_capacity(args.len());
+        for arg in args {
+            objects.push(self.object(arg).await?);
+        }
+        Ok(objects)
+    }
+
+    fn pure(&mut self, arg: PtbArg, layout: &MoveTypeLayout) -> Result<Argument, anyhow::Error> {
+        match self.argument(arg)? {
+            Ok(arg) => Ok(arg),
+            Err(value) => self
+                .builder
+                .input(CallArg::Pure(value.to_bcs_bytes(layout)?)),
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PtbResponse {
+    /// The commands of the programmable transaction
+    pub commands: Vec<SuiCommand>,
+    /// The position in `commands` of the command added for each command given to `sui client ptb`
+    pub command_indices: Vec<u16>,
+    /// The effects of the transaction if it was dry run
+    pub dry_run: Option<DryRunTransactionResponse>,
+    /// The values returned by each command if the transaction was dry run
+    pub dev_inspect: Option<DevInspectResults>,
+    /// The execution result if the transaction was executed
+    pub response: Option<SuiTransactionResponse>,
+}
+
+impl Display for PtbResponse {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut writer = String::new();
+        writeln!(writer, "{}", "----- Commands ----".bold())?;
+        let results = self
+            .dev_inspect
+            .as_ref()
+            .and_then(|inspect| inspect.results.as_ref().ok());
+        // Commands are listed by their position in the commands given by the user, with the
+        // commands added to build their arguments, so results are referred to as in the
+        // transaction.
+        let mut start = 0;
+        for (i, &index) in self.command_indices.iter().enumerate() {
+            let index = index as usize;
+            writeln!(writer, "[{i}] {}", self.commands[index])?;
+            if index != i {
+                writeln!(writer, "    Transaction command {index}")?;
+            }
+            for (j, command) in self.commands[start..index].iter().enumerate() {
+                writeln!(writer, "    Transaction command {}: {command}", start + j)?;
+            }
+            start = index + 1;
+            if let Some(result) = results.and_then(|results| results.get(index)) {
+                for (value, type_) in &result.return_values {
+                    writeln!(writer, "    Returned {type_}: {}", Base64::encode(value))?;
+                }
+                for (arg, value, type_) in &result.mutable_reference_outputs {
+                    writeln!(
+                        writer,
+                        "    Mutated {arg} {type_}: {}",
+                        Base64::encode(value)
+                    )?;
+                }
+            }
+        }
+        if let Some(Err(e)) = self.dev_inspect.as_ref().map(|inspect| &inspect.results) {
+            writeln!(writer, "Execution error: {e}")?;
+        }
+        if let Some(dry_run) = &self.dry_run {
+            writeln!(writer, "{}", "----- Dry Run Effects ----".bold())?;
+            write!(writer, "{}", dry_run.effects)?;
+        }
+        if let Some(response) = &self.response {
+            write!(writer, "{}", write_transaction_response(response)?)?;
+        }
+        write!(f, "{}", writer.trim_end_matches('\n'))
+    }
+}
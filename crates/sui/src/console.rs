@@ -129,7 +129,9 @@ async fn handle_command(
             SuiClientCommandResult::Addresses(ref addresses, _) => {
                 let addresses = addresses
                     .iter()
-                    .map(|addr| format!("{addr}"))
+                    .flat_map(|(addr, alias)| {
+                        std::iter::once(format!("{addr}")).chain(alias.clone())
+                    })
                     .collect::<Vec<_>>();
                 cache.insert(CacheKey::flag("--address"), addresses.clone());
                 cache.insert(CacheKey::flag("--to"), addresses);
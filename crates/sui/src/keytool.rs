@@ -3,12 +3,13 @@
 use anyhow::anyhow;
 use bip32::DerivationPath;
 use clap::*;
-use fastcrypto::encoding::{decode_bytes_hex, Base64, Encoding};
+use fastcrypto::encoding::{Base64, Encoding};
 use fastcrypto::traits::KeyPair;
 use shared_crypto::intent::{Intent, IntentMessage};
 use std::fs;
 use std::path::{Path, PathBuf};
 use sui_keys::key_derive::generate_new_key;
+use sui_keys::key_identity::KeyIdentity;
 use sui_keys::keypair_file::{
     read_authority_keypair_from_file, read_keypair_from_file, write_authority_keypair_to_file,
     write_keypair_to_file,
@@ -19,7 +20,9 @@ use sui_types::multisig::{MultiSig, MultiSigPublicKey, ThresholdUnit, WeightUnit
 use sui_types::signature::GenericSignature;
 use tracing::info;
 
-use sui_keys::keystore::{AccountKeystore, Keystore};
+use sui_keys::keystore::{
+    AccountKeystore, EncryptedFileKeystore, Keystore, SUI_KEYSTORE_PASSWORD_ENV,
+};
 use sui_types::base_types::SuiAddress;
 use sui_types::crypto::{get_authority_key_pair, EncodeDecodeBase64, SignatureScheme, SuiKeyPair};
 #[cfg(test)]
@@ -49,9 +52,16 @@ pub enum KeyToolCommand {
     /// outputs the keypair into a file at the current directory, and prints out its Sui
     /// address, Base64 encoded public key, and the key scheme flag.
     Unpack { keypair: SuiKeyPair },
-    /// List all keys by its Sui address, Base64 encoded public key, key scheme name in
+    /// List all keys by its Sui address, alias, Base64 encoded public key, key scheme name in
     /// sui.keystore.
     List,
+    /// Attach a human readable alias to an address in sui.keystore. The alias can be used in
+    /// place of the address in `sui client` commands.
+    SetAlias {
+        /// The address or current alias of the key.
+        address: KeyIdentity,
+        alias: String,
+    },
     /// Create signature using the private key for for the given address in sui keystore.
     /// Any signature commits to a [struct IntentMessage] consisting of the Base64 encoded
     /// of the BCS serialized transaction bytes itself (the result of
@@ -59,8 +69,8 @@ pub enum KeyToolCommand {
     /// intent is absent, default will be used. See [struct IntentMessage] and [struct Intent]
     /// for more details.
     Sign {
-        #[clap(long, parse(try_from_str = decode_bytes_hex))]
-        address: SuiAddress,
+        #[clap(long)]
+        address: KeyIdentity,
         #[clap(long)]
         data: String,
         #[clap(long)]
@@ -104,6 +114,11 @@ pub enum KeyToolCommand {
         #[clap(long)]
         threshold: ThresholdUnit,
     },
+
+    /// Encrypt the plaintext sui.keystore in place with a passphrase, the client config using
+    /// the keystore is switched to the encrypted keystore. The passphrase is read from the
+    /// `SUI_KEYSTORE_PASSWORD` environment variable, or prompted for if not set.
+    Encrypt,
 }
 
 impl KeyToolCommand {
@@ -156,24 +171,32 @@ impl KeyToolCommand {
             }
             KeyToolCommand::List => {
                 println!(
-                    " {0: ^42} | {1: ^45} | {2: ^6}",
-                    "Sui Address", "Public Key (Base64)", "Scheme"
+                    " {0: ^66} | {1: ^16} | {2: ^45} | {3: ^6}",
+                    "Sui Address", "Alias", "Public Key (Base64)", "Scheme"
                 );
-                println!("{}", ["-"; 100].join(""));
+                println!("{}", ["-"; 145].join(""));
                 for pub_key in keystore.keys() {
+                    let address = Into::<SuiAddress>::into(&pub_key);
                     println!(
-                        " {0: ^42} | {1: ^45} | {2: ^6}",
-                        Into::<SuiAddress>::into(&pub_key),
+                        " {0: ^66} | {1: ^16} | {2: ^45} | {3: ^6}",
+                        address,
+                        keystore.get_alias(&address).unwrap_or(""),
                         pub_key.encode_base64(),
                         pub_key.scheme().to_string()
                     );
                 }
             }
+            KeyToolCommand::SetAlias { address, alias } => {
+                let address = keystore.get_address(&address)?;
+                keystore.set_alias(&address, alias.clone())?;
+                println!("Alias [{alias}] set for address [{address}]");
+            }
             KeyToolCommand::Sign {
                 address,
                 data,
                 intent,
             } => {
+                let address = keystore.get_address(&address)?;
                 println!("Signer address: {}", address);
                 println!("Raw tx_bytes to execute: {}", data);
                 let intent = intent.unwrap_or_default();
@@ -265,6 +288,19 @@ impl KeyToolCommand {
                 println!("MultiSig parsed: {:?}", generic_sig);
                 println!("MultiSig serialized: {:?}", generic_sig.encode_base64());
             }
+            KeyToolCommand::Encrypt => {
+                if !matches!(keystore, Keystore::File(_)) {
+                    return Err(anyhow!("Only plaintext file keystores can be encrypted"));
+                }
+                let password = read_keystore_password(true)?;
+                if let Keystore::File(file) =
+                    std::mem::replace(keystore, Keystore::InMem(Default::default()))
+                {
+                    let encrypted = EncryptedFileKeystore::from_file_keystore(file, &password)?;
+                    println!("Keystore encrypted at {:?}", encrypted.path());
+                    *keystore = Keystore::Encrypted(encrypted);
+                }
+            }
         }
 
         Ok(())
@@ -284,3 +320,17 @@ fn store_and_print_keypair(address: SuiAddress, keypair: SuiKeyPair) {
         path.to_str().unwrap()
     );
 }
+
+/// Reads the keystore passphrase from the `SUI_KEYSTORE_PASSWORD` environment variable, or prompts
+/// for it on the terminal without echoing it. A prompted passphrase has to be entered twice if
+/// `confirm` is set.
+pub fn read_keystore_password(confirm: bool) -> Result<String, anyhow::Error> {
+    if let Ok(password) = std::env::var(SUI_KEYSTORE_PASSWORD_ENV) {
+        return Ok(password);
+    }
+    let password = rpassword::prompt_password("Enter keystore passphrase: ")?;
+    if confirm && rpassword::prompt_password("Confirm keystore passphrase: ")? != password {
+        return Err(anyhow!("Passphrases do not match"));
+    }
+    Ok(password)
+}
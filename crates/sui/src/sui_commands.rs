@@ -19,15 +19,16 @@ use sui_config::{
     sui_config_dir, Config, PersistedConfig, FULL_NODE_DB_PATH, SUI_CLIENT_CONFIG,
     SUI_FULLNODE_CONFIG, SUI_NETWORK_CONFIG,
 };
-use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
+use sui_keys::keystore::{AccountKeystore, EncryptedFileKeystore, FileBasedKeystore, Keystore};
 use sui_swarm::memory::Swarm;
+use sui_types::committee::EpochId;
 use sui_types::crypto::{SignatureScheme, SuiKeyPair};
 
 use crate::client_commands::{SuiClientCommands, WalletContext};
 use crate::config::{SuiClientConfig, SuiEnv};
 use crate::console::start_console;
 use crate::genesis_ceremony::{run, Ceremony};
-use crate::keytool::KeyToolCommand;
+use crate::keytool::{read_keystore_password, KeyToolCommand};
 use sui_move::{self, execute_move_command};
 
 #[allow(clippy::large_enum_variant)]
@@ -96,6 +97,13 @@ pub enum SuiCommand {
         json: bool,
         #[clap(short = 'y', long = "yes")]
         accept_defaults: bool,
+        /// Transactions built by the command are rejected by validators after the end of this epoch.
+        #[clap(long, global = true)]
+        expiration_epoch: Option<EpochId>,
+        /// Print the Base64 encoded transaction data built by the command instead of signing and
+        /// executing it, to sign it offline with `sui keytool sign`.
+        #[clap(long, global = true)]
+        serialize_unsigned: bool,
     },
 
     /// Tool to build and test Move applications.
@@ -213,13 +221,24 @@ impl SuiCommand {
             SuiCommand::KeyTool { keystore_path, cmd } => {
                 let keystore_path =
                     keystore_path.unwrap_or(sui_config_dir()?.join(SUI_KEYSTORE_FILENAME));
-                let mut keystore = Keystore::from(FileBasedKeystore::new(&keystore_path)?);
-                cmd.execute(&mut keystore)
+                let mut keystore = if EncryptedFileKeystore::is_encrypted(&keystore_path) {
+                    Keystore::from(EncryptedFileKeystore::new(&keystore_path)?)
+                } else {
+                    Keystore::from(FileBasedKeystore::new(&keystore_path)?)
+                };
+                prompt_for_passphrase(&mut keystore);
+                let encrypt = matches!(cmd, KeyToolCommand::Encrypt);
+                cmd.execute(&mut keystore)?;
+                if encrypt {
+                    use_encrypted_keystore(&keystore_path)?;
+                }
+                Ok(())
             }
             SuiCommand::Console { config } => {
                 let config = config.unwrap_or(sui_config_dir()?.join(SUI_CLIENT_CONFIG));
                 prompt_if_no_config(&config, false).await?;
-                let context = WalletContext::new(&config, None).await?;
+                let mut context = WalletContext::new(&config, None).await?;
+                prompt_for_passphrase(&mut context.config.keystore);
                 start_console(context, &mut stdout(), &mut stderr()).await
             }
             SuiCommand::Client {
@@ -227,10 +246,15 @@ impl SuiCommand {
                 cmd,
                 json,
                 accept_defaults,
+                expiration_epoch,
+                serialize_unsigned,
             } => {
                 let config_path = config.unwrap_or(sui_config_dir()?.join(SUI_CLIENT_CONFIG));
                 prompt_if_no_config(&config_path, accept_defaults).await?;
                 let mut context = WalletContext::new(&config_path, None).await?;
+                context.expiration_epoch = expiration_epoch;
+                context.serialize_unsigned = serialize_unsigned;
+                prompt_for_passphrase(&mut context.config.keystore);
                 if let Some(cmd) = cmd {
                     cmd.execute(&mut context).await?.print(!json);
                 } else {
@@ -512,3 +536,45 @@ fn read_line() -> Result<String, anyhow::Error> {
     io::stdin().read_line(&mut s)?;
     Ok(s.trim_end().to_string())
 }
+
+/// Lets an encrypted keystore that wasn't unlocked from the environment ask for its passphrase
+/// the first time its keys are used, commands that never sign don't need it.
+fn prompt_for_passphrase(keystore: &mut Keystore) {
+    if let Keystore::Encrypted(keystore) = keystore {
+        keystore.set_passphrase_prompt(Box::new(|| read_keystore_password(false)));
+    }
+}
+
+/// Switches the client config next to `keystore_path` to the encrypted keystore after
+/// `keytool encrypt`. The config is edited as yaml since the plaintext keystore it refers to
+/// can no longer be loaded.
+fn use_encrypted_keystore(keystore_path: &Path) -> Result<(), anyhow::Error> {
+    let config_path = keystore_path
+        .parent()
+        .unwrap_or(&sui_config_dir()?)
+        .join(SUI_CLIENT_CONFIG);
+    if !config_path.exists() {
+        return Ok(());
+    }
+    let mut config: serde_yaml::Value = serde_yaml::from_str(&fs::read_to_string(&config_path)?)?;
+    let uses_keystore = config
+        .get("keystore")
+        .and_then(|keystore| keystore.get("File"))
+        .and_then(|path| path.as_str())
+        .map(Path::new)
+        == Some(keystore_path);
+    if uses_keystore {
+        let mut keystore = serde_yaml::Mapping::new();
+        keystore.insert(
+            "Encrypted".into(),
+            keystore_path.to_string_lossy().to_string().into(),
+        );
+        config["keystore"] = serde_yaml::Value::Mapping(keystore);
+        fs::write(&config_path, serde_yaml::to_string(&config)?)?;
+        println!(
+            "Client config {:?} switched to the encrypted keystore",
+            config_path
+        );
+    }
+    Ok(())
+}
@@ -1,18 +1,20 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{fmt::Write, fs::read_dir, path::PathBuf, str, thread, time::Duration};
+use std::{fmt::Write, fs::read_dir, path::PathBuf, str, str::FromStr, thread, time::Duration};
 
 use anyhow::anyhow;
 use expect_test::expect;
+use fastcrypto::encoding::{Base64, Encoding};
 use move_package::BuildConfig;
 use serde_json::json;
+use shared_crypto::intent::Intent;
 use sui_types::object::Owner;
 use tokio::time::sleep;
 
 use sui::client_commands::SwitchResponse;
 use sui::{
-    client_commands::{SuiClientCommandResult, SuiClientCommands, WalletContext},
+    client_commands::{SuiClientCommandResult, SuiClientCommands, UpgradePolicy, WalletContext},
     config::SuiClientConfig,
     sui_commands::SuiCommand,
 };
@@ -23,17 +25,23 @@ use sui_config::{
 };
 use sui_json::SuiJsonValue;
 use sui_json_rpc_types::{
-    OwnedObjectRef, SuiObjectData, SuiObjectDataOptions, SuiObjectResponse,
-    SuiTransactionEffectsAPI,
+    OwnedObjectRef, SuiExecutionStatus, SuiObjectData, SuiObjectDataOptions, SuiObjectResponse,
+    SuiTransactionDataAPI, SuiTransactionEffectsAPI,
 };
+use sui_keys::key_identity::KeyIdentity;
 use sui_keys::keystore::AccountKeystore;
 use sui_macros::sim_test;
 use sui_types::base_types::{ObjectType, SuiAddress};
 use sui_types::crypto::{
-    Ed25519SuiSignature, Secp256k1SuiSignature, SignatureScheme, SuiKeyPair, SuiSignatureInner,
+    Ed25519SuiSignature, EncodeDecodeBase64, Secp256k1SuiSignature, SignatureScheme, SuiKeyPair,
+    SuiSignatureInner,
 };
+use sui_types::messages::{Command, TransactionData, TransactionDataAPI, TransactionKind};
 use sui_types::{base_types::ObjectID, crypto::get_key_pair, gas_coin::GasCoin};
-use sui_types::{sui_framework_address_concat_string, SUI_FRAMEWORK_ADDRESS};
+use sui_types::{
+    sui_framework_address_concat_string, MOVE_STDLIB_OBJECT_ID, SUI_FRAMEWORK_ADDRESS,
+    SUI_FRAMEWORK_OBJECT_ID,
+};
 use test_utils::messages::make_transactions_with_wallet_context;
 use test_utils::network::TestClusterBuilder;
 
@@ -137,7 +145,7 @@ async fn test_objects_command() -> Result<(), anyhow::Error> {
 
     // Print objects owned by `address`
     SuiClientCommands::Objects {
-        address: Some(address),
+        address: Some(address.into()),
     }
     .execute(context)
     .await?
@@ -159,7 +167,7 @@ async fn test_regression_6546() -> Result<(), anyhow::Error> {
     let context = &mut test_cluster.wallet;
 
     let SuiClientCommandResult::Objects(coins) = SuiClientCommands::Objects {
-        address: Some(address),
+        address: Some(address.into()),
     }
         .execute(context)
         .await? else{
@@ -251,7 +259,7 @@ async fn test_custom_genesis() -> Result<(), anyhow::Error> {
 
     // Print objects owned by `address`
     SuiClientCommands::Objects {
-        address: Some(address),
+        address: Some(address.into()),
     }
     .execute(context)
     .await?
@@ -311,7 +319,7 @@ async fn test_gas_command() -> Result<(), anyhow::Error> {
     let object_to_send = object_refs.get(1).unwrap().object_id;
 
     SuiClientCommands::Gas {
-        address: Some(address),
+        address: Some(address.into()),
     }
     .execute(context)
     .await?
@@ -321,17 +329,17 @@ async fn test_gas_command() -> Result<(), anyhow::Error> {
 
     // Send an object
     SuiClientCommands::Transfer {
-        to: SuiAddress::random_for_testing_only(),
+        to: SuiAddress::random_for_testing_only().into(),
         object_id: object_to_send,
         gas: Some(object_id),
-        gas_budget: 50000,
+        gas_budget: Some(50000),
     }
     .execute(context)
     .await?;
 
     // Fetch gas again
     SuiClientCommands::Gas {
-        address: Some(address),
+        address: Some(address.into()),
     }
     .execute(context)
     .await?
@@ -362,7 +370,7 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         package_path,
         build_config,
         gas: Some(gas_obj_id),
-        gas_budget: 20_000,
+        gas_budget: Some(20_000),
         skip_dependency_verification: false,
         with_unpublished_dependencies: false,
     }
@@ -390,7 +398,7 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
 
     // Print objects owned by `address1`
     SuiClientCommands::Objects {
-        address: Some(address1),
+        address: Some(address1.into()),
     }
     .execute(context)
     .await?
@@ -427,7 +435,7 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         type_args: vec![],
         args,
         gas: None,
-        gas_budget: 20_000,
+        gas_budget: Some(20_000),
     }
     .execute(context)
     .await?;
@@ -464,7 +472,7 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         type_args: vec![],
         args: args.to_vec(),
         gas: Some(gas),
-        gas_budget: 20_000,
+        gas_budget: Some(20_000),
     }
     .execute(context)
     .await;
@@ -488,7 +496,7 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         type_args: vec![],
         args: args.to_vec(),
         gas: Some(gas),
-        gas_budget: 20_000,
+        gas_budget: Some(20_000),
     }
     .execute(context)
     .await;
@@ -513,7 +521,7 @@ async fn test_move_call_args_linter_command() -> Result<(), anyhow::Error> {
         type_args: vec![],
         args: args.to_vec(),
         gas: Some(gas),
-        gas_budget: 20_000,
+        gas_budget: Some(20_000),
     }
     .execute(context)
     .await?;
@@ -544,7 +552,7 @@ async fn test_package_publish_command() -> Result<(), anyhow::Error> {
         package_path,
         build_config,
         gas: Some(gas_obj_id),
-        gas_budget: 20_000,
+        gas_budget: Some(20_000),
         skip_dependency_verification: false,
         with_unpublished_dependencies: false,
     }
@@ -575,6 +583,101 @@ async fn test_package_publish_command() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_package_upgrade_command() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await?;
+    let address = test_cluster.get_address_0();
+    let context = &mut test_cluster.wallet;
+
+    let mut package_path = PathBuf::from(TEST_DATA_DIR);
+    package_path.push("dummy_modules_publish");
+    SuiClientCommands::Publish {
+        package_path: package_path.clone(),
+        build_config: BuildConfig::default(),
+        gas: None,
+        gas_budget: Some(20_000),
+        skip_dependency_verification: false,
+        with_unpublished_dependencies: false,
+    }
+    .execute(context)
+    .await?;
+
+    let client = context.get_client().await?;
+    let upgrade_capability = client
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?
+        .into_iter()
+        .find(|object| object.type_ == "0x2::package::UpgradeCap")
+        .unwrap()
+        .object_id;
+
+    // The upgrade depends on the packages published at the addresses recorded for its
+    // dependencies, the standard library and the framework.
+    context.serialize_unsigned = true;
+    let result = SuiClientCommands::Upgrade {
+        package_path: package_path.clone(),
+        upgrade_capability,
+        policy: UpgradePolicy::Compatible,
+        build_config: BuildConfig::default(),
+        gas: None,
+        gas_budget: Some(20_000),
+        skip_dependency_verification: false,
+        with_unpublished_dependencies: false,
+    }
+    .execute(context)
+    .await?;
+    context.serialize_unsigned = false;
+    let SuiClientCommandResult::SerializeUnsigned(tx_bytes) = result else {
+        panic!("Upgrade command did not serialize the transaction")
+    };
+    let data: TransactionData = bcs::from_bytes(&Base64::decode(&tx_bytes).unwrap())?;
+    let TransactionKind::ProgrammableTransaction(pt) = data.kind() else {
+        panic!("Upgrade transaction is not programmable")
+    };
+    let dep_ids = pt
+        .commands
+        .iter()
+        .find_map(|command| match command {
+            Command::Upgrade(_, dep_ids, _) => Some(dep_ids.clone()),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(
+        dep_ids,
+        vec![MOVE_STDLIB_OBJECT_ID, SUI_FRAMEWORK_OBJECT_ID]
+    );
+
+    let resp = SuiClientCommands::Upgrade {
+        package_path,
+        upgrade_capability,
+        policy: UpgradePolicy::Compatible,
+        build_config: BuildConfig::default(),
+        gas: None,
+        gas_budget: Some(20_000),
+        skip_dependency_verification: false,
+        with_unpublished_dependencies: false,
+    }
+    .execute(context)
+    .await?;
+
+    let response = if let SuiClientCommandResult::Upgrade(response) = resp {
+        response
+    } else {
+        unreachable!("Invalid response");
+    };
+    // The upgrade is authorized by the cap, but executing upgrades is not enabled by the protocol
+    // yet.
+    match response.effects.unwrap().status() {
+        SuiExecutionStatus::Failure { error } => {
+            assert!(error.contains("FeatureNotYetSupported"), "{error}")
+        }
+        status => panic!("Unexpected upgrade status: {status:?}"),
+    }
+
+    Ok(())
+}
+
 #[sim_test]
 async fn test_package_publish_command_with_unpublished_dependency_succeeds(
 ) -> Result<(), anyhow::Error> {
@@ -599,7 +702,7 @@ async fn test_package_publish_command_with_unpublished_dependency_succeeds(
         package_path,
         build_config,
         gas: Some(gas_obj_id),
-        gas_budget: 20_000,
+        gas_budget: Some(20_000),
         skip_dependency_verification: false,
         with_unpublished_dependencies,
     }
@@ -654,7 +757,7 @@ async fn test_package_publish_command_with_unpublished_dependency_fails(
         package_path,
         build_config,
         gas: Some(gas_obj_id),
-        gas_budget: 20_000,
+        gas_budget: Some(20_000),
         skip_dependency_verification: false,
         with_unpublished_dependencies,
     }
@@ -695,7 +798,7 @@ async fn test_package_publish_command_failure_invalid() -> Result<(), anyhow::Er
         package_path,
         build_config,
         gas: Some(gas_obj_id),
-        gas_budget: 20_000,
+        gas_budget: Some(20_000),
         skip_dependency_verification: false,
         with_unpublished_dependencies,
     }
@@ -732,9 +835,9 @@ async fn test_native_transfer() -> Result<(), anyhow::Error> {
 
     let resp = SuiClientCommands::Transfer {
         gas: Some(gas_obj_id),
-        to: recipient,
+        to: recipient.into(),
         object_id: obj_id,
-        gas_budget: 50000,
+        gas_budget: Some(50000),
     }
     .execute(context)
     .await?;
@@ -812,9 +915,9 @@ async fn test_native_transfer() -> Result<(), anyhow::Error> {
 
     let resp = SuiClientCommands::Transfer {
         gas: None,
-        to: recipient,
+        to: recipient.into(),
         object_id: obj_id,
-        gas_budget: 50000,
+        gas_budget: Some(50000),
     }
     .execute(context)
     .await?;
@@ -851,6 +954,45 @@ async fn test_native_transfer() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_transfer_with_estimated_gas_budget() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await?;
+    let address = test_cluster.get_address_0();
+    let context = &mut test_cluster.wallet;
+
+    let recipient = SuiAddress::random_for_testing_only();
+    let client = context.get_client().await?;
+    let object_refs = client
+        .read_api()
+        .get_objects_owned_by_address(address)
+        .await?;
+    let obj_id = object_refs.first().unwrap().object_id;
+
+    // Without a gas budget the transaction is dry run to estimate it.
+    let resp = SuiClientCommands::Transfer {
+        gas: None,
+        to: recipient.into(),
+        object_id: obj_id,
+        gas_budget: None,
+    }
+    .execute(context)
+    .await?;
+
+    let response = if let SuiClientCommandResult::Transfer(_, response) = resp {
+        response
+    } else {
+        panic!()
+    };
+    let effects = response.effects.unwrap();
+    assert_eq!(effects.status(), &SuiExecutionStatus::Success);
+    let gas_used = effects.gas_used();
+    assert!(
+        response.transaction.unwrap().data.gas_data().budget
+            >= gas_used.computation_cost + gas_used.storage_cost
+    );
+    Ok(())
+}
+
 #[test]
 // Test for issue https://github.com/MystenLabs/sui/issues/1078
 fn test_bug_1078() {
@@ -894,7 +1036,7 @@ async fn test_switch_command() -> Result<(), anyhow::Error> {
 
     // Switch the address
     let resp = SuiClientCommands::Switch {
-        address: Some(addr2),
+        address: Some(addr2.into()),
         env: None,
     }
     .execute(context)
@@ -919,6 +1061,7 @@ async fn test_switch_command() -> Result<(), anyhow::Error> {
     let os = SuiClientCommands::NewAddress {
         key_scheme: SignatureScheme::ED25519,
         derivation_path: None,
+        alias: None,
     }
     .execute(context)
     .await?;
@@ -931,7 +1074,7 @@ async fn test_switch_command() -> Result<(), anyhow::Error> {
     // Check that we can switch to this address
     // Switch the address
     let resp = SuiClientCommands::Switch {
-        address: Some(new_addr),
+        address: Some(new_addr.into()),
         env: None,
     }
     .execute(context)
@@ -970,6 +1113,7 @@ async fn test_new_address_command_by_flag() -> Result<(), anyhow::Error> {
     SuiClientCommands::NewAddress {
         key_scheme: SignatureScheme::Secp256k1,
         derivation_path: None,
+        alias: None,
     }
     .execute(context)
     .await?;
@@ -989,6 +1133,45 @@ async fn test_new_address_command_by_flag() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_address_alias() -> Result<(), anyhow::Error> {
+    let mut cluster = TestClusterBuilder::new().build().await?;
+    let context = cluster.wallet_mut();
+
+    let os = SuiClientCommands::NewAddress {
+        key_scheme: SignatureScheme::ED25519,
+        derivation_path: None,
+        alias: Some("treasury".to_string()),
+    }
+    .execute(context)
+    .await?;
+    let SuiClientCommandResult::NewAddress((treasury, _, _)) = os else {
+        panic!("Command failed")
+    };
+
+    // Switch to the new address by its alias
+    SuiClientCommands::Switch {
+        address: Some(KeyIdentity::from_str("treasury")?),
+        env: None,
+    }
+    .execute(context)
+    .await?;
+    assert_eq!(treasury, context.active_address()?);
+
+    let os = SuiClientCommands::Addresses.execute(context).await?;
+    assert!(format!("{os}").contains(&format!("{treasury} (treasury) <=")));
+
+    // Unknown aliases are rejected
+    assert!(SuiClientCommands::Switch {
+        address: Some(KeyIdentity::from_str("unknown")?),
+        env: None,
+    }
+    .execute(context)
+    .await
+    .is_err());
+    Ok(())
+}
+
 #[sim_test]
 async fn test_active_address_command() -> Result<(), anyhow::Error> {
     let mut cluster = TestClusterBuilder::new().build().await?;
@@ -1009,7 +1192,7 @@ async fn test_active_address_command() -> Result<(), anyhow::Error> {
 
     let addr2 = context.config.keystore.addresses().get(1).cloned().unwrap();
     let resp = SuiClientCommands::Switch {
-        address: Some(addr2),
+        address: Some(addr2.into()),
         env: None,
     }
     .execute(context)
@@ -1078,7 +1261,7 @@ async fn test_merge_coin() -> Result<(), anyhow::Error> {
         primary_coin,
         coin_to_merge,
         gas: Some(gas),
-        gas_budget: 20_000,
+        gas_budget: Some(20_000),
     }
     .execute(context)
     .await?;
@@ -1120,7 +1303,7 @@ async fn test_merge_coin() -> Result<(), anyhow::Error> {
         primary_coin,
         coin_to_merge,
         gas: None,
-        gas_budget: 10_000,
+        gas_budget: Some(10_000),
     }
     .execute(context)
     .await?;
@@ -1170,7 +1353,7 @@ async fn test_split_coin() -> Result<(), anyhow::Error> {
     // Test with gas specified
     let resp = SuiClientCommands::SplitCoin {
         gas: Some(gas),
-        gas_budget: 20_000,
+        gas_budget: Some(20_000),
         coin_id: coin,
         amounts: Some(vec![1000, 10]),
         count: None,
@@ -1223,7 +1406,7 @@ async fn test_split_coin() -> Result<(), anyhow::Error> {
     // Test split coin into equal parts
     let resp = SuiClientCommands::SplitCoin {
         gas: None,
-        gas_budget: 20_000,
+        gas_budget: Some(20_000),
         coin_id: coin,
         amounts: None,
         count: Some(3),
@@ -1279,7 +1462,7 @@ async fn test_split_coin() -> Result<(), anyhow::Error> {
     // Test with no gas specified
     let resp = SuiClientCommands::SplitCoin {
         gas: None,
-        gas_budget: 20_000,
+        gas_budget: Some(20_000),
         coin_id: coin,
         amounts: Some(vec![1000, 10]),
         count: None,
@@ -1368,9 +1551,9 @@ async fn test_serialize_tx() -> Result<(), anyhow::Error> {
     let coin = object_refs.get(1).unwrap().object_id;
 
     SuiClientCommands::SerializeTransferSui {
-        to: address1,
+        to: address1.into(),
         sui_coin_object_id: coin,
-        gas_budget: 1000,
+        gas_budget: Some(1000),
         amount: Some(1),
     }
     .execute(context)
@@ -1378,6 +1561,344 @@ async fn test_serialize_tx() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_serialize_unsigned_and_execute_signed_tx() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await?;
+    let sender = test_cluster.get_address_0();
+    let sponsor = test_cluster.get_address_1();
+    let context = &mut test_cluster.wallet;
+    let client = context.get_client().await?;
+    let object_refs = client
+        .read_api()
+        .get_objects_owned_by_address(sender)
+        .await?;
+    let coin = object_refs.get(1).unwrap().object_id;
+
+    // The command only prints the transaction data to sign.
+    context.serialize_unsigned = true;
+    let result = SuiClientCommands::Transfer {
+        to: sponsor.into(),
+        object_id: coin,
+        gas: None,
+        gas_budget: Some(1000),
+    }
+    .execute(context)
+    .await?;
+    let tx_bytes = if let SuiClientCommandResult::SerializeUnsigned(tx_bytes) = result {
+        tx_bytes
+    } else {
+        panic!("Transfer command did not serialize the transaction")
+    };
+    let data: TransactionData = bcs::from_bytes(&Base64::decode(&tx_bytes).unwrap())?;
+    assert_eq!(data.sender(), sender);
+    context.serialize_unsigned = false;
+
+    // Sponsor the transaction, then sign it offline as `sui keytool sign` does.
+    let result = SuiClientCommands::SponsorTx {
+        tx_bytes,
+        sponsor: Some(sponsor.into()),
+        gas: None,
+        gas_budget: None,
+    }
+    .execute(context)
+    .await?;
+    let tx_bytes = if let SuiClientCommandResult::SponsorTx(tx_bytes) = result {
+        tx_bytes
+    } else {
+        panic!("SponsorTx command failed")
+    };
+    let data: TransactionData = bcs::from_bytes(&Base64::decode(&tx_bytes).unwrap())?;
+    let signatures: Vec<_> = [sender, sponsor]
+        .iter()
+        .map(|address| {
+            context
+                .config
+                .keystore
+                .sign_secure(address, &data, Intent::default())
+                .map(|signature| signature.encode_base64())
+        })
+        .collect::<Result<_, _>>()?;
+
+    // Both the sender and the sponsor have to sign.
+    assert!(SuiClientCommands::ExecuteSignedTx {
+        tx_bytes: tx_bytes.clone(),
+        signatures: signatures[..1].to_vec(),
+    }
+    .execute(context)
+    .await
+    .is_err());
+
+    let result = SuiClientCommands::ExecuteSignedTx {
+        tx_bytes,
+        signatures,
+    }
+    .execute(context)
+    .await?;
+    let response = if let SuiClientCommandResult::ExecuteSignedTx(response) = result {
+        response
+    } else {
+        panic!("ExecuteSignedTx command failed")
+    };
+    let effects = response.effects.unwrap();
+    assert!(effects.status().is_ok());
+    assert_eq!(effects.gas_object().reference.object_id, data.gas()[0].0);
+    Ok(())
+}
+
+#[sim_test]
+async fn test_ptb_command() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await?;
+    let address1 = test_cluster.get_address_1();
+    let context = &mut test_cluster.wallet;
+
+    let commands = vec![
+        json!({"splitCoin": {"coin": "gas", "amount": 1000}}),
+        json!({"splitCoin": {"coin": "gas", "amount": 2000}}),
+        json!({"mergeCoins": {"coin": "$0", "coins": ["$1"]}}),
+        json!({"moveCall": {
+            "package": SUI_FRAMEWORK_OBJECT_ID.to_hex_literal(),
+            "module": "coin",
+            "function": "value",
+            "typeArguments": ["0x2::sui::SUI"],
+            "arguments": ["$0"],
+        }}),
+        json!({"transferObjects": {"objects": ["$0"], "recipient": address1.to_string()}}),
+    ]
+    .into_iter()
+    .map(|command| command.to_string())
+    .collect::<Vec<_>>();
+
+    // A dry run returns the value of each command without executing the transaction.
+    let result = SuiClientCommands::Ptb {
+        file: None,
+        commands: commands.clone(),
+        gas: None,
+        gas_budget: Some(2000),
+        dry_run: true,
+    }
+    .execute(context)
+    .await?;
+    let response = if let SuiClientCommandResult::Ptb(response) = result {
+        response
+    } else {
+        panic!("Ptb command failed")
+    };
+    assert!(response.response.is_none());
+    assert_eq!(response.commands.len(), 5);
+    assert!(response.dry_run.unwrap().effects.status().is_ok());
+    let results = response.dev_inspect.unwrap().results.unwrap();
+    assert_eq!(results[3].return_values[0].0, bcs::to_bytes(&3000u64)?);
+
+    // A dry run has no transaction to sign.
+    context.serialize_unsigned = true;
+    assert!(SuiClientCommands::Ptb {
+        file: None,
+        commands: commands.clone(),
+        gas: None,
+        gas_budget: Some(2000),
+        dry_run: true,
+    }
+    .execute(context)
+    .await
+    .is_err());
+    context.serialize_unsigned = false;
+
+    // A result can only be used by later commands.
+    assert!(SuiClientCommands::Ptb {
+        file: None,
+        commands: vec![
+            json!({"transferObjects": {"objects": ["$0"], "recipient": address1.to_string()}})
+                .to_string()
+        ],
+        gas: None,
+        gas_budget: Some(2000),
+        dry_run: true,
+    }
+    .execute(context)
+    .await
+    .is_err());
+
+    let result = SuiClientCommands::Ptb {
+        file: None,
+        commands,
+        gas: None,
+        gas_budget: Some(2000),
+        dry_run: false,
+    }
+    .execute(context)
+    .await?;
+    let response = if let SuiClientCommandResult::Ptb(response) = result {
+        response
+    } else {
+        panic!("Ptb command failed")
+    };
+    let effects = response.response.unwrap().effects.unwrap();
+    assert!(effects.status().is_ok());
+    assert_eq!(effects.created().len(), 1);
+    assert_eq!(effects.created()[0].owner, Owner::AddressOwner(address1));
+    Ok(())
+}
+
+#[sim_test]
+async fn test_ptb_command_results_follow_user_commands() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await?;
+    let address = test_cluster.get_address_0();
+    let address1 = test_cluster.get_address_1();
+    let context = &mut test_cluster.wallet;
+    let client = context.get_client().await?;
+    let coin = client
+        .coin_read_api()
+        .get_coins(address, None, None, None)
+        .await?
+        .data
+        .remove(0);
+
+    // The vector of coins passed to `join_vec` is built by a command added to the transaction
+    // before the Move call.
+    let commands = vec![
+        json!({"splitCoin": {"coin": "gas", "amount": 1000}}),
+        json!({"moveCall": {
+            "package": SUI_FRAMEWORK_OBJECT_ID.to_hex_literal(),
+            "module": "pay",
+            "function": "join_vec",
+            "typeArguments": ["0x2::sui::SUI"],
+            "arguments": ["$0", [coin.coin_object_id.to_hex_literal()]],
+        }}),
+        json!({"moveCall": {
+            "package": SUI_FRAMEWORK_OBJECT_ID.to_hex_literal(),
+            "module": "coin",
+            "function": "value",
+            "typeArguments": ["0x2::sui::SUI"],
+            "arguments": ["$0"],
+        }}),
+        json!({"transferObjects": {"objects": ["$0"], "recipient": address1.to_string()}}),
+    ]
+    .into_iter()
+    .map(|command| command.to_string())
+    .collect::<Vec<_>>();
+
+    let result = SuiClientCommands::Ptb {
+        file: None,
+        commands,
+        gas: None,
+        gas_budget: Some(2000),
+        dry_run: true,
+    }
+    .execute(context)
+    .await?;
+    let response = if let SuiClientCommandResult::Ptb(response) = result {
+        response
+    } else {
+        panic!("Ptb command failed")
+    };
+    assert_eq!(response.commands.len(), 5);
+    assert_eq!(response.command_indices, vec![0, 2, 3, 4]);
+
+    let output = format!("{response}");
+    assert!(output.contains("[2] MoveCall"), "{output}");
+    assert!(
+        output.contains("Transaction command 1: MakeMoveVec"),
+        "{output}"
+    );
+
+    let results = response.dev_inspect.unwrap().results.unwrap();
+    assert_eq!(
+        results[response.command_indices[2] as usize].return_values[0].0,
+        bcs::to_bytes(&(coin.balance + 1000))?
+    );
+    Ok(())
+}
+
+#[sim_test]
+async fn test_sponsored_transaction() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await?;
+    let sender = test_cluster.get_address_0();
+    let sponsor = test_cluster.get_address_1();
+    let context = &mut test_cluster.wallet;
+    let client = context.get_client().await?;
+    let object_refs = client
+        .read_api()
+        .get_objects_owned_by_address(sender)
+        .await?;
+    let coin = object_refs.get(1).unwrap().object_id;
+
+    // The sender prepares a transaction paying for its own gas, and hands it to the sponsor.
+    let data = client
+        .transaction_builder()
+        .transfer_object(sender, coin, None, 1000, sponsor)
+        .await?;
+    let result = SuiClientCommands::SponsorTx {
+        tx_bytes: Base64::encode(bcs::to_bytes(&data)?),
+        sponsor: Some(sponsor.into()),
+        gas: None,
+        gas_budget: None,
+    }
+    .execute(context)
+    .await?;
+    let tx_bytes = if let SuiClientCommandResult::SponsorTx(tx_bytes) = result {
+        tx_bytes
+    } else {
+        panic!("SponsorTx command failed")
+    };
+    let data: TransactionData = bcs::from_bytes(&Base64::decode(&tx_bytes).unwrap())?;
+    assert_eq!(data.sender(), sender);
+    assert_eq!(data.gas_owner(), sponsor);
+    assert_eq!(data.gas_budget(), 1000);
+
+    // The sponsor rejects a signature that is not the sender's.
+    let sponsor_signature =
+        context
+            .config
+            .keystore
+            .sign_secure(&sponsor, &data, Intent::default())?;
+    assert!(SuiClientCommands::SponsorSign {
+        tx_bytes: tx_bytes.clone(),
+        sender_signature: sponsor_signature.encode_base64(),
+        execute: true,
+    }
+    .execute(context)
+    .await
+    .is_err());
+
+    let sender_signature =
+        context
+            .config
+            .keystore
+            .sign_secure(&sender, &data, Intent::default())?;
+
+    // With --serialize-unsigned, the sponsor signs offline instead.
+    context.serialize_unsigned = true;
+    let result = SuiClientCommands::SponsorSign {
+        tx_bytes: tx_bytes.clone(),
+        sender_signature: sender_signature.encode_base64(),
+        execute: true,
+    }
+    .execute(context)
+    .await?;
+    assert!(matches!(
+        result,
+        SuiClientCommandResult::SerializeUnsigned(serialized) if serialized == tx_bytes
+    ));
+    context.serialize_unsigned = false;
+
+    let result = SuiClientCommands::SponsorSign {
+        tx_bytes,
+        sender_signature: sender_signature.encode_base64(),
+        execute: true,
+    }
+    .execute(context)
+    .await?;
+    let response = if let SuiClientCommandResult::SponsorSign(response) = result {
+        response
+    } else {
+        panic!("SponsorSign command failed")
+    };
+    let effects = response.response.unwrap().effects.unwrap();
+    assert!(effects.status().is_ok());
+    assert_eq!(effects.gas_object().reference.object_id, data.gas()[0].0);
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_stake_with_none_amount() -> Result<(), anyhow::Error> {
     let mut test_cluster = TestClusterBuilder::new().build().await?;
@@ -365,7 +365,7 @@ fn test_sign_command() -> Result<(), anyhow::Error> {
 
     // Sign an intent message for the transaction data and a passed-in intent with scope as PersonalMessage.
     KeyToolCommand::Sign {
-        address: *sender,
+        address: (*sender).into(),
         data: Base64::encode(bcs::to_bytes(&tx_data)?),
         intent: Some(Intent::default().with_scope(IntentScope::PersonalMessage)),
     }
@@ -373,7 +373,7 @@ fn test_sign_command() -> Result<(), anyhow::Error> {
 
     // Sign an intent message for the transaction data without intent passed in, so default is used.
     KeyToolCommand::Sign {
-        address: *sender,
+        address: (*sender).into(),
         data: Base64::encode(bcs::to_bytes(&tx_data)?),
         intent: None,
     }
@@ -14,8 +14,8 @@ use prometheus::Registry;
 use sui::client_commands::{SuiClientCommandResult, SuiClientCommands, WalletContext};
 use sui_json_rpc_types::{
     type_and_fields_from_move_struct, EventPage, SuiEvent, SuiEventEnvelope, SuiEventFilter,
-    SuiExecutionStatus, SuiMoveStruct, SuiMoveValue, SuiTransactionEffectsAPI,
-    SuiTransactionResponse, SuiTransactionResponseOptions,
+    SuiExecutionStatus, SuiMoveStruct, SuiMoveValue, SuiObjectChangeEnvelope,
+    SuiTransactionEffectsAPI, SuiTransactionResponse, SuiTransactionResponseOptions,
 };
 use sui_keys::keystore::AccountKeystore;
 use sui_macros::*;
@@ -32,7 +32,7 @@ use sui_types::messages::{
 };
 use sui_types::object::{Object, ObjectRead, Owner, PastObjectRead};
 use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
-use sui_types::query::{EventQuery, TransactionFilter};
+use sui_types::query::{EventQuery, ObjectChangeFilter, TransactionFilter};
 use sui_types::utils::to_sender_signed_transaction_with_multi_signers;
 use sui_types::{
     base_types::{ObjectID, SuiAddress},
@@ -417,6 +417,88 @@ async fn test_full_node_indexes() -> Result<(), anyhow::Error> {
     assert_eq!(events_by_object[0], sender_event.clone());
     assert_eq!(events_by_object[1], recipient_event.clone());
 
+    // query by composed criteria
+    let events_by_and = node
+        .state()
+        .query_events(
+            EventQuery::And(vec![
+                EventQuery::Sender(sender),
+                EventQuery::Object(transferred_object),
+                EventQuery::Not(Box::new(EventQuery::Recipient(Owner::AddressOwner(
+                    receiver,
+                )))),
+            ]),
+            None,
+            10,
+            true,
+        )
+        .await?;
+    assert_eq!(events_by_and[0].1.tx_digest, digest);
+    assert_eq!(events_by_and[0].1.event, sender_event.clone());
+
+    let events_by_or = node
+        .state()
+        .query_events(
+            EventQuery::Or(vec![
+                EventQuery::Transaction(digest),
+                EventQuery::Object(transferred_object),
+            ]),
+            None,
+            3,
+            true,
+        )
+        .await?;
+    let events_by_or = events_by_or
+        .into_iter()
+        .map(|(_, envelope)| envelope.event)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        events_by_or,
+        vec![
+            recipient_event.clone(),
+            sender_event.clone(),
+            gas_event.clone()
+        ]
+    );
+
+    let events_by_time = node
+        .state()
+        .query_events(
+            EventQuery::And(vec![
+                EventQuery::TimeRange {
+                    start_time: ts.unwrap() - HOUR_MS,
+                    end_time: ts.unwrap() + HOUR_MS,
+                },
+                EventQuery::Not(Box::new(EventQuery::Recipient(Owner::AddressOwner(
+                    receiver,
+                )))),
+            ]),
+            None,
+            100,
+            false,
+        )
+        .await?;
+    let events_by_time = events_by_time[events_by_time.len() - 2..]
+        .iter()
+        .map(|(_, envelope)| envelope.event.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        events_by_time,
+        vec![gas_event.clone(), sender_event.clone()]
+    );
+
+    // composed queries without an indexed criterion are rejected
+    assert!(node
+        .state()
+        .query_events(
+            EventQuery::Not(Box::new(EventQuery::Sender(sender))),
+            None,
+            10,
+            false
+        )
+        .await
+        .is_err());
+
     // query by transaction module
     // Query by module ID
     let events_by_module = node
@@ -518,7 +600,7 @@ async fn test_full_node_sync_flood() -> Result<(), anyhow::Error> {
                         count: None,
                         coin_id: object_to_split.0,
                         gas: Some(gas_object_id),
-                        gas_budget: 50000,
+                        gas_budget: Some(50000),
                     }
                     .execute(context)
                     .await
@@ -669,6 +751,199 @@ async fn test_full_node_sub_and_query_move_event_ok() -> Result<(), anyhow::Erro
     Ok(())
 }
 
+#[sim_test]
+async fn test_full_node_sub_move_event_from_cursor_ok() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new()
+        .enable_fullnode_events()
+        .build()
+        .await?;
+
+    let fullnode = start_fullnode_from_config(
+        test_cluster
+            .fullnode_config_builder()
+            .with_event_store()
+            .build()
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let node = fullnode.sui_node;
+    let ws_client = fullnode.ws_client;
+    let context = &mut test_cluster.wallet;
+    let struct_tag_str = sui_framework_address_concat_string("::devnet_nft::MintNFTEvent");
+
+    // Mint two NFTs before subscribing, the second one should be replayed from the store.
+    let (_, _, first_digest) = create_devnet_nft(context).await?;
+    let (_, _, second_digest) = create_devnet_nft(context).await?;
+    wait_for_all_txes(vec![first_digest, second_digest], node.state().clone()).await;
+
+    let events = node
+        .state()
+        .query_events(
+            EventQuery::MoveEvent(struct_tag_str.clone()),
+            None,
+            10,
+            false,
+        )
+        .await?;
+    assert_eq!(events.len(), 2);
+    let cursor = events[0].0.clone();
+    assert_eq!(cursor.tx_digest, first_digest);
+
+    let mut sub: Subscription<SuiEventEnvelope> = ws_client
+        .subscribe(
+            "sui_subscribeEvent",
+            rpc_params![
+                SuiEventFilter::MoveEventType(struct_tag_str.clone()),
+                Some(cursor)
+            ],
+            "sui_unsubscribeEvent",
+        )
+        .await
+        .unwrap();
+
+    match timeout(Duration::from_secs(5), sub.next()).await {
+        Ok(Some(Ok(envelope))) => assert_eq!(envelope.tx_digest, second_digest),
+        other => panic!("Failed to get replayed SuiEvent, but {:?}", other),
+    }
+
+    // New events are streamed after the replayed ones.
+    let (_, _, third_digest) = create_devnet_nft(context).await?;
+    wait_for_tx(third_digest, node.state().clone()).await;
+    match timeout(Duration::from_secs(5), sub.next()).await {
+        Ok(Some(Ok(envelope))) => assert_eq!(envelope.tx_digest, third_digest),
+        other => panic!("Failed to get live SuiEvent, but {:?}", other),
+    }
+
+    // No duplicates
+    match timeout(Duration::from_secs(5), sub.next()).await {
+        Err(_) => (),
+        other => panic!(
+            "Expect to time out because no new events are coming in. Got {:?}",
+            other
+        ),
+    }
+
+    Ok(())
+}
+
+#[sim_test]
+async fn test_full_node_sub_transaction_ok() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await?;
+
+    let fullnode = start_fullnode_from_config(
+        test_cluster
+            .fullnode_config_builder()
+            .with_event_store()
+            .build()
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let node = fullnode.sui_node;
+    let ws_client = fullnode.ws_client;
+    let context = &mut test_cluster.wallet;
+    let receiver = context.config.keystore.addresses().get(1).cloned().unwrap();
+
+    let mut sub: Subscription<SuiTransactionResponse> = ws_client
+        .subscribe(
+            "sui_subscribeTransaction",
+            rpc_params![
+                TransactionFilter::ToAddress(receiver),
+                SuiTransactionResponseOptions::new().with_effects()
+            ],
+            "sui_unsubscribeTransaction",
+        )
+        .await
+        .unwrap();
+
+    let (transferred_object, _, _, digest, _, _) = transfer_coin(context).await?;
+    wait_for_tx(digest, node.state().clone()).await;
+
+    match timeout(Duration::from_secs(5), sub.next()).await {
+        Ok(Some(Ok(response))) => {
+            assert_eq!(response.digest, digest);
+            let effects = response.effects.unwrap();
+            assert!(effects
+                .mutated()
+                .iter()
+                .any(|o| o.reference.object_id == transferred_object
+                    && o.owner == Owner::AddressOwner(receiver)));
+        }
+        other => panic!("Failed to get SuiTransactionResponse, but {:?}", other),
+    }
+
+    // No more
+    match timeout(Duration::from_secs(5), sub.next()).await {
+        Err(_) => (),
+        other => panic!(
+            "Expect to time out because no new transactions are coming in. Got {:?}",
+            other
+        ),
+    }
+
+    Ok(())
+}
+
+#[sim_test]
+async fn test_full_node_sub_object_changes_ok() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await?;
+
+    let fullnode = start_fullnode_from_config(
+        test_cluster
+            .fullnode_config_builder()
+            .with_event_store()
+            .build()
+            .unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let node = fullnode.sui_node;
+    let ws_client = fullnode.ws_client;
+    let context = &mut test_cluster.wallet;
+    let receiver = context.config.keystore.addresses().get(1).cloned().unwrap();
+
+    let mut sub: Subscription<SuiObjectChangeEnvelope> = ws_client
+        .subscribe(
+            "sui_subscribeObjectChanges",
+            rpc_params![ObjectChangeFilter::Owner(receiver)],
+            "sui_unsubscribeObjectChanges",
+        )
+        .await
+        .unwrap();
+
+    let (transferred_object, sender, _, digest, _, _) = transfer_coin(context).await?;
+    wait_for_tx(digest, node.state().clone()).await;
+
+    match timeout(Duration::from_secs(5), sub.next()).await {
+        Ok(Some(Ok(envelope))) => {
+            assert_eq!(envelope.tx_digest, digest);
+            assert_eq!(envelope.change.object_id(), transferred_object);
+            assert_eq!(
+                envelope.change.owner(),
+                Some(&Owner::AddressOwner(receiver))
+            );
+            assert_eq!(envelope.previous_owner, Some(Owner::AddressOwner(sender)));
+            assert_eq!(envelope.reference.object_id, transferred_object);
+        }
+        other => panic!("Failed to get SuiObjectChangeEnvelope, but {:?}", other),
+    }
+
+    // The gas object of the sender is not owned by the receiver.
+    match timeout(Duration::from_secs(5), sub.next()).await {
+        Err(_) => (),
+        other => panic!(
+            "Expect to time out because no new object changes are coming in. Got {:?}",
+            other
+        ),
+    }
+
+    Ok(())
+}
+
 // Test fullnode has event read jsonrpc endpoints working
 #[sim_test]
 async fn test_full_node_event_read_api_ok() {
@@ -90,6 +90,10 @@ use std::{
     env,
     io::{stderr, Write},
     str::FromStr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
 };
 use tracing::metadata::LevelFilter;
 use tracing::Level;
@@ -138,20 +142,77 @@ pub struct TelemetryGuards {
 }
 
 #[derive(Clone, Debug)]
-pub struct FilterHandle(reload::Handle<EnvFilter, Registry>);
+pub struct FilterHandle {
+    log_filter: reload::Handle<EnvFilter, Registry>,
+    span_sampler: SpanSampler,
+}
 
 impl FilterHandle {
     pub fn update<S: AsRef<str>>(&self, directives: S) -> Result<(), BoxError> {
         let filter = EnvFilter::try_new(directives)?;
-        self.0.reload(filter)?;
+        self.log_filter.reload(filter)?;
         Ok(())
     }
 
     pub fn get(&self) -> Result<String, BoxError> {
-        self.0
+        self.log_filter
             .with_current(|filter| filter.to_string())
             .map_err(Into::into)
     }
+
+    /// The fraction of spans, between 0 and 1, that are sampled for span latency measurements.
+    pub fn get_span_sampling_rate(&self) -> f64 {
+        self.span_sampler.rate()
+    }
+
+    /// Sets the fraction of spans to sample, 0 disables span sampling and 1 samples every span.
+    pub fn set_span_sampling_rate(&self, rate: f64) -> Result<(), BoxError> {
+        if !(0.0..=1.0).contains(&rate) {
+            return Err(format!("Span sampling rate must be between 0 and 1, got {rate}").into());
+        }
+        self.span_sampler.set_rate(rate);
+        Ok(())
+    }
+}
+
+/// Samples a fraction of the spans that pass the span level filter. Samples are spread evenly over
+/// span creations rather than drawn at random.
+#[derive(Clone, Debug)]
+struct SpanSampler {
+    rate_ppm: Arc<AtomicU64>,
+    counter: Arc<AtomicU64>,
+}
+
+impl SpanSampler {
+    const SCALE: u64 = 1_000_000;
+
+    fn new() -> Self {
+        Self {
+            rate_ppm: Arc::new(AtomicU64::new(Self::SCALE)),
+            counter: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    fn rate(&self) -> f64 {
+        self.rate_ppm.load(Ordering::Relaxed) as f64 / Self::SCALE as f64
+    }
+
+    fn set_rate(&self, rate: f64) {
+        self.rate_ppm.store(
+            (rate * Self::SCALE as f64).round() as u64,
+            Ordering::Relaxed,
+        );
+    }
+
+    fn sample(&self) -> bool {
+        let rate = self.rate_ppm.load(Ordering::Relaxed) as u128;
+        if rate >= Self::SCALE as u128 {
+            return true;
+        }
+        let n = self.counter.fetch_add(1, Ordering::Relaxed) as u128;
+        // Sample whenever the expected number of samples so far crosses an integer.
+        (n + 1) * rate / Self::SCALE as u128 > n * rate / Self::SCALE as u128
+    }
 }
 
 fn get_output(log_file: Option<String>) -> (NonBlocking, WorkerGuard) {
@@ -278,14 +339,17 @@ impl TelemetryConfig {
         let env_filter =
             EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(log_level));
         let (log_filter, reload_handle) = reload::Layer::new(env_filter);
-        let filter_handle = FilterHandle(reload_handle);
+        let span_sampler = SpanSampler::new();
+        let filter_handle = FilterHandle {
+            log_filter: reload_handle,
+            span_sampler: span_sampler.clone(),
+        };
 
         // Separate span level filter.
-        // This is a dumb filter for now - allows all spans that are below a given level.
-        // TODO: implement a sampling filter
+        // Allows the sampled fraction of spans that are below a given level.
         let span_level = config.span_level.unwrap_or(Level::INFO);
         let span_filter = filter::filter_fn(move |metadata| {
-            metadata.is_span() && *metadata.level() <= span_level
+            metadata.is_span() && *metadata.level() <= span_level && span_sampler.sample()
         });
 
         let mut layers = Vec::new();
@@ -409,6 +473,18 @@ mod tests {
         panic!("This should cause error logs to be printed out!");
     }
 
+    #[test]
+    fn test_span_sampler() {
+        let sampler = SpanSampler::new();
+        assert!((0..100).all(|_| sampler.sample()));
+
+        sampler.set_rate(0.25);
+        assert_eq!((0..100).filter(|_| sampler.sample()).count(), 25);
+
+        sampler.set_rate(0.0);
+        assert!((0..100).all(|_| !sampler.sample()));
+    }
+
     // Both the following tests should be able to "race" to initialize logging without causing a
     // panic
     #[test]
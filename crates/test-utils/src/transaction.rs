@@ -285,10 +285,10 @@ pub async fn transfer_sui(
         .unwrap();
 
     let res = SuiClientCommands::TransferSui {
-        to: receiver,
+        to: receiver.into(),
         amount: None,
         sui_coin_object_id: gas_ref.0,
-        gas_budget: GAS_BUDGET,
+        gas_budget: Some(GAS_BUDGET),
     }
     .execute(context)
     .await?;
@@ -330,10 +330,10 @@ pub async fn transfer_coin(
         object_to_send, sender, receiver
     );
     let res = SuiClientCommands::Transfer {
-        to: receiver,
+        to: receiver.into(),
         object_id: object_to_send,
         gas: None,
-        gas_budget: GAS_BUDGET,
+        gas_budget: Some(GAS_BUDGET),
     }
     .execute(context)
     .await?;
@@ -371,7 +371,7 @@ pub async fn split_coin_with_wallet_context(context: &mut WalletContext, coin_id
         amounts: None,
         count: Some(2),
         gas: None,
-        gas_budget: MAX_GAS,
+        gas_budget: Some(MAX_GAS),
     }
     .execute(context)
     .await